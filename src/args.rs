@@ -4,14 +4,28 @@
 
 use argh::FromArgs;
 
+use crate::game::Difficulty;
+
 #[derive(FromArgs)]
 #[argh(
     description = "Please, report issues to <https://github.com/veeso/donmaze>
 Please, consider supporting the author <https://ko-fi.com/veeso>"
 )]
 pub struct Args {
+    #[argh(
+        option,
+        short = 'a',
+        description = "autosave every N turns (0 to disable; default 20)"
+    )]
+    pub autosave: Option<u64>,
     #[argh(switch, short = 'D', description = "enable TRACE log level")]
     pub debug: bool,
+    #[argh(
+        option,
+        short = 'd',
+        description = "difficulty selected for new games (easy, normal, hard)"
+    )]
+    pub difficulty: Option<Difficulty>,
     #[argh(switch, short = 'm', description = "play donmaze without audio")]
     pub muted: bool,
     #[argh(switch, short = 'M', description = "disable music")]
@@ -11,6 +11,7 @@ pub enum Sound {
     DrinkPotion,
     EnemyApproaching,
     EnemyAttack,
+    EnemyNearby,
     EnemyScream,
     Error,
     Input,
@@ -32,6 +33,7 @@ impl Sound {
             Sound::DrinkPotion => Self::drink_potion(),
             Sound::EnemyApproaching => Self::enemy_approaching(),
             Sound::EnemyAttack => Self::enemy_attack(),
+            Sound::EnemyNearby => Self::enemy_nearby(),
             Sound::EnemyScream => Self::enemy_scream(),
             Sound::Error => Self::error(),
             Sound::GameSaved => Self::game_saved(),
@@ -98,6 +100,13 @@ impl Sound {
             .tone(100.0, 250, 1.0)
     }
 
+    fn enemy_nearby() -> Track {
+        Track::default()
+            .tone(10.0, 300, 2.0)
+            .tone(0.0, 400, 0.2)
+            .tone(10.0, 300, 2.0)
+    }
+
     fn enemy_scream() -> Track {
         Track::default()
             .tone(100.0, 120, 1.0)
@@ -7,6 +7,7 @@ use super::{Note, Track};
 /// Donmaze theme type
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum Theme {
+    BossFight,
     Fight,
     GameOver,
     Maze,
@@ -18,6 +19,7 @@ pub enum Theme {
 impl Theme {
     pub fn track(self) -> Track {
         match self {
+            Theme::BossFight => Self::boss_fight(),
             Theme::Fight => Self::fight(),
             Theme::GameOver => Self::game_over(),
             Theme::Maze => Self::maze(),
@@ -27,6 +29,20 @@ impl Theme {
         }
     }
 
+    fn boss_fight() -> Track {
+        Track::default()
+            .tone(Note::A.freq(1), 250, 4.0)
+            .tone(Note::A.freq(1), 250, 4.0)
+            .tone(Note::C.freq(2), 250, 4.0)
+            .tone(Note::A.freq(1), 250, 4.0)
+            .tone(Note::Ds.freq(2), 500, 4.0)
+            .tone(Note::D.freq(2), 250, 4.0)
+            .tone(Note::A.freq(1), 250, 4.0)
+            .tone(Note::A.freq(1), 250, 4.0)
+            .tone(Note::C.freq(2), 250, 4.0)
+            .tone(Note::Gs.freq(1), 750, 4.0)
+    }
+
     fn fight() -> Track {
         Track::default()
             .tone(Note::Cs.freq(2), 300, 4.0)
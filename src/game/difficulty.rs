@@ -0,0 +1,97 @@
+//! # Difficulty
+//!
+//! Game difficulty, which tunes the level curve
+
+use std::str::FromStr;
+
+/// Max level the player can reach
+pub const MAX_LEVEL: u8 = 10;
+
+/// Game difficulty
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    /// All the difficulties, from the easiest
+    pub const ALL: [Difficulty; 3] = [Self::Easy, Self::Normal, Self::Hard];
+
+    /// Returns the difficulty name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
+
+    /// Experience required to advance from `level` to the next level.
+    /// Returns `None` if `level` is the max level
+    pub fn experience_to_level_up(&self, level: u8) -> Option<u32> {
+        if level >= MAX_LEVEL {
+            return None;
+        }
+        let (base, growth) = match self {
+            Self::Easy => (15, 10),
+            Self::Normal => (20, 15),
+            Self::Hard => (30, 20),
+        };
+        Some(base + growth * (level.saturating_sub(1) as u32))
+    }
+
+    /// Percentage applied to the merchant's prices
+    pub fn price_factor(&self) -> u16 {
+        match self {
+            Self::Easy => 80,
+            Self::Normal => 100,
+            Self::Hard => 125,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("unknown difficulty '{}' (easy, normal, hard)", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_get_experience_to_level_up() {
+        assert_eq!(Difficulty::Easy.experience_to_level_up(1), Some(15));
+        assert_eq!(Difficulty::Normal.experience_to_level_up(1), Some(20));
+        assert_eq!(Difficulty::Normal.experience_to_level_up(3), Some(50));
+        assert_eq!(Difficulty::Hard.experience_to_level_up(2), Some(50));
+        assert_eq!(Difficulty::Hard.experience_to_level_up(MAX_LEVEL), None);
+    }
+
+    #[test]
+    fn should_parse_difficulty() {
+        assert_eq!(Difficulty::from_str("easy").unwrap(), Difficulty::Easy);
+        assert_eq!(Difficulty::from_str("Normal").unwrap(), Difficulty::Normal);
+        assert_eq!(Difficulty::from_str("HARD").unwrap(), Difficulty::Hard);
+        assert!(Difficulty::from_str("nightmare").is_err());
+    }
+
+    #[test]
+    fn should_get_price_factor() {
+        assert_eq!(Difficulty::Easy.price_factor(), 80);
+        assert_eq!(Difficulty::Normal.price_factor(), 100);
+        assert_eq!(Difficulty::Hard.price_factor(), 125);
+    }
+}
@@ -2,10 +2,20 @@
 //!
 //! This module contains all the game entities
 
+mod chest;
 mod enemy;
 mod items;
+mod npc;
 mod player;
 
-pub use enemy::{Daemon, Enemy, Shadow};
-pub use items::{Item, Potion};
-pub use player::{Player, State as PlayerState};
+pub use chest::Chest;
+pub use enemy::{Daemon, DonMaze, Enemy, Mimic, Shadow};
+pub use items::{
+    Amulet, Armor, Artifact, EquipmentSlot, Herb, Item, Potion, PotionKnowledge, Recipe, Relic,
+    ThrownEffect, Weapon, WeaponKind,
+};
+pub use npc::{Dialogue, DialogueEffect, Merchant, Npc, Speaker, EXIT_HINT, KEY_HINT};
+pub use player::{
+    ActiveStatusEffect, LightLevel, Player, State as PlayerState, StatusEffect, Torch,
+    MAX_TORCH_FUEL,
+};
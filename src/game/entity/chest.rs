@@ -0,0 +1,69 @@
+//! # Chest
+//!
+//! Chests holding the items placed in the maze
+
+use crate::game::Hp;
+
+/// The chest holding the item in a room. It may hide a few surprises
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(tag = "type")]
+pub enum Chest {
+    /// An ordinary chest, which can be opened right away
+    Plain,
+    /// Must be opened with a lockpick or forced open for the provided amount of turns
+    Locked { forcing_turns: u8 },
+    /// Opening the chest springs a trap, which deals damage to the player
+    Trapped { damage: Hp },
+    /// It's not a chest: it's a mimic, which attacks whoever tries to open it
+    Mimic,
+}
+
+impl Chest {
+    /// Returns whether the chest is locked
+    pub fn is_locked(&self) -> bool {
+        matches!(self, Self::Locked { .. })
+    }
+
+    /// Force the lock for a turn. Returns the remaining turns before the lock gives way
+    pub fn force(&mut self) -> u8 {
+        match self {
+            Self::Locked { forcing_turns } => {
+                *forcing_turns = forcing_turns.saturating_sub(1);
+                *forcing_turns
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_force_chest() {
+        let mut chest = Chest::Locked { forcing_turns: 2 };
+        assert_eq!(chest.is_locked(), true);
+        assert_eq!(chest.force(), 1);
+        assert_eq!(chest.force(), 0);
+        assert_eq!(Chest::Plain.is_locked(), false);
+        assert_eq!(Chest::Mimic.force(), 0);
+    }
+
+    #[test]
+    fn should_serialize_chest() {
+        for chest in [
+            Chest::Plain,
+            Chest::Locked { forcing_turns: 3 },
+            Chest::Trapped { damage: 2 },
+            Chest::Mimic,
+        ] {
+            let json = serde_json::to_string(&chest).unwrap();
+            let decoded: Chest = serde_json::from_str(&json).unwrap();
+            assert_eq!(chest, decoded);
+        }
+    }
+}
@@ -1,22 +1,31 @@
 //! # Enemy
 
+use rand::Rng;
+
+use super::{Item, Potion, StatusEffect, Weapon, WeaponKind};
 use crate::game::Hp;
 
+/// Don Maze's health. Each phase of the boss fight lasts for a third of it
+const DON_MAZE_HEALTH: Hp = 24;
+
 /// Enemies in the maze
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
 #[serde(tag = "type")]
 pub enum Enemy {
     /// The boss
-    DonMaze,
+    DonMaze(DonMaze),
     Daemon(Daemon),
+    /// Disguised as a chest until the player inspects or opens it
+    Mimic(Mimic),
     Shadow(Shadow),
 }
 
 impl Enemy {
     pub fn name(&self) -> &str {
         match self {
-            Self::DonMaze => "Don Maze",
+            Self::DonMaze(_) => "Don Maze",
             Self::Daemon(_) => "Daemon",
+            Self::Mimic(_) => "Mimic",
             Self::Shadow(_) => "Shadow",
         }
     }
@@ -24,22 +33,142 @@ impl Enemy {
     /// Get enemy health
     pub fn health(&self) -> Hp {
         match self {
-            Self::Daemon(Daemon { health }) => *health,
-            Self::DonMaze => 255,
-            Self::Shadow(Shadow { health }) => *health,
+            Self::Daemon(Daemon { health, .. }) => *health,
+            Self::DonMaze(DonMaze { health }) => *health,
+            Self::Mimic(Mimic { health, .. }) => *health,
+            Self::Shadow(Shadow { health, .. }) => *health,
+        }
+    }
+
+    /// Get enemy health when it was spawned
+    pub fn max_health(&self) -> Hp {
+        match self {
+            Self::Daemon(Daemon { max_health, .. }) => *max_health,
+            Self::DonMaze(_) => DON_MAZE_HEALTH,
+            Self::Mimic(Mimic { max_health, .. }) => *max_health,
+            Self::Shadow(Shadow { max_health, .. }) => *max_health,
+        }
+    }
+
+    /// Experience awarded to the player for killing the enemy.
+    /// Scales with enemy type and max health
+    pub fn experience(&self) -> u32 {
+        let base = match self {
+            Self::Daemon(_) => 5,
+            Self::DonMaze(_) => 50,
+            Self::Mimic(_) => 6,
+            Self::Shadow(_) => 8,
+        };
+        base + 2 * self.max_health() as u32
+    }
+
+    /// Items the enemy may drop when defeated, along with the probability (%) of each one to be dropped.
+    /// The probabilities sum up to less than 100; the remainder is the chance of dropping nothing
+    pub fn drop_table(&self) -> &'static [(u8, Item)] {
+        match self {
+            Self::Daemon(_) => &[
+                (20, Item::Potion(Potion::DaemonsBlood)),
+                (10, Item::Potion(Potion::Vinegar)),
+                (10, Item::Potion(Potion::Red)),
+            ],
+            Self::DonMaze(_) => &[],
+            Self::Mimic(_) => &[(30, Item::Gold), (15, Item::Lockpick)],
+            Self::Shadow(_) => &[
+                (15, Item::Sonar),
+                (15, Item::Potion(Potion::Chamomille)),
+                (10, Item::Potion(Potion::Mead)),
+                (5, Item::Talisman),
+            ],
+        }
+    }
+
+    /// Roll the enemy drop table and return the dropped item, if any
+    pub fn loot<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Item> {
+        let mut roll = rng.random_range(0..100u8);
+        for (probability, item) in self.drop_table() {
+            if roll < *probability {
+                return Some(*item);
+            }
+            roll -= probability;
+        }
+        None
+    }
+
+    /// Returns whether the enemy can be hurt by the player wielding `weapon`.
+    /// Don Maze can only be hurt by the Mazebane
+    pub fn is_vulnerable_to(&self, weapon: Option<&Weapon>) -> bool {
+        match self {
+            Self::DonMaze(_) => weapon.map(|x| x.kind) == Some(WeaponKind::Mazebane),
+            Self::Daemon(_) | Self::Mimic(_) | Self::Shadow(_) => true,
+        }
+    }
+
+    /// Boss fight phase. Returns `None` if the enemy is not a boss
+    pub fn phase(&self) -> Option<u8> {
+        match self {
+            Self::DonMaze(don_maze) => Some(don_maze.phase()),
+            Self::Daemon(_) | Self::Mimic(_) | Self::Shadow(_) => None,
         }
     }
 
     /// Inflict damage to enemy
     pub fn damage(&mut self, hp: Hp) {
         match self {
-            Self::Daemon(daemon) => {
-                daemon.health = daemon.health.saturating_sub(hp);
+            Self::Daemon(Daemon { health, .. })
+            | Self::DonMaze(DonMaze { health })
+            | Self::Mimic(Mimic { health, .. })
+            | Self::Shadow(Shadow { health, .. }) => {
+                *health = health.saturating_sub(hp);
             }
-            Self::Shadow(shadow) => {
-                shadow.health = shadow.health.saturating_sub(hp);
+        }
+    }
+
+    /// Heal enemy by `hp`, up to its max health
+    pub fn heal(&mut self, hp: Hp) {
+        let max_health = self.max_health();
+        match self {
+            Self::Daemon(Daemon { health, .. })
+            | Self::DonMaze(DonMaze { health })
+            | Self::Mimic(Mimic { health, .. })
+            | Self::Shadow(Shadow { health, .. }) => {
+                *health = health.saturating_add(hp).min(max_health);
             }
-            Self::DonMaze => {}
+        }
+    }
+
+    /// Modifier applied to the player's chance to retreat from this enemy.
+    /// Fast enemies are harder to leave behind
+    pub fn retreat_modifier(&self) -> i16 {
+        match self {
+            Self::Daemon(_) => 0,
+            Self::DonMaze(_) => 20,
+            Self::Mimic(_) => 10,
+            Self::Shadow(_) => -15,
+        }
+    }
+
+    /// Returns whether the enemy wanders through the maze; mimics never leave their room
+    pub fn wanders(&self) -> bool {
+        !matches!(self, Self::Mimic(_))
+    }
+
+    /// Describes the noise the enemy makes, which can be heard from the nearby rooms
+    pub fn noise(&self) -> &'static str {
+        match self {
+            Self::Daemon(_) => "claws scraping",
+            Self::DonMaze(_) => "heavy footsteps",
+            Self::Mimic(_) => "teeth chattering",
+            Self::Shadow(_) => "whispers",
+        }
+    }
+
+    /// Chance (%) for the enemy to dodge the player's attacks
+    pub fn evasion(&self) -> u8 {
+        match self {
+            Self::Daemon(_) => 5,
+            Self::DonMaze(_) => 10,
+            Self::Mimic(_) => 0,
+            Self::Shadow(_) => 20,
         }
     }
 
@@ -47,7 +176,12 @@ impl Enemy {
     pub fn accuracy(&self) -> u8 {
         match self {
             Self::Daemon(_) => 85,
-            Self::DonMaze => 10,
+            Self::DonMaze(don_maze) => match don_maze.phase() {
+                1 => 10,
+                2 => 60,
+                _ => 85,
+            },
+            Self::Mimic(_) => 75,
             Self::Shadow(_) => 95,
         }
     }
@@ -56,10 +190,56 @@ impl Enemy {
     pub fn base_attack(&self) -> u8 {
         match self {
             Self::Daemon(_) => 1,
-            Self::DonMaze => 10,
+            Self::DonMaze(don_maze) => match don_maze.phase() {
+                1 => 10,
+                2 => 3,
+                _ => 2,
+            },
+            Self::Mimic(_) => 2,
             Self::Shadow(_) => 2,
         }
     }
+
+    /// Status effect inflicted to the player on critical hit and its duration
+    pub fn critical_hit_status_effect(&self) -> Option<(StatusEffect, u8)> {
+        match self {
+            Self::Daemon(_) => Some((StatusEffect::Poisoned, 2)),
+            Self::DonMaze(don_maze) if don_maze.phase() == 3 => Some((StatusEffect::Blinded, 2)),
+            Self::DonMaze(_) | Self::Mimic(_) => None,
+            Self::Shadow(_) => Some((StatusEffect::Blinded, 3)),
+        }
+    }
+}
+
+/// Don Maze is the boss of the maze. It can only be hurt by the Mazebane.
+/// The fight has three phases, based on its health:
+///
+/// 1. Base attack: 10; Accuracy: 10
+/// 2. Base attack: 3; Accuracy: 60
+/// 3. Base attack: 2; Accuracy: 85; critical hits blind the player
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub struct DonMaze {
+    health: Hp,
+}
+
+impl Default for DonMaze {
+    fn default() -> Self {
+        Self {
+            health: DON_MAZE_HEALTH,
+        }
+    }
+}
+
+impl DonMaze {
+    /// Boss fight phase (1-3)
+    pub fn phase(&self) -> u8 {
+        let phase_health = DON_MAZE_HEALTH / 3;
+        match self.health {
+            x if x > phase_health * 2 => 1,
+            x if x > phase_health => 2,
+            _ => 3,
+        }
+    }
 }
 
 /// A daemon is an enemy which deals 1HP damage to player.
@@ -69,11 +249,34 @@ impl Enemy {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
 pub struct Daemon {
     health: Hp,
+    max_health: Hp,
 }
 
 impl Daemon {
     pub fn new(health: Hp) -> Self {
-        Self { health }
+        Self {
+            health,
+            max_health: health,
+        }
+    }
+}
+
+/// A mimic looks like a chest, until it bites the player trying to open it.
+/// HP is between 4-6
+/// Base attack: 2
+/// Accuracy: 75
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub struct Mimic {
+    health: Hp,
+    max_health: Hp,
+}
+
+impl Mimic {
+    pub fn new(health: Hp) -> Self {
+        Self {
+            health,
+            max_health: health,
+        }
     }
 }
 
@@ -84,51 +287,139 @@ impl Daemon {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
 pub struct Shadow {
     health: Hp,
+    max_health: Hp,
 }
 
 impl Shadow {
     pub fn new(health: Hp) -> Self {
-        Self { health }
+        Self {
+            health,
+            max_health: health,
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
+    use rand_pcg::Pcg64;
+    use rand_seeder::Seeder;
 
     use super::*;
 
+    #[test]
+    fn should_drop_loot() {
+        let daemon = Enemy::Daemon(Daemon::new(3));
+        let shadow = Enemy::Shadow(Shadow::new(3));
+        let mut rng: Pcg64 = Seeder::from("loot").into_rng();
+        let loot: Vec<Option<Item>> = (0..64).map(|_| daemon.loot(&mut rng)).collect();
+        assert!(loot.iter().any(|x| x.is_none()));
+        assert!(loot
+            .iter()
+            .flatten()
+            .all(|x| daemon.drop_table().iter().any(|(_, item)| item == x)));
+        // same seed, same loot
+        let mut rng: Pcg64 = Seeder::from("loot").into_rng();
+        assert_eq!(
+            (0..64).map(|_| daemon.loot(&mut rng)).collect::<Vec<_>>(),
+            loot
+        );
+        let mut rng: Pcg64 = Seeder::from("loot").into_rng();
+        assert!((0..64)
+            .filter_map(|_| shadow.loot(&mut rng))
+            .all(|x| shadow.drop_table().iter().any(|(_, item)| *item == x)));
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).loot(&mut rng), None);
+    }
+
+    #[test]
+    fn should_have_drop_tables_below_100_percent() {
+        for enemy in [
+            Enemy::Daemon(Daemon::new(3)),
+            Enemy::DonMaze(DonMaze::default()),
+            Enemy::Mimic(Mimic::new(5)),
+            Enemy::Shadow(Shadow::new(3)),
+        ] {
+            assert!(
+                enemy
+                    .drop_table()
+                    .iter()
+                    .map(|(p, _)| *p as u16)
+                    .sum::<u16>()
+                    < 100
+            );
+        }
+    }
+
     #[test]
     fn should_get_enemy_name() {
-        assert_eq!(Enemy::Daemon(Daemon { health: 2 }).name(), "Daemon");
-        assert_eq!(Enemy::DonMaze.name(), "Don Maze");
-        assert_eq!(Enemy::Shadow(Shadow { health: 3 }).name(), "Shadow");
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).name(), "Daemon");
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).name(), "Don Maze");
+        assert_eq!(Enemy::Mimic(Mimic::new(5)).name(), "Mimic");
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).name(), "Shadow");
+    }
+
+    #[test]
+    fn should_tell_whether_enemy_wanders() {
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).wanders(), true);
+        assert_eq!(Enemy::Mimic(Mimic::new(5)).wanders(), false);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).wanders(), true);
     }
 
     #[test]
     fn should_get_health() {
-        assert_eq!(Enemy::Daemon(Daemon { health: 2 }).health(), 2);
-        assert_eq!(Enemy::DonMaze.health(), 255);
-        assert_eq!(Enemy::Shadow(Shadow { health: 3 }).health(), 3);
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).health(), 2);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).health(), 24);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).health(), 3);
     }
 
     #[test]
     fn should_get_base_attack() {
-        assert_eq!(Enemy::Daemon(Daemon { health: 2 }).base_attack(), 1);
-        assert_eq!(Enemy::DonMaze.base_attack(), 10);
-        assert_eq!(Enemy::Shadow(Shadow { health: 3 }).base_attack(), 2);
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).base_attack(), 1);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).base_attack(), 10);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).base_attack(), 2);
     }
 
     #[test]
     fn should_get_accuracy() {
-        assert_eq!(Enemy::Daemon(Daemon { health: 2 }).accuracy(), 85);
-        assert_eq!(Enemy::DonMaze.accuracy(), 10);
-        assert_eq!(Enemy::Shadow(Shadow { health: 3 }).accuracy(), 95);
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).accuracy(), 85);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).accuracy(), 10);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).accuracy(), 95);
+    }
+
+    #[test]
+    fn should_get_critical_hit_status_effect() {
+        assert_eq!(
+            Enemy::Daemon(Daemon::new(2)).critical_hit_status_effect(),
+            Some((StatusEffect::Poisoned, 2))
+        );
+        assert_eq!(
+            Enemy::DonMaze(DonMaze::default()).critical_hit_status_effect(),
+            None
+        );
+        assert_eq!(
+            Enemy::Shadow(Shadow::new(3)).critical_hit_status_effect(),
+            Some((StatusEffect::Blinded, 3))
+        );
+    }
+
+    #[test]
+    fn should_get_max_health() {
+        let mut daemon = Enemy::Daemon(Daemon::new(4));
+        daemon.damage(3);
+        assert_eq!(daemon.max_health(), 4);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).max_health(), 24);
+    }
+
+    #[test]
+    fn should_get_experience() {
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).experience(), 9);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).experience(), 98);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).experience(), 14);
     }
 
     #[test]
     fn should_damage_daemon() {
-        let mut daemon = Enemy::Daemon(Daemon { health: 2 });
+        let mut daemon = Enemy::Daemon(Daemon::new(2));
         daemon.damage(1);
         assert_eq!(daemon.health(), 1);
         daemon.damage(4);
@@ -137,7 +428,7 @@ mod test {
 
     #[test]
     fn should_damage_shadow() {
-        let mut shadow = Enemy::Shadow(Shadow { health: 2 });
+        let mut shadow = Enemy::Shadow(Shadow::new(2));
         shadow.damage(1);
         assert_eq!(shadow.health(), 1);
         shadow.damage(4);
@@ -145,10 +436,70 @@ mod test {
     }
 
     #[test]
-    fn should_not_damage_don_maze() {
-        let mut don_maze = Enemy::DonMaze;
+    fn should_heal_enemy() {
+        let mut daemon = Enemy::Daemon(Daemon::new(5));
+        daemon.damage(3);
+        daemon.heal(2);
+        assert_eq!(daemon.health(), 4);
+        daemon.heal(255);
+        assert_eq!(daemon.health(), 5);
+    }
+
+    #[test]
+    fn should_get_retreat_modifier() {
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).retreat_modifier(), 0);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).retreat_modifier(), 20);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).retreat_modifier(), -15);
+    }
+
+    #[test]
+    fn should_get_evasion() {
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).evasion(), 5);
+        assert_eq!(Enemy::DonMaze(DonMaze::default()).evasion(), 10);
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).evasion(), 20);
+    }
+
+    #[test]
+    fn should_get_noise() {
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).noise(), "claws scraping");
+        assert_eq!(
+            Enemy::DonMaze(DonMaze::default()).noise(),
+            "heavy footsteps"
+        );
+        assert_eq!(Enemy::Shadow(Shadow::new(3)).noise(), "whispers");
+    }
+
+    #[test]
+    fn should_fight_don_maze_in_phases() {
+        let mut don_maze = Enemy::DonMaze(DonMaze::default());
+        assert_eq!(don_maze.phase(), Some(1));
+        assert_eq!(don_maze.base_attack(), 10);
+        assert_eq!(don_maze.accuracy(), 10);
+        don_maze.damage(8);
+        assert_eq!(don_maze.phase(), Some(2));
+        assert_eq!(don_maze.base_attack(), 3);
+        assert_eq!(don_maze.accuracy(), 60);
+        assert_eq!(don_maze.critical_hit_status_effect(), None);
+        don_maze.damage(8);
+        assert_eq!(don_maze.phase(), Some(3));
+        assert_eq!(
+            don_maze.critical_hit_status_effect(),
+            Some((StatusEffect::Blinded, 2))
+        );
         don_maze.damage(255);
-        assert_eq!(don_maze.health(), 255);
+        assert_eq!(don_maze.health(), 0);
+        assert_eq!(Enemy::Daemon(Daemon::new(2)).phase(), None);
+    }
+
+    #[test]
+    fn should_tell_whether_enemy_is_vulnerable() {
+        let sword = Weapon::new(WeaponKind::Sword);
+        let mazebane = Weapon::new(WeaponKind::Mazebane);
+        let don_maze = Enemy::DonMaze(DonMaze::default());
+        assert_eq!(don_maze.is_vulnerable_to(None), false);
+        assert_eq!(don_maze.is_vulnerable_to(Some(&sword)), false);
+        assert_eq!(don_maze.is_vulnerable_to(Some(&mazebane)), true);
+        assert_eq!(Enemy::Shadow(Shadow::new(2)).is_vulnerable_to(None), true);
     }
 
     #[test]
@@ -159,9 +510,9 @@ mod test {
         }
         let test = Test {
             enemies: vec![
-                Enemy::DonMaze,
-                Enemy::Shadow(Shadow { health: 2 }),
-                Enemy::Daemon(Daemon { health: 3 }),
+                Enemy::DonMaze(DonMaze::default()),
+                Enemy::Shadow(Shadow::new(2)),
+                Enemy::Daemon(Daemon::new(3)),
             ],
         };
         let json = serde_json::to_string(&test).unwrap();
@@ -4,9 +4,19 @@
 
 use super::PlayerState;
 
+mod artifacts;
+mod equipment;
+mod herbs;
 mod potions;
+mod recipes;
+mod relics;
 
-pub use potions::Potion;
+pub use artifacts::Artifact;
+pub use equipment::{Amulet, Armor, EquipmentSlot, Weapon, WeaponKind};
+pub use herbs::Herb;
+pub use potions::{Potion, PotionKnowledge, ThrownEffect};
+pub use recipes::Recipe;
+pub use relics::Relic;
 
 /// Game items
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
@@ -14,18 +24,42 @@ pub use potions::Potion;
 pub enum Item {
     /// Makes you know the content of a potion in advance
     AlchemyBook,
-    /// Increase max HP and current hp by one
-    Armor,
+    /// Amulet with a passive effect while worn
+    Amulet(Amulet),
+    /// Reduces incoming damage while worn
+    Armor(Armor),
+    /// A unique artifact with a passive effect while carried
+    Artifact(Artifact),
+    /// Points towards the first step on the shortest path to the exit
+    Compass,
+    /// A pouch of gold coins; coins are put in the player's purse once collected
+    Gold,
+    /// A herb, which can be brewed with other ingredients
+    Herb(Herb),
+    /// Opens a locked chest
+    Lockpick,
+    /// Reveals the layout of the maze within a few rooms
+    MapScroll,
     /// Required to leave the maze
     MazeKey,
     /// Used to mark visited rooms
     PaintCan,
     /// A potion with an effect, but you cannot know what it does until you drink it
     Potion(Potion),
+    /// One of the three relics required to forge the Mazebane
+    Relic(Relic),
     /// Sonar: tells you if there are items or enemies in the adjacent rooms
     Sonar,
     /// Kill any enemy except don maze, which will vanish
     Talisman,
+    /// Walks the player back to the entrance of the maze
+    Rope,
+    /// Sends the player to a random room already visited
+    TeleportStone,
+    /// Refuels the player's torch
+    TorchOil,
+    /// Changes the damage dealt by the player while worn
+    Weapon(Weapon),
 }
 
 impl Item {
@@ -33,42 +67,172 @@ impl Item {
     pub fn key(&self) -> u32 {
         match self {
             Self::AlchemyBook => 0,
-            Self::Armor => 1,
+            Self::Amulet(amulet) => amulet.key(),
+            Self::Armor(armor) => armor.key(),
+            Self::Artifact(artifact) => artifact.key(),
+            Self::Compass => 8,
+            Self::Gold => 7,
+            Self::Herb(herb) => herb.key(),
+            Self::Lockpick => 12,
+            Self::MapScroll => 9,
             Self::MazeKey => 2,
             Self::PaintCan => 5,
             Self::Potion(potion) => potion.key(),
+            Self::Relic(relic) => relic.key(),
+            Self::Rope => 11,
             Self::Sonar => 3,
             Self::Talisman => 4,
+            Self::TeleportStone => 10,
+            Self::TorchOil => 6,
+            Self::Weapon(weapon) => weapon.key(),
         }
     }
 
-    /// Return the item name
-    pub fn name(&self, has_alchemy_book: bool) -> &str {
+    /// Get item from its key; returns `None` if the key doesn't belong to any item
+    pub fn from_key(key: u32) -> Option<Self> {
+        match key {
+            0 => Some(Self::AlchemyBook),
+            1 => Some(Self::Armor(Armor::default())), // NOTE: legacy key for armor
+            2 => Some(Self::MazeKey),
+            3 => Some(Self::Sonar),
+            4 => Some(Self::Talisman),
+            5 => Some(Self::PaintCan),
+            6 => Some(Self::TorchOil),
+            7 => Some(Self::Gold),
+            8 => Some(Self::Compass),
+            9 => Some(Self::MapScroll),
+            10 => Some(Self::TeleportStone),
+            11 => Some(Self::Rope),
+            12 => Some(Self::Lockpick),
+            x if (0x1000..0x2000).contains(&x) => Weapon::from_key(x).map(Self::Weapon),
+            x if (0x2000..0x3000).contains(&x) => Some(Self::Armor(Armor::from_key(x))),
+            x if (0x3000..0x4000).contains(&x) => Amulet::from_key(x).map(Self::Amulet),
+            x if (0x4000..0x5000).contains(&x) => Relic::from_key(x).map(Self::Relic),
+            x if (0x5000..0x6000).contains(&x) => Herb::from_key(x).map(Self::Herb),
+            x if (0x6000..0x7000).contains(&x) => Artifact::from_key(x).map(Self::Artifact),
+            x => Potion::from_key(x).map(Self::Potion),
+        }
+    }
+
+    /// Returns the equipment slot the item can be worn in, if any
+    pub fn slot(&self) -> Option<EquipmentSlot> {
+        match self {
+            Self::Amulet(_) => Some(EquipmentSlot::Amulet),
+            Self::Armor(_) => Some(EquipmentSlot::Armor),
+            Self::Weapon(_) => Some(EquipmentSlot::Weapon),
+            Self::AlchemyBook
+            | Self::Artifact(_)
+            | Self::Compass
+            | Self::Gold
+            | Self::Herb(_)
+            | Self::Lockpick
+            | Self::MapScroll
+            | Self::MazeKey
+            | Self::PaintCan
+            | Self::Potion(_)
+            | Self::Relic(_)
+            | Self::Rope
+            | Self::Sonar
+            | Self::Talisman
+            | Self::TeleportStone
+            | Self::TorchOil => None,
+        }
+    }
+
+    /// Returns current and max durability for items which wear out
+    pub fn durability(&self) -> Option<(u8, u8)> {
+        match self {
+            Self::Armor(armor) => Some((armor.durability(), armor.max_durability())),
+            Self::Weapon(weapon) => Some((weapon.durability(), weapon.kind.max_durability())),
+            _ => None,
+        }
+    }
+
+    /// Return the item name; unidentified potions are named after their look
+    pub fn name<'a>(&'a self, knowledge: &PotionKnowledge) -> &'a str {
         match self {
             Self::AlchemyBook => "Alchemy book",
-            Self::Armor => "Armor",
+            Self::Amulet(Amulet::Swiftness) => "Amulet of swiftness",
+            Self::Amulet(Amulet::Warding) => "Amulet of warding",
+            Self::Armor(_) => "Armor",
+            Self::Artifact(artifact) => artifact.name(),
+            Self::Compass => "Compass",
+            Self::Gold => "Gold coins",
+            Self::Herb(herb) => herb.name(),
+            Self::Lockpick => "Lockpick",
+            Self::MapScroll => "Map scroll",
             Self::MazeKey => "Maze key",
             Self::PaintCan => "Paint can",
-            Self::Potion(_) if !has_alchemy_book => "Potion (???)",
+            Self::Potion(potion) if !knowledge.is_identified(*potion) => {
+                knowledge.appearance(*potion).name
+            }
             Self::Potion(potion) => potion.name(),
+            Self::Relic(relic) => relic.name(),
+            Self::Rope => "Rope",
             Self::Sonar => "Sonar",
             Self::Talisman => "Talisman",
+            Self::TeleportStone => "Teleport stone",
+            Self::TorchOil => "Torch oil",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Axe,
+                ..
+            }) => "Axe",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Dagger,
+                ..
+            }) => "Dagger",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Sword,
+                ..
+            }) => "Sword",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Mazebane,
+                ..
+            }) => "Mazebane",
         }
     }
 
-    /// Returns the item description
-    pub fn description(&self, has_alchemy_book: bool) -> &str {
+    /// Returns the item description; unidentified potions are described by their look
+    pub fn description<'a>(&'a self, knowledge: &PotionKnowledge) -> &'a str {
         match self {
             Self::AlchemyBook => "Makes you able to know the content of a potion",
-            Self::Armor => "Increase max HP by 1",
+            Self::Amulet(Amulet::Swiftness) => "Increases the chance to escape from a fight",
+            Self::Amulet(Amulet::Warding) => "Makes enemies miss their attacks more often",
+            Self::Armor(_) => "Reduces damage suffered by 1 HP, until it breaks",
+            Self::Artifact(artifact) => artifact.description(),
+            Self::Compass => "Its needle points towards the way out of the maze",
+            Self::Gold => "Shiny gold coins. Someone down here may be interested in them",
+            Self::Herb(_) => "A herb. Brew it with a potion or another herb and see what comes out",
+            Self::Lockpick => "A thin bent wire. It opens a locked chest, but it breaks in the lock",
+            Self::MapScroll => "A scrap of an old map. It shows the rooms around you",
             Self::MazeKey => "Allows you to leave the maze... once you'll find the exit",
             Self::PaintCan => "Used to mark visited rooms",
-            Self::Potion(_) if !has_alchemy_book => {
-                "If only I had an alchemy book or something like that..."
+            Self::Potion(potion) if !knowledge.is_identified(*potion) => {
+                knowledge.appearance(*potion).description
             }
             Self::Potion(potion) => potion.description(),
+            Self::Relic(_) => "One of the three relics. Gather them all and combine them to forge the weapon which can defeat Don Maze",
+            Self::Rope => "Tie it here and there while exploring and it will lead you back to the entrance",
             Self::Sonar => "Tells you if there are enemies or items in the adjacent rooms",
             Self::Talisman => "Instantly kills an enemy except for don maze, but it seems it will make him disappear",
+            Self::TeleportStone => "Takes you to one of the rooms you've already been to",
+            Self::TorchOil => "Refuels your torch. Without light you won't see much down here",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Axe,
+                ..
+            }) => "Deals 4-8 HP, but it's heavy and you'll miss often",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Dagger,
+                ..
+            }) => "Deals 2-5 HP. Light and accurate",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Sword,
+                ..
+            }) => "Deals 3-6 HP. Rarely misses",
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Mazebane,
+                ..
+            }) => "Deals 4-7 HP and never breaks. The only weapon which can hurt Don Maze",
         }
     }
 
@@ -76,12 +240,59 @@ impl Item {
     pub fn effect(&self) -> &str {
         match self {
             Self::AlchemyBook => "",
-            Self::Armor => "You worn the armor. Your max HP has been increased by 1",
+            Self::Amulet(_) => "",
+            Self::Armor(_) => "",
+            Self::Artifact(_) => "",
+            Self::Compass => "The needle spins and then stops",
+            Self::Gold => "",
+            Self::Herb(_) => "",
+            Self::Lockpick => "",
+            Self::MapScroll => "You unroll the map, which crumbles to dust once you've studied it",
             Self::MazeKey => "",
             Self::PaintCan => "",
             Self::Potion(potion) => potion.effect(),
+            Self::Relic(_) => "",
+            Self::Rope => "You follow the rope back to the entrance of the maze",
             Self::Sonar => "The content of the adjacent rooms is revealed",
             Self::Talisman => "You used the ancient power beneath the talisman",
+            Self::TeleportStone => {
+                "The world spins around you and you find yourself somewhere else"
+            }
+            Self::TorchOil => "Your torch burns brighter",
+            Self::Weapon(_) => "",
+        }
+    }
+
+    /// Returns the base value in gold of the item, if it can be traded
+    pub fn value(&self) -> Option<u16> {
+        match self {
+            Self::AlchemyBook => Some(40),
+            Self::Amulet(_) => Some(35),
+            Self::Armor(_) => Some(25),
+            Self::Artifact(_) => Some(60),
+            Self::Compass => Some(30),
+            Self::Gold => None,
+            Self::Herb(_) => Some(5),
+            Self::Lockpick => Some(8),
+            Self::MapScroll => Some(12),
+            Self::MazeKey => None,
+            Self::PaintCan => Some(10),
+            Self::Potion(_) => Some(10),
+            Self::Relic(_) => None,
+            Self::Rope => Some(10),
+            Self::Sonar => Some(15),
+            Self::Talisman => Some(30),
+            Self::TeleportStone => Some(20),
+            Self::TorchOil => Some(8),
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Mazebane,
+                ..
+            }) => None,
+            Self::Weapon(Weapon {
+                kind: WeaponKind::Dagger,
+                ..
+            }) => Some(15),
+            Self::Weapon(_) => Some(20),
         }
     }
 
@@ -89,15 +300,32 @@ impl Item {
     pub fn consumable(&self) -> bool {
         match self {
             Self::AlchemyBook => false,
-            Self::Armor => true,
+            Self::Amulet(_) => false,
+            Self::Armor(_) => false,
+            Self::Artifact(_) => false,
+            Self::Compass => false,
+            Self::Gold => false,
+            Self::Herb(_) => false,
+            Self::Lockpick => true,
+            Self::MapScroll => true,
             Self::MazeKey => false,
             Self::PaintCan => false,
             Self::Potion(_) => true,
+            Self::Relic(_) => false,
+            Self::Rope => true,
             Self::Sonar => true,
             Self::Talisman => true,
+            Self::TeleportStone => true,
+            Self::TorchOil => true,
+            Self::Weapon(_) => false,
         }
     }
 
+    /// Returns whether the item can be brewed
+    pub fn is_ingredient(&self) -> bool {
+        matches!(self, Self::Herb(_) | Self::Potion(_))
+    }
+
     /// Returns whether item is usable in current state
     pub fn usable(&self, state: PlayerState) -> bool {
         match (self, state) {
@@ -105,35 +333,36 @@ impl Item {
                 Self::AlchemyBook,
                 PlayerState::Explore | PlayerState::Fight | PlayerState::Asleep,
             ) => false,
-            (Self::Armor, PlayerState::Explore | PlayerState::Fight) => true,
-            (Self::Armor, PlayerState::Asleep) => false,
-            (Self::MazeKey, PlayerState::Explore | PlayerState::Fight | PlayerState::Asleep) => {
-                false
-            }
+            (
+                Self::Amulet(_) | Self::Armor(_) | Self::Weapon(_),
+                PlayerState::Explore | PlayerState::Fight,
+            ) => true,
+            (Self::Amulet(_) | Self::Armor(_) | Self::Weapon(_), PlayerState::Asleep) => false,
+            (
+                Self::Artifact(_) | Self::Gold | Self::Herb(_) | Self::Lockpick | Self::MazeKey,
+                PlayerState::Explore | PlayerState::Fight | PlayerState::Asleep,
+            ) => false,
             (Self::PaintCan, PlayerState::Explore | PlayerState::Fight | PlayerState::Asleep) => {
                 false
             }
             (Self::Potion(_), PlayerState::Explore | PlayerState::Fight) => true,
             (Self::Potion(_), PlayerState::Asleep) => false,
-            (Self::Sonar, PlayerState::Explore) => true,
-            (Self::Sonar, PlayerState::Asleep | PlayerState::Fight) => false,
-            (Self::Talisman, PlayerState::Fight) => true,
-            (Self::Talisman, PlayerState::Asleep | PlayerState::Explore) => false,
-        }
-    }
-}
-
-impl From<u32> for Item {
-    fn from(key: u32) -> Self {
-        match key {
-            0 => Self::AlchemyBook,
-            1 => Self::Armor,
-            2 => Self::MazeKey,
-            3 => Self::Sonar,
-            4 => Self::Talisman,
-            5 => Self::PaintCan,
-            x if x > 255 => Item::Potion(Potion::from(x)),
-            _ => Self::Armor, // fallback item
+            (Self::Relic(_), PlayerState::Explore) => true,
+            (Self::Relic(_), PlayerState::Asleep | PlayerState::Fight) => false,
+            (
+                Self::Compass | Self::MapScroll | Self::Rope | Self::Sonar | Self::TeleportStone,
+                PlayerState::Explore,
+            ) => true,
+            (
+                Self::Compass | Self::MapScroll | Self::Rope | Self::Sonar | Self::TeleportStone,
+                PlayerState::Asleep | PlayerState::Fight,
+            ) => false,
+            // NOTE: the talisman is used on the enemy chosen among the fight actions
+            (Self::Talisman, PlayerState::Asleep | PlayerState::Explore | PlayerState::Fight) => {
+                false
+            }
+            (Self::TorchOil, PlayerState::Explore | PlayerState::Fight) => true,
+            (Self::TorchOil, PlayerState::Asleep) => false,
         }
     }
 }
@@ -141,54 +370,100 @@ impl From<u32> for Item {
 #[cfg(test)]
 mod test {
 
+    use std::collections::HashSet;
+
     use pretty_assertions::assert_eq;
 
     use super::*;
 
     #[test]
     fn should_get_item_name() {
-        assert_eq!(Item::AlchemyBook.name(false), "Alchemy book");
-        assert_eq!(Item::Armor.name(false), "Armor");
-        assert_eq!(Item::MazeKey.name(false), "Maze key");
-        assert_eq!(Item::PaintCan.name(false), "Paint can");
-        assert_eq!(Item::Potion(Potion::Chamomille).name(false), "Potion (???)");
+        let identified = HashSet::new();
+        let unknown = PotionKnowledge::new("test", &identified, false);
+        let known = PotionKnowledge::new("test", &identified, true);
+        assert_eq!(Item::AlchemyBook.name(&unknown), "Alchemy book");
+        assert_eq!(
+            Item::Amulet(Amulet::Swiftness).name(&unknown),
+            "Amulet of swiftness"
+        );
         assert_eq!(
-            Item::Potion(Potion::Chamomille).name(true),
+            Item::Amulet(Amulet::Warding).name(&unknown),
+            "Amulet of warding"
+        );
+        assert_eq!(Item::Armor(Armor::default()).name(&unknown), "Armor");
+        assert_eq!(Item::Relic(Relic::Hilt).name(&unknown), "Ancient hilt");
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Mazebane)).name(&unknown),
+            "Mazebane"
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Axe)).name(&unknown),
+            "Axe"
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Dagger)).name(&unknown),
+            "Dagger"
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Sword)).name(&unknown),
+            "Sword"
+        );
+        assert_eq!(Item::MazeKey.name(&unknown), "Maze key");
+        assert_eq!(Item::PaintCan.name(&unknown), "Paint can");
+        assert_eq!(
+            Item::Potion(Potion::Chamomille).name(&unknown),
+            unknown.appearance(Potion::Chamomille).name
+        );
+        assert_eq!(
+            Item::Potion(Potion::Chamomille).name(&known),
             Potion::Chamomille.name()
         );
-        assert_eq!(Item::Sonar.name(false), "Sonar");
-        assert_eq!(Item::Talisman.name(false), "Talisman");
+        assert_eq!(Item::Sonar.name(&unknown), "Sonar");
+        assert_eq!(Item::Talisman.name(&unknown), "Talisman");
+        assert_eq!(Item::TorchOil.name(&unknown), "Torch oil");
+        assert_eq!(Item::Gold.name(&unknown), "Gold coins");
+        assert_eq!(Item::Herb(Herb::Sage).name(&unknown), "Sage leaves");
     }
 
     #[test]
     fn should_get_item_description() {
+        let identified = HashSet::new();
+        let unknown = PotionKnowledge::new("test", &identified, false);
+        let known = PotionKnowledge::new("test", &identified, true);
         assert_eq!(
-            Item::AlchemyBook.description(false),
+            Item::AlchemyBook.description(&unknown),
             "Makes you able to know the content of a potion"
         );
         assert_eq!(
-            Item::PaintCan.description(false),
+            Item::PaintCan.description(&unknown),
             "Used to mark visited rooms"
         );
-        assert_eq!(Item::Armor.description(false), "Increase max HP by 1");
         assert_eq!(
-            Item::MazeKey.description(false),
+            Item::Armor(Armor::default()).description(&unknown),
+            "Reduces damage suffered by 1 HP, until it breaks"
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Sword)).description(&unknown),
+            "Deals 3-6 HP. Rarely misses"
+        );
+        assert_eq!(
+            Item::MazeKey.description(&unknown),
             "Allows you to leave the maze... once you'll find the exit"
         );
         assert_eq!(
-            Item::Potion(Potion::Chamomille).description(false),
-            "If only I had an alchemy book or something like that..."
+            Item::Potion(Potion::Chamomille).description(&unknown),
+            unknown.appearance(Potion::Chamomille).description
         );
         assert_eq!(
-            Item::Potion(Potion::Chamomille).description(true),
+            Item::Potion(Potion::Chamomille).description(&known),
             Potion::Chamomille.description()
         );
         assert_eq!(
-            Item::Sonar.description(false),
+            Item::Sonar.description(&unknown),
             "Tells you if there are enemies or items in the adjacent rooms"
         );
         assert_eq!(
-            Item::Talisman.description(false),
+            Item::Talisman.description(&unknown),
             "Instantly kills an enemy except for don maze, but it seems it will make him disappear"
         );
     }
@@ -197,10 +472,7 @@ mod test {
     fn should_get_item_effect() {
         assert_eq!(Item::AlchemyBook.effect(), "");
         assert_eq!(Item::PaintCan.effect(), "");
-        assert_eq!(
-            Item::Armor.effect(),
-            "You worn the armor. Your max HP has been increased by 1"
-        );
+        assert_eq!(Item::Armor(Armor::default()).effect(), "");
         assert_eq!(Item::MazeKey.effect(), "");
         assert_eq!(
             Item::Potion(Potion::Chamomille).effect(),
@@ -219,26 +491,94 @@ mod test {
     #[test]
     fn should_get_whether_item_is_consumable() {
         assert_eq!(Item::AlchemyBook.consumable(), false);
-        assert_eq!(Item::Armor.consumable(), true);
+        assert_eq!(Item::Amulet(Amulet::Warding).consumable(), false);
+        assert_eq!(Item::Armor(Armor::default()).consumable(), false);
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Axe)).consumable(),
+            false
+        );
         assert_eq!(Item::MazeKey.consumable(), false);
         assert_eq!(Item::PaintCan.consumable(), false);
         assert_eq!(Item::Potion(Potion::Chamomille).consumable(), true);
         assert_eq!(Item::Sonar.consumable(), true);
         assert_eq!(Item::Talisman.consumable(), true);
+        assert_eq!(Item::TorchOil.consumable(), true);
+        assert_eq!(Item::Gold.consumable(), false);
+        assert_eq!(Item::Compass.consumable(), false);
+        assert_eq!(Item::MapScroll.consumable(), true);
+        assert_eq!(Item::TeleportStone.consumable(), true);
+        assert_eq!(Item::Rope.consumable(), true);
+    }
+
+    #[test]
+    fn should_tell_whether_item_is_ingredient() {
+        assert!(Item::Herb(Herb::Mandrake).is_ingredient());
+        assert!(Item::Potion(Potion::Vinegar).is_ingredient());
+        assert!(!Item::TorchOil.is_ingredient());
+        assert!(!Item::AlchemyBook.is_ingredient());
+    }
+
+    #[test]
+    fn should_get_item_value() {
+        assert_eq!(Item::Potion(Potion::Red).value(), Some(10));
+        assert_eq!(Item::Armor(Armor::default()).value(), Some(25));
+        assert_eq!(Item::Sonar.value(), Some(15));
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Dagger)).value(),
+            Some(15)
+        );
+        assert_eq!(Item::Weapon(Weapon::new(WeaponKind::Axe)).value(), Some(20));
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Mazebane)).value(),
+            None
+        );
+        assert_eq!(Item::MazeKey.value(), None);
+        assert_eq!(Item::Relic(Relic::Gem).value(), None);
+        assert_eq!(Item::Gold.value(), None);
     }
 
     #[test]
     fn should_get_whether_item_is_usable() {
+        for item in [
+            Item::Compass,
+            Item::MapScroll,
+            Item::Rope,
+            Item::TeleportStone,
+        ] {
+            assert_eq!(item.usable(PlayerState::Explore), true);
+            assert_eq!(item.usable(PlayerState::Fight), false);
+            assert_eq!(item.usable(PlayerState::Asleep), false);
+        }
         assert_eq!(Item::AlchemyBook.usable(PlayerState::Asleep), false);
         assert_eq!(Item::AlchemyBook.usable(PlayerState::Explore), false);
         assert_eq!(Item::AlchemyBook.usable(PlayerState::Fight), false);
-        assert_eq!(Item::Armor.usable(PlayerState::Asleep), false);
-        assert_eq!(Item::Armor.usable(PlayerState::Explore), true);
-        assert_eq!(Item::Armor.usable(PlayerState::Fight), true);
+        assert_eq!(
+            Item::Armor(Armor::default()).usable(PlayerState::Asleep),
+            false
+        );
+        assert_eq!(
+            Item::Armor(Armor::default()).usable(PlayerState::Explore),
+            true
+        );
+        assert_eq!(
+            Item::Armor(Armor::default()).usable(PlayerState::Fight),
+            true
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Dagger)).usable(PlayerState::Fight),
+            true
+        );
+        assert_eq!(
+            Item::Amulet(Amulet::Swiftness).usable(PlayerState::Asleep),
+            false
+        );
         assert_eq!(Item::MazeKey.usable(PlayerState::Asleep), false);
         assert_eq!(Item::MazeKey.usable(PlayerState::Explore), false);
         assert_eq!(Item::MazeKey.usable(PlayerState::Fight), false);
         assert_eq!(Item::PaintCan.usable(PlayerState::Asleep), false);
+        assert_eq!(Item::Relic(Relic::Blade).usable(PlayerState::Asleep), false);
+        assert_eq!(Item::Relic(Relic::Blade).usable(PlayerState::Explore), true);
+        assert_eq!(Item::Relic(Relic::Blade).usable(PlayerState::Fight), false);
         assert_eq!(Item::PaintCan.usable(PlayerState::Explore), false);
         assert_eq!(Item::PaintCan.usable(PlayerState::Fight), false);
         assert_eq!(
@@ -258,7 +598,11 @@ mod test {
         assert_eq!(Item::Sonar.usable(PlayerState::Fight), false);
         assert_eq!(Item::Talisman.usable(PlayerState::Asleep), false);
         assert_eq!(Item::Talisman.usable(PlayerState::Explore), false);
-        assert_eq!(Item::Talisman.usable(PlayerState::Fight), true);
+        assert_eq!(Item::Talisman.usable(PlayerState::Fight), false);
+        assert_eq!(Item::TorchOil.usable(PlayerState::Asleep), false);
+        assert_eq!(Item::TorchOil.usable(PlayerState::Explore), true);
+        assert_eq!(Item::TorchOil.usable(PlayerState::Fight), true);
+        assert_eq!(Item::Gold.usable(PlayerState::Explore), false);
     }
 
     #[test]
@@ -268,7 +612,13 @@ mod test {
             objects: Vec<Item>,
         }
         let test = Test {
-            objects: vec![Item::AlchemyBook, Item::Potion(Potion::Red)],
+            objects: vec![
+                Item::AlchemyBook,
+                Item::Potion(Potion::Red),
+                Item::Amulet(Amulet::Warding),
+                Item::Armor(Armor::default()),
+                Item::Weapon(Weapon::new(WeaponKind::Dagger)),
+            ],
         };
         let json = serde_json::to_string(&test).unwrap();
         let decoded: Test = serde_json::from_str(&json).unwrap();
@@ -276,16 +626,77 @@ mod test {
     }
 
     #[test]
-    fn should_convert_items_to_key() {
-        assert_eq!(Item::AlchemyBook, Item::from(Item::AlchemyBook.key()));
-        assert_eq!(Item::Armor, Item::from(Item::Armor.key()));
-        assert_eq!(Item::MazeKey, Item::from(Item::MazeKey.key()));
-        assert_eq!(Item::PaintCan, Item::from(Item::PaintCan.key()));
+    fn should_get_item_slot() {
         assert_eq!(
-            Item::Potion(Potion::Chamomille),
-            Item::from(Item::Potion(Potion::Chamomille).key())
+            Item::Amulet(Amulet::Warding).slot(),
+            Some(EquipmentSlot::Amulet)
+        );
+        assert_eq!(
+            Item::Armor(Armor::default()).slot(),
+            Some(EquipmentSlot::Armor)
+        );
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Sword)).slot(),
+            Some(EquipmentSlot::Weapon)
         );
-        assert_eq!(Item::Sonar, Item::from(Item::Sonar.key()));
-        assert_eq!(Item::Talisman, Item::from(Item::Talisman.key()));
+        assert_eq!(Item::Sonar.slot(), None);
+    }
+
+    #[test]
+    fn should_get_item_durability() {
+        assert_eq!(Item::Armor(Armor::default()).durability(), Some((8, 8)));
+        assert_eq!(
+            Item::Weapon(Weapon::new(WeaponKind::Sword)).durability(),
+            Some((15, 15))
+        );
+        assert_eq!(Item::Amulet(Amulet::Warding).durability(), None);
+    }
+
+    #[test]
+    fn should_deserialize_legacy_armor() {
+        let item: Item = serde_json::from_str(r#"{"type":"Armor"}"#).unwrap();
+        assert_eq!(item, Item::Armor(Armor::default()));
+    }
+
+    #[test]
+    fn should_convert_items_to_key() {
+        let items = [
+            Item::AlchemyBook,
+            Item::Amulet(Amulet::Swiftness),
+            Item::Armor(Armor::default()),
+            Item::Weapon(Weapon::new(WeaponKind::Axe)),
+            Item::Weapon(Weapon::new(WeaponKind::Mazebane)),
+            Item::Relic(Relic::Gem),
+            Item::MazeKey,
+            Item::PaintCan,
+            Item::Potion(Potion::Chamomille),
+            Item::Sonar,
+            Item::Talisman,
+            Item::Herb(Herb::Nightshade),
+            Item::TorchOil,
+            Item::Compass,
+            Item::MapScroll,
+            Item::TeleportStone,
+            Item::Rope,
+            Item::Lockpick,
+            Item::Gold,
+            Item::Artifact(Artifact::TrollHeart),
+        ];
+        for item in items {
+            assert_eq!(Item::from_key(item.key()), Some(item));
+        }
+        assert_eq!(Item::from_key(1), Some(Item::Armor(Armor::default())));
+    }
+
+    #[test]
+    fn should_not_convert_unknown_keys() {
+        assert_eq!(Item::from_key(13), None);
+        assert_eq!(Item::from_key(200), None);
+        assert_eq!(Item::from_key(0x1f00), None);
+        assert_eq!(Item::from_key(0x3fff), None);
+        assert_eq!(Item::from_key(0x4fff), None);
+        assert_eq!(Item::from_key(0x5fff), None);
+        assert_eq!(Item::from_key(0x6fff), None);
+        assert_eq!(Item::from_key(0x7000), None);
     }
 }
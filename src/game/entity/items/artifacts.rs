@@ -0,0 +1,73 @@
+//! # Artifacts
+//!
+//! Rare artifacts, which grant a passive bonus while carried
+
+/// Unique artifacts; there's at most one of each in the maze
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(tag = "artifact")]
+pub enum Artifact {
+    /// Senses the enemies in the adjacent rooms when entering a room
+    OwlEye,
+    /// Increases the chance to escape from a fight
+    RabbitFoot,
+    /// Halves the time spent asleep
+    RoosterFeather,
+    /// Regenerates 1 HP every few turns
+    TrollHeart,
+}
+
+impl Artifact {
+    pub const ALL: [Artifact; 4] = [
+        Artifact::OwlEye,
+        Artifact::RabbitFoot,
+        Artifact::RoosterFeather,
+        Artifact::TrollHeart,
+    ];
+
+    pub fn key(&self) -> u32 {
+        match self {
+            Self::OwlEye => 0x6000,
+            Self::RabbitFoot => 0x6001,
+            Self::RoosterFeather => 0x6002,
+            Self::TrollHeart => 0x6003,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::OwlEye => "Owl's eye",
+            Self::RabbitFoot => "Rabbit's foot",
+            Self::RoosterFeather => "Rooster feather",
+            Self::TrollHeart => "Troll heart",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            Self::OwlEye => "A glassy eye which never closes. While carried, you sense the enemies lurking in the rooms next to you",
+            Self::RabbitFoot => "It brings luck, except to the rabbit. While carried, it's easier to escape from a fight",
+            Self::RoosterFeather => "It still smells of dawn. While carried, you sleep half the time",
+            Self::TrollHeart => "It's still beating. While carried, you slowly regenerate your wounds",
+        }
+    }
+
+    pub fn from_key(key: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.key() == key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_artifact_to_key() {
+        for artifact in Artifact::ALL {
+            assert_eq!(Artifact::from_key(artifact.key()), Some(artifact));
+        }
+        assert_eq!(Artifact::from_key(0x6004), None);
+    }
+}
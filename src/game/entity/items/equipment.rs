@@ -0,0 +1,321 @@
+//! # Equipment
+//!
+//! Items which can be worn by the player in an equipment slot
+
+use crate::game::Hp;
+
+const AXE_DURABILITY: u8 = 10;
+const MAZEBANE_DURABILITY: u8 = 255;
+const DAGGER_DURABILITY: u8 = 20;
+const SWORD_DURABILITY: u8 = 15;
+const ARMOR_DURABILITY: u8 = 8;
+
+/// Equipment slot
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum EquipmentSlot {
+    Amulet,
+    Armor,
+    Weapon,
+}
+
+/// Weapon types
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum WeaponKind {
+    /// Deals 4-8 HP, but it's heavy and misses often
+    Axe,
+    /// Deals 2-5 HP; light and accurate
+    Dagger,
+    /// Deals 4-7 HP; forged from the three relics, it never breaks and it's the only weapon which can hurt Don Maze
+    Mazebane,
+    /// Deals 3-6 HP
+    Sword,
+}
+
+impl WeaponKind {
+    /// Key offset to use in dictionaries
+    fn key(&self) -> u32 {
+        match self {
+            Self::Axe => 0,
+            Self::Dagger => 1,
+            Self::Sword => 2,
+            Self::Mazebane => 3,
+        }
+    }
+
+    /// Accuracy 1-100 for weapon
+    pub fn accuracy(&self) -> u8 {
+        match self {
+            Self::Axe => 75,
+            Self::Dagger => 100,
+            Self::Mazebane => 95,
+            Self::Sword => 90,
+        }
+    }
+
+    /// Bonus (%) to the player's critical hit probability.
+    /// Light weapons find weak spots more easily
+    pub fn critical_bonus(&self) -> u8 {
+        match self {
+            Self::Axe => 0,
+            Self::Dagger => 15,
+            Self::Mazebane => 10,
+            Self::Sword => 5,
+        }
+    }
+
+    /// Get the damage dealt by the weapon for a roll in range 0-99
+    pub fn damage(&self, roll: u8) -> Hp {
+        match (self, roll) {
+            (Self::Axe, x) if x < 20 => 4,
+            (Self::Axe, x) if x < 50 => 5,
+            (Self::Axe, x) if x < 75 => 6,
+            (Self::Axe, x) if x < 92 => 7,
+            (Self::Axe, _) => 8,
+            (Self::Dagger, x) if x < 30 => 2,
+            (Self::Dagger, x) if x < 65 => 3,
+            (Self::Dagger, x) if x < 90 => 4,
+            (Self::Dagger, _) => 5,
+            (Self::Mazebane, x) if x < 30 => 4,
+            (Self::Mazebane, x) if x < 65 => 5,
+            (Self::Mazebane, x) if x < 90 => 6,
+            (Self::Mazebane, _) => 7,
+            (Self::Sword, x) if x < 25 => 3,
+            (Self::Sword, x) if x < 60 => 4,
+            (Self::Sword, x) if x < 85 => 5,
+            (Self::Sword, _) => 6,
+        }
+    }
+
+    /// Durability of a brand new weapon
+    pub fn max_durability(&self) -> u8 {
+        match self {
+            Self::Axe => AXE_DURABILITY,
+            Self::Dagger => DAGGER_DURABILITY,
+            Self::Mazebane => MAZEBANE_DURABILITY,
+            Self::Sword => SWORD_DURABILITY,
+        }
+    }
+}
+
+/// A weapon. Each hit decreases its durability; when it reaches 0, the weapon breaks
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub struct Weapon {
+    pub kind: WeaponKind,
+    durability: u8,
+}
+
+impl Weapon {
+    /// Create a brand new weapon
+    pub fn new(kind: WeaponKind) -> Self {
+        Self {
+            kind,
+            durability: kind.max_durability(),
+        }
+    }
+
+    /// Get current durability
+    pub fn durability(&self) -> u8 {
+        self.durability
+    }
+
+    /// Decrease durability by one. Returns whether the weapon is broken.
+    /// The Mazebane never wears
+    pub fn wear(&mut self) -> bool {
+        if self.kind == WeaponKind::Mazebane {
+            return false;
+        }
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+
+    pub(super) fn key(&self) -> u32 {
+        0x1000 + (self.kind.key() << 8) + self.durability as u32
+    }
+
+    pub(super) fn from_key(key: u32) -> Option<Self> {
+        let kind = match (key >> 8) & 0x0f {
+            0 => WeaponKind::Axe,
+            1 => WeaponKind::Dagger,
+            2 => WeaponKind::Sword,
+            3 => WeaponKind::Mazebane,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            durability: (key & 0xff) as u8,
+        })
+    }
+}
+
+/// An armor reduces incoming damage by 1.
+/// Each blocked hit decreases its durability; when it reaches 0, the armor breaks
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(default)]
+pub struct Armor {
+    durability: u8,
+}
+
+impl Default for Armor {
+    fn default() -> Self {
+        Self {
+            durability: ARMOR_DURABILITY,
+        }
+    }
+}
+
+impl Armor {
+    /// Damage absorbed by the armor for each hit
+    pub const DAMAGE_REDUCTION: Hp = 1;
+
+    /// Get current durability
+    pub fn durability(&self) -> u8 {
+        self.durability
+    }
+
+    /// Durability of a brand new armor
+    pub fn max_durability(&self) -> u8 {
+        ARMOR_DURABILITY
+    }
+
+    /// Decrease durability by one. Returns whether the armor is broken
+    pub fn wear(&mut self) -> bool {
+        self.durability = self.durability.saturating_sub(1);
+        self.durability == 0
+    }
+
+    pub(super) fn key(&self) -> u32 {
+        0x2000 + self.durability as u32
+    }
+
+    pub(super) fn from_key(key: u32) -> Self {
+        Self {
+            durability: (key & 0xff) as u8,
+        }
+    }
+}
+
+/// Amulets have a passive effect while worn and never break
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(tag = "amulet")]
+pub enum Amulet {
+    /// Increases escape chance by 15%
+    Swiftness,
+    /// Decreases enemies' accuracy by 15
+    Warding,
+}
+
+impl Amulet {
+    /// Escape chance bonus given by the amulet
+    pub fn escape_bonus(&self) -> u8 {
+        match self {
+            Self::Swiftness => 15,
+            Self::Warding => 0,
+        }
+    }
+
+    /// Enemies' accuracy malus given by the amulet
+    pub fn enemy_accuracy_malus(&self) -> u8 {
+        match self {
+            Self::Swiftness => 0,
+            Self::Warding => 15,
+        }
+    }
+
+    pub(super) fn key(&self) -> u32 {
+        match self {
+            Self::Swiftness => 0x3000,
+            Self::Warding => 0x3001,
+        }
+    }
+
+    pub(super) fn from_key(key: u32) -> Option<Self> {
+        match key {
+            0x3000 => Some(Self::Swiftness),
+            0x3001 => Some(Self::Warding),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_get_weapon_damage() {
+        assert_eq!(WeaponKind::Axe.damage(0), 4);
+        assert_eq!(WeaponKind::Axe.damage(99), 8);
+        assert_eq!(WeaponKind::Dagger.damage(0), 2);
+        assert_eq!(WeaponKind::Dagger.damage(99), 5);
+        assert_eq!(WeaponKind::Sword.damage(0), 3);
+        assert_eq!(WeaponKind::Sword.damage(99), 6);
+        assert_eq!(WeaponKind::Mazebane.damage(0), 4);
+        assert_eq!(WeaponKind::Mazebane.damage(99), 7);
+    }
+
+    #[test]
+    fn should_get_weapon_accuracy() {
+        assert_eq!(WeaponKind::Axe.accuracy(), 75);
+        assert_eq!(WeaponKind::Dagger.accuracy(), 100);
+        assert_eq!(WeaponKind::Sword.accuracy(), 90);
+    }
+
+    #[test]
+    fn should_get_weapon_critical_bonus() {
+        assert_eq!(WeaponKind::Axe.critical_bonus(), 0);
+        assert_eq!(WeaponKind::Dagger.critical_bonus(), 15);
+        assert_eq!(WeaponKind::Mazebane.critical_bonus(), 10);
+        assert_eq!(WeaponKind::Sword.critical_bonus(), 5);
+    }
+
+    #[test]
+    fn should_wear_weapon() {
+        let mut weapon = Weapon::new(WeaponKind::Axe);
+        assert_eq!(weapon.durability(), AXE_DURABILITY);
+        for _ in 1..AXE_DURABILITY {
+            assert_eq!(weapon.wear(), false);
+        }
+        assert_eq!(weapon.wear(), true);
+        assert_eq!(weapon.durability(), 0);
+        // mazebane never breaks
+        let mut weapon = Weapon::new(WeaponKind::Mazebane);
+        assert_eq!(weapon.wear(), false);
+        assert_eq!(weapon.durability(), MAZEBANE_DURABILITY);
+    }
+
+    #[test]
+    fn should_wear_armor() {
+        let mut armor = Armor::default();
+        assert_eq!(armor.durability(), ARMOR_DURABILITY);
+        assert_eq!(armor.max_durability(), ARMOR_DURABILITY);
+        for _ in 1..ARMOR_DURABILITY {
+            assert_eq!(armor.wear(), false);
+        }
+        assert_eq!(armor.wear(), true);
+    }
+
+    #[test]
+    fn should_get_amulet_bonuses() {
+        assert_eq!(Amulet::Swiftness.escape_bonus(), 15);
+        assert_eq!(Amulet::Swiftness.enemy_accuracy_malus(), 0);
+        assert_eq!(Amulet::Warding.escape_bonus(), 0);
+        assert_eq!(Amulet::Warding.enemy_accuracy_malus(), 15);
+    }
+
+    #[test]
+    fn should_convert_equipment_to_key() {
+        let mut weapon = Weapon::new(WeaponKind::Sword);
+        weapon.wear();
+        assert_eq!(Weapon::from_key(weapon.key()), Some(weapon));
+        let mut armor = Armor::default();
+        armor.wear();
+        assert_eq!(Armor::from_key(armor.key()), armor);
+        assert_eq!(
+            Amulet::from_key(Amulet::Warding.key()),
+            Some(Amulet::Warding)
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! # Herbs
+//!
+//! Herbs items, used as ingredients to brew potions
+
+/// Herbs growing in the maze
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(tag = "herb")]
+pub enum Herb {
+    Lavender,
+    Mandrake,
+    Nightshade,
+    Sage,
+}
+
+impl Herb {
+    /// All the herbs which can be found in the maze
+    pub const ALL: [Herb; 4] = [Herb::Lavender, Herb::Mandrake, Herb::Nightshade, Herb::Sage];
+
+    pub fn key(&self) -> u32 {
+        match self {
+            Self::Lavender => 0x5000,
+            Self::Mandrake => 0x5001,
+            Self::Nightshade => 0x5002,
+            Self::Sage => 0x5003,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Lavender => "Lavender",
+            Self::Mandrake => "Mandrake root",
+            Self::Nightshade => "Nightshade",
+            Self::Sage => "Sage leaves",
+        }
+    }
+
+    pub fn from_key(key: u32) -> Option<Self> {
+        match key {
+            0x5000 => Some(Self::Lavender),
+            0x5001 => Some(Self::Mandrake),
+            0x5002 => Some(Self::Nightshade),
+            0x5003 => Some(Self::Sage),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_get_herb_name() {
+        assert_eq!(Herb::Lavender.name(), "Lavender");
+        assert_eq!(Herb::Mandrake.name(), "Mandrake root");
+        assert_eq!(Herb::Nightshade.name(), "Nightshade");
+        assert_eq!(Herb::Sage.name(), "Sage leaves");
+    }
+
+    #[test]
+    fn should_convert_herb_to_key() {
+        for herb in Herb::ALL {
+            assert_eq!(Herb::from_key(herb.key()), Some(herb));
+        }
+        assert_eq!(Herb::from_key(0x5004), None);
+    }
+}
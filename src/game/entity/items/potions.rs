@@ -2,6 +2,73 @@
 //!
 //! Potions items
 
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
+
+use crate::game::Hp;
+
+/// The look of an unidentified potion
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Appearance {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// The looks potions can have; each run assigns a different one to each potion kind
+const APPEARANCES: [Appearance; 12] = [
+    Appearance::new(
+        "Murky green flask",
+        "A murky green liquid. It smells of swamp",
+    ),
+    Appearance::new(
+        "Bubbling red vial",
+        "A red liquid, bubbling on its own. It smells of iron",
+    ),
+    Appearance::new(
+        "Cloudy white bottle",
+        "A cloudy white liquid. It smells of milk and flowers",
+    ),
+    Appearance::new(
+        "Golden phial",
+        "A thick golden liquid. It smells sweet, like honey",
+    ),
+    Appearance::new(
+        "Black flask",
+        "A liquid so dark you can't see through it. It smells of ash",
+    ),
+    Appearance::new(
+        "Glowing blue vial",
+        "A faintly glowing blue liquid. It smells of rain",
+    ),
+    Appearance::new(
+        "Purple bottle",
+        "A purple liquid with a silvery shimmer. It smells of lavender",
+    ),
+    Appearance::new(
+        "Rusty brown flask",
+        "A brown, muddy liquid. It smells of rotten eggs",
+    ),
+    Appearance::new(
+        "Clear crystal vial",
+        "It looks like water, but it smells of nothing at all",
+    ),
+    Appearance::new(
+        "Orange gourd",
+        "An orange liquid in a dried gourd. It smells of spices",
+    ),
+    Appearance::new(
+        "Smoking grey phial",
+        "A grey liquid which keeps smoking. It smells of sulphur",
+    ),
+    Appearance::new(
+        "Pink bottle",
+        "A pink, sparkling liquid. It smells of berries",
+    ),
+];
+
 /// Potion types
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
 #[serde(tag = "potion")]
@@ -9,11 +76,13 @@ pub enum Potion {
     // -- bonus
     /// Heals 2 HP
     Mead,
-    /// Heals 5 HP
+    /// Heals 5 HP and makes you hasted
     Red,
-    /// A fairy in a bottle; restores all HP
+    /// A fairy in a bottle; restores all HP and makes you regenerate
     FairyInABottle,
-    /// Heals all HP and increase max HP by 5; kinda rare though
+    /// Cures poison and heals 1 HP; can only be brewed
+    Antidote,
+    /// Heals all HP, increase max HP by 5 and blesses you; kinda rare though
     UnicornElixir,
     // -- malus
     /// Decrease HP by 1
@@ -22,13 +91,79 @@ pub enum Potion {
     DaemonsBlood,
     /// Makes you sleep for 3 turns, but restores 1 HP
     Chamomille,
-    /// Decrease HP by 2
+    /// Poisons you for 3 turns
     SnakePoison,
     /// it's game over; very rare though
     DeadlyPoison,
 }
 
+impl Appearance {
+    const fn new(name: &'static str, description: &'static str) -> Self {
+        Self { name, description }
+    }
+}
+
+/// What the player knows about the potions in the current run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PotionKnowledge<'a> {
+    /// The maze seed; potion appearances change with it
+    seed: &'a str,
+    /// Potions identified by drinking them
+    identified: &'a HashSet<Potion>,
+    /// The alchemy book identifies all potions
+    has_alchemy_book: bool,
+}
+
+impl<'a> PotionKnowledge<'a> {
+    pub fn new(seed: &'a str, identified: &'a HashSet<Potion>, has_alchemy_book: bool) -> Self {
+        Self {
+            seed,
+            identified,
+            has_alchemy_book,
+        }
+    }
+
+    /// Returns whether the player knows what `potion` is
+    pub fn is_identified(&self, potion: Potion) -> bool {
+        self.has_alchemy_book || self.identified.contains(&potion)
+    }
+
+    /// Get the look of `potion` in this run
+    pub fn appearance(&self, potion: Potion) -> Appearance {
+        let mut rng: Pcg64 = Seeder::from(format!("{}:potions", self.seed)).into_rng();
+        let mut appearances = APPEARANCES;
+        appearances.shuffle(&mut rng);
+        let index = Potion::ALL.iter().position(|x| *x == potion).unwrap();
+        appearances[index]
+    }
+}
+
+/// The effect of a potion thrown at an enemy
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ThrownEffect {
+    /// Deals damage to the enemy
+    Damage(Hp),
+    /// Heals the enemy
+    Heal(Hp),
+    /// Puts the enemy asleep, so it won't attack this turn
+    Sleep,
+}
+
 impl Potion {
+    /// All the potion kinds
+    pub const ALL: [Potion; 10] = [
+        Potion::Mead,
+        Potion::Red,
+        Potion::FairyInABottle,
+        Potion::Antidote,
+        Potion::UnicornElixir,
+        Potion::Vinegar,
+        Potion::DaemonsBlood,
+        Potion::Chamomille,
+        Potion::SnakePoison,
+        Potion::DeadlyPoison,
+    ];
+
     pub fn key(&self) -> u32 {
         match self {
             Self::Mead => 256,
@@ -40,11 +175,18 @@ impl Potion {
             Self::SnakePoison => 262,
             Self::DeadlyPoison => 263,
             Self::FairyInABottle => 264,
+            Self::Antidote => 265,
         }
     }
 
+    /// Get potion from its key; returns `None` if the key doesn't belong to any potion
+    pub fn from_key(key: u32) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.key() == key)
+    }
+
     pub fn name(&self) -> &str {
         match self {
+            Self::Antidote => "Weak antidote",
             Self::Mead => "Mead",
             Self::Red => "Red potion",
             Self::UnicornElixir => "Unicorn elixir",
@@ -59,20 +201,24 @@ impl Potion {
 
     pub fn description(&self) -> &str {
         match self {
+            Self::Antidote => "Cures poison and restores 1HP",
             Self::Mead => "Restores 2HP",
-            Self::Red => "Restores 5HP",
-            Self::FairyInABottle => "Restores all HP",
-            Self::UnicornElixir => "Restores all HP and increase max HP by 5",
+            Self::Red => "Restores 5HP and makes you faster for 3 turns",
+            Self::FairyInABottle => "Restores all HP and keeps healing you for 5 turns",
+            Self::UnicornElixir => {
+                "Restores all HP, increase max HP by 5 and blesses you for 10 turns"
+            }
             Self::Vinegar => "Decrease HP by 1",
             Self::DaemonsBlood => "Decrease HP and max HP by 1",
             Self::Chamomille => "Put you asleep for 3 turns, but restores 1HP",
-            Self::SnakePoison => "Decrease HP by 2",
+            Self::SnakePoison => "Poisons you, decreasing HP by 1 for 3 turns",
             Self::DeadlyPoison => "Drink it and you will die",
         }
     }
 
     pub fn effect(&self) -> &str {
         match self {
+            Self::Antidote => "It tastes bitter, but the poison fades away",
             Self::Chamomille => "You suddenly feel sleepy, but restored at the same time",
             Self::DaemonsBlood => "Uuugh, that sucks, tastes of iron and rotten flesh, you immediately feel bad",
             Self::DeadlyPoison => {
@@ -86,21 +232,20 @@ impl Potion {
             Self::Vinegar => "Uuugh, it's vinegar. Probably I should have smelled it before drinking it..."
         }
     }
-}
 
-impl From<u32> for Potion {
-    fn from(key: u32) -> Self {
-        match key {
-            256 => Self::Mead,
-            257 => Self::Red,
-            258 => Self::UnicornElixir,
-            259 => Self::Vinegar,
-            260 => Self::DaemonsBlood,
-            261 => Self::Chamomille,
-            262 => Self::SnakePoison,
-            263 => Self::DeadlyPoison,
-            264 => Self::FairyInABottle,
-            _ => Potion::Mead, // NOTE: default potion
+    /// Get the effect of the potion when thrown at an enemy
+    pub fn thrown_effect(&self) -> ThrownEffect {
+        match self {
+            Self::Antidote => ThrownEffect::Heal(1),
+            Self::Chamomille => ThrownEffect::Sleep,
+            Self::DaemonsBlood => ThrownEffect::Damage(1),
+            Self::DeadlyPoison => ThrownEffect::Damage(255),
+            Self::FairyInABottle => ThrownEffect::Heal(255),
+            Self::Mead => ThrownEffect::Heal(2),
+            Self::Red => ThrownEffect::Heal(5),
+            Self::SnakePoison => ThrownEffect::Damage(3),
+            Self::UnicornElixir => ThrownEffect::Heal(255),
+            Self::Vinegar => ThrownEffect::Damage(1),
         }
     }
 }
@@ -123,17 +268,24 @@ mod test {
         assert_eq!(Potion::SnakePoison.name(), "Snake poison");
         assert_eq!(Potion::DeadlyPoison.name(), "Deadly poison");
         assert_eq!(Potion::FairyInABottle.name(), "Fairy in a bottle");
+        assert_eq!(Potion::Antidote.name(), "Weak antidote");
     }
 
     #[test]
     fn should_get_potion_description() {
         assert_eq!(Potion::Mead.description(), "Restores 2HP");
-        assert_eq!(Potion::Red.description(), "Restores 5HP");
+        assert_eq!(
+            Potion::Red.description(),
+            "Restores 5HP and makes you faster for 3 turns"
+        );
         assert_eq!(
             Potion::UnicornElixir.description(),
-            "Restores all HP and increase max HP by 5"
+            "Restores all HP, increase max HP by 5 and blesses you for 10 turns"
+        );
+        assert_eq!(
+            Potion::FairyInABottle.description(),
+            "Restores all HP and keeps healing you for 5 turns"
         );
-        assert_eq!(Potion::FairyInABottle.description(), "Restores all HP");
         assert_eq!(Potion::Vinegar.description(), "Decrease HP by 1");
         assert_eq!(
             Potion::DaemonsBlood.description(),
@@ -143,13 +295,41 @@ mod test {
             Potion::Chamomille.description(),
             "Put you asleep for 3 turns, but restores 1HP"
         );
-        assert_eq!(Potion::SnakePoison.description(), "Decrease HP by 2");
+        assert_eq!(
+            Potion::SnakePoison.description(),
+            "Poisons you, decreasing HP by 1 for 3 turns"
+        );
         assert_eq!(
             Potion::DeadlyPoison.description(),
             "Drink it and you will die"
         );
     }
 
+    #[test]
+    fn should_get_potion_thrown_effect() {
+        assert_eq!(Potion::Chamomille.thrown_effect(), ThrownEffect::Sleep);
+        assert_eq!(
+            Potion::DaemonsBlood.thrown_effect(),
+            ThrownEffect::Damage(1)
+        );
+        assert_eq!(
+            Potion::DeadlyPoison.thrown_effect(),
+            ThrownEffect::Damage(255)
+        );
+        assert_eq!(
+            Potion::FairyInABottle.thrown_effect(),
+            ThrownEffect::Heal(255)
+        );
+        assert_eq!(Potion::Mead.thrown_effect(), ThrownEffect::Heal(2));
+        assert_eq!(Potion::Red.thrown_effect(), ThrownEffect::Heal(5));
+        assert_eq!(Potion::SnakePoison.thrown_effect(), ThrownEffect::Damage(3));
+        assert_eq!(
+            Potion::UnicornElixir.thrown_effect(),
+            ThrownEffect::Heal(255)
+        );
+        assert_eq!(Potion::Vinegar.thrown_effect(), ThrownEffect::Damage(1));
+    }
+
     #[test]
     fn should_get_potion_effect() {
         assert_eq!(
@@ -188,27 +368,40 @@ mod test {
     }
 
     #[test]
-    fn should_convert_to_key() {
-        assert_eq!(Potion::Mead, Potion::from(Potion::Mead.key()));
-        assert_eq!(Potion::Red, Potion::from(Potion::Red.key()));
+    fn should_give_potions_an_appearance_per_seed() {
+        let identified = HashSet::new();
+        let knowledge = PotionKnowledge::new("foo", &identified, false);
+        let appearances: HashSet<&str> = Potion::ALL
+            .iter()
+            .map(|x| knowledge.appearance(*x).name)
+            .collect();
+        assert_eq!(appearances.len(), Potion::ALL.len());
         assert_eq!(
-            Potion::UnicornElixir,
-            Potion::from(Potion::UnicornElixir.key())
-        );
-        assert_eq!(Potion::Vinegar, Potion::from(Potion::Vinegar.key()));
-        assert_eq!(
-            Potion::DaemonsBlood,
-            Potion::from(Potion::DaemonsBlood.key())
-        );
-        assert_eq!(Potion::Chamomille, Potion::from(Potion::Chamomille.key()));
-        assert_eq!(Potion::SnakePoison, Potion::from(Potion::SnakePoison.key()));
-        assert_eq!(
-            Potion::DeadlyPoison,
-            Potion::from(Potion::DeadlyPoison.key())
-        );
-        assert_eq!(
-            Potion::FairyInABottle,
-            Potion::from(Potion::FairyInABottle.key())
+            knowledge.appearance(Potion::Mead),
+            knowledge.appearance(Potion::Mead)
         );
+        let other = PotionKnowledge::new("bar", &identified, false);
+        assert!(Potion::ALL
+            .iter()
+            .any(|x| knowledge.appearance(*x) != other.appearance(*x)));
+    }
+
+    #[test]
+    fn should_identify_potions() {
+        let identified = HashSet::from([Potion::Mead]);
+        let knowledge = PotionKnowledge::new("foo", &identified, false);
+        assert!(knowledge.is_identified(Potion::Mead));
+        assert!(!knowledge.is_identified(Potion::Red));
+        let knowledge = PotionKnowledge::new("foo", &identified, true);
+        assert!(knowledge.is_identified(Potion::Red));
+    }
+
+    #[test]
+    fn should_convert_to_key() {
+        for potion in Potion::ALL {
+            assert_eq!(Potion::from_key(potion.key()), Some(potion));
+        }
+        assert_eq!(Potion::from_key(255), None);
+        assert_eq!(Potion::from_key(266), None);
     }
 }
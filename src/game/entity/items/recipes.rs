@@ -0,0 +1,113 @@
+//! # Recipes
+//!
+//! Alchemy recipes, used to brew potions out of a pair of ingredients
+
+use super::{Herb, Item, Potion};
+
+/// A pair of ingredients which brewed together make a potion
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub struct Recipe {
+    pub ingredients: (Item, Item),
+    pub potion: Potion,
+}
+
+/// Every recipe known to alchemy
+const RECIPES: &[Recipe] = &[
+    Recipe::new(
+        Item::Potion(Potion::Vinegar),
+        Item::Potion(Potion::Mead),
+        Potion::Antidote,
+    ),
+    Recipe::new(
+        Item::Potion(Potion::Red),
+        Item::Potion(Potion::Red),
+        Potion::FairyInABottle,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Sage),
+        Item::Potion(Potion::Mead),
+        Potion::Red,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Lavender),
+        Item::Potion(Potion::Mead),
+        Potion::Chamomille,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Sage),
+        Item::Potion(Potion::SnakePoison),
+        Potion::Antidote,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Nightshade),
+        Item::Potion(Potion::Mead),
+        Potion::SnakePoison,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Nightshade),
+        Item::Herb(Herb::Nightshade),
+        Potion::DeadlyPoison,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Mandrake),
+        Item::Potion(Potion::DaemonsBlood),
+        Potion::Mead,
+    ),
+    Recipe::new(
+        Item::Herb(Herb::Mandrake),
+        Item::Potion(Potion::FairyInABottle),
+        Potion::UnicornElixir,
+    ),
+];
+
+impl Recipe {
+    const fn new(first: Item, second: Item, potion: Potion) -> Self {
+        Self {
+            ingredients: (first, second),
+            potion,
+        }
+    }
+
+    /// Find the recipe which uses `first` and `second` as ingredients, in any order
+    pub fn find(first: Item, second: Item) -> Option<Self> {
+        RECIPES.iter().copied().find(|x| x.uses(first, second))
+    }
+
+    /// Returns whether the recipe uses `first` and `second` as ingredients, in any order
+    pub fn uses(&self, first: Item, second: Item) -> bool {
+        self.ingredients == (first, second) || self.ingredients == (second, first)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_find_recipe() {
+        assert_eq!(
+            Recipe::find(Item::Potion(Potion::Mead), Item::Potion(Potion::Vinegar))
+                .unwrap()
+                .potion,
+            Potion::Antidote
+        );
+        assert_eq!(
+            Recipe::find(Item::Potion(Potion::Red), Item::Potion(Potion::Red))
+                .unwrap()
+                .potion,
+            Potion::FairyInABottle
+        );
+        assert!(Recipe::find(Item::Herb(Herb::Sage), Item::Herb(Herb::Lavender)).is_none());
+    }
+
+    #[test]
+    fn should_have_only_ingredients_in_recipes() {
+        for recipe in RECIPES {
+            assert!(recipe.ingredients.0.is_ingredient());
+            assert!(recipe.ingredients.1.is_ingredient());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! # Relics
+//!
+//! Relics items, which combined together forge the Mazebane
+
+/// The three relics scattered in the maze
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+#[serde(tag = "relic")]
+pub enum Relic {
+    Blade,
+    Gem,
+    Hilt,
+}
+
+impl Relic {
+    /// All the relics required to forge the Mazebane
+    pub const ALL: [Relic; 3] = [Relic::Blade, Relic::Gem, Relic::Hilt];
+
+    pub fn key(&self) -> u32 {
+        match self {
+            Self::Blade => 0x4000,
+            Self::Gem => 0x4001,
+            Self::Hilt => 0x4002,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Blade => "Shattered blade",
+            Self::Gem => "Labyrinth gem",
+            Self::Hilt => "Ancient hilt",
+        }
+    }
+
+    pub fn from_key(key: u32) -> Option<Self> {
+        match key {
+            0x4000 => Some(Self::Blade),
+            0x4001 => Some(Self::Gem),
+            0x4002 => Some(Self::Hilt),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_get_relic_name() {
+        assert_eq!(Relic::Blade.name(), "Shattered blade");
+        assert_eq!(Relic::Gem.name(), "Labyrinth gem");
+        assert_eq!(Relic::Hilt.name(), "Ancient hilt");
+    }
+
+    #[test]
+    fn should_convert_relic_to_key() {
+        for relic in Relic::ALL {
+            assert_eq!(Relic::from_key(relic.key()), Some(relic));
+        }
+        assert_eq!(Relic::from_key(0x4003), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! # Npc
+//!
+//! Non-playable characters, which never attack the player.
+//! Some of them wander through the maze like enemies, others sit in their room and talk to the player
+
+mod dialogue;
+
+pub use dialogue::{Dialogue, DialogueEffect, EXIT_HINT, KEY_HINT};
+
+use super::Item;
+
+/// A non-playable character
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type")]
+pub enum Npc {
+    /// The ghost of a lost adventurer
+    Ghost(Speaker),
+    /// Trades items for gold
+    Merchant(Merchant),
+    /// A prisoner trapped in the maze
+    Prisoner(Speaker),
+}
+
+impl Npc {
+    /// Return the npc name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Ghost(_) => "Ghost of a lost adventurer",
+            Self::Merchant(_) => "Wandering merchant",
+            Self::Prisoner(_) => "Trapped prisoner",
+        }
+    }
+
+    /// Returns whether the npc wanders through the maze
+    pub fn wanders(&self) -> bool {
+        matches!(self, Self::Merchant(_))
+    }
+
+    /// Get the dialogue spoken by the npc, if it talks
+    pub fn dialogue(&self) -> Option<&'static Dialogue> {
+        match self {
+            Self::Ghost(_) => Some(Dialogue::ghost()),
+            Self::Merchant(_) => None,
+            Self::Prisoner(_) => Some(Dialogue::prisoner()),
+        }
+    }
+
+    /// Get the speaker state, if the npc talks
+    pub fn speaker_mut(&mut self) -> Option<&mut Speaker> {
+        match self {
+            Self::Ghost(speaker) | Self::Prisoner(speaker) => Some(speaker),
+            Self::Merchant(_) => None,
+        }
+    }
+}
+
+/// State of an npc which talks to the player
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Speaker {
+    /// Whether the npc has already triggered a dialogue effect; each npc triggers only one
+    #[serde(default)]
+    effect_triggered: bool,
+}
+
+impl Speaker {
+    /// Returns whether the npc has already triggered a dialogue effect
+    pub fn effect_triggered(&self) -> bool {
+        self.effect_triggered
+    }
+
+    /// Mark the dialogue effect as triggered
+    pub fn trigger_effect(&mut self) {
+        self.effect_triggered = true;
+    }
+}
+
+/// The wandering merchant sells the items in his stock and buys the items the player doesn't need anymore
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Merchant {
+    /// Items the merchant is selling
+    stock: Vec<Item>,
+}
+
+impl Merchant {
+    pub fn new(stock: Vec<Item>) -> Self {
+        Self { stock }
+    }
+
+    /// Items the merchant is selling
+    pub fn stock(&self) -> &[Item] {
+        &self.stock
+    }
+
+    /// Returns whether the merchant has `item` in stock
+    pub fn has(&self, item: Item) -> bool {
+        self.stock.contains(&item)
+    }
+
+    /// Remove `item` from the stock once sold to the player
+    pub fn sell(&mut self, item: Item) {
+        if let Some(index) = self.stock.iter().position(|x| *x == item) {
+            self.stock.remove(index);
+        }
+    }
+
+    /// Put `item` bought from the player in the stock
+    pub fn buy(&mut self, item: Item) {
+        self.stock.push(item);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::game::entity::Potion;
+
+    #[test]
+    fn should_trade_with_merchant() {
+        let mut merchant = Merchant::new(vec![Item::Sonar, Item::Potion(Potion::Red)]);
+        assert!(merchant.has(Item::Sonar));
+        merchant.sell(Item::Sonar);
+        assert!(!merchant.has(Item::Sonar));
+        assert_eq!(merchant.stock(), &[Item::Potion(Potion::Red)]);
+        merchant.buy(Item::Talisman);
+        assert_eq!(
+            merchant.stock(),
+            &[Item::Potion(Potion::Red), Item::Talisman]
+        );
+    }
+
+    #[test]
+    fn should_serialize_npc() {
+        let npc = Npc::Merchant(Merchant::new(vec![Item::Sonar]));
+        let json = serde_json::to_string(&npc).unwrap();
+        let decoded: Npc = serde_json::from_str(&json).unwrap();
+        assert_eq!(npc, decoded);
+        let mut npc = Npc::Ghost(Speaker::default());
+        npc.speaker_mut().unwrap().trigger_effect();
+        let json = serde_json::to_string(&npc).unwrap();
+        let decoded: Npc = serde_json::from_str(&json).unwrap();
+        assert_eq!(npc, decoded);
+    }
+
+    #[test]
+    fn should_tell_which_npcs_talk() {
+        assert!(Npc::Ghost(Speaker::default()).dialogue().is_some());
+        assert!(Npc::Prisoner(Speaker::default()).dialogue().is_some());
+        assert!(Npc::Merchant(Merchant::default()).dialogue().is_none());
+        assert!(Npc::Merchant(Merchant::default()).wanders());
+        assert!(!Npc::Ghost(Speaker::default()).wanders());
+        assert!(Npc::Merchant(Merchant::default()).speaker_mut().is_none());
+    }
+}
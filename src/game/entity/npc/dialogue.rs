@@ -0,0 +1,109 @@
+//! # Dialogue
+//!
+//! Dialogue trees spoken by the npcs. Trees are data files in the `dialogues` directory,
+//! embedded into the executable at build time
+
+use super::super::{Item, StatusEffect};
+use crate::game::Hp;
+
+lazy_static! {
+    static ref GHOST: Dialogue = Dialogue::parse(include_str!("dialogues/ghost.json"));
+    static ref PRISONER: Dialogue = Dialogue::parse(include_str!("dialogues/prisoner.json"));
+}
+
+/// Placeholder in dialogue lines, replaced with a hint on where the maze key is
+pub const KEY_HINT: &str = "{key}";
+/// Placeholder in dialogue lines, replaced with a hint on where the exit is
+pub const EXIT_HINT: &str = "{exit}";
+
+/// A dialogue tree. The dialogue starts from the first node
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Dialogue {
+    nodes: Vec<DialogueNode>,
+}
+
+/// A line spoken by the npc, followed by the choices the player can answer with
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct DialogueNode {
+    pub line: String,
+    #[serde(default)]
+    pub choices: Vec<Choice>,
+}
+
+/// An answer of the player
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Choice {
+    pub text: String,
+    /// Node the dialogue continues from; the dialogue ends if `None`
+    #[serde(default)]
+    pub next: Option<usize>,
+    #[serde(default)]
+    pub effect: Option<DialogueEffect>,
+}
+
+/// Effect triggered on the player by a choice
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize)]
+pub enum DialogueEffect {
+    Heal(Hp),
+    GiveItem(Item),
+    /// Apply status effect for the provided amount of turns
+    Curse(StatusEffect, u8),
+}
+
+impl Dialogue {
+    /// Dialogue spoken by the ghost of the lost adventurer
+    pub fn ghost() -> &'static Self {
+        &GHOST
+    }
+
+    /// Dialogue spoken by the trapped prisoner
+    pub fn prisoner() -> &'static Self {
+        &PRISONER
+    }
+
+    fn parse(data: &str) -> Self {
+        serde_json::from_str(data).expect("invalid dialogue data")
+    }
+
+    /// Get dialogue node at `index`
+    pub fn node(&self, index: usize) -> Option<&DialogueNode> {
+        self.nodes.get(index)
+    }
+
+    /// Get `choice` of node at `index`
+    pub fn choice(&self, index: usize, choice: usize) -> Option<&Choice> {
+        self.node(index).and_then(|x| x.choices.get(choice))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_load_dialogues() {
+        for dialogue in [Dialogue::ghost(), Dialogue::prisoner()] {
+            assert!(dialogue.node(0).is_some());
+            for node in dialogue.nodes.iter() {
+                assert!(!node.choices.is_empty());
+                for choice in node.choices.iter() {
+                    if let Some(next) = choice.next {
+                        assert!(dialogue.node(next).is_some());
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            Dialogue::prisoner().choice(0, 2).unwrap().effect,
+            Some(DialogueEffect::GiveItem(Item::Talisman))
+        );
+        assert_eq!(
+            Dialogue::ghost().choice(3, 1).unwrap().effect,
+            Some(DialogueEffect::Curse(StatusEffect::Blinded, 5))
+        );
+        assert!(Dialogue::ghost().choice(0, 4).is_none());
+    }
+}
@@ -2,20 +2,51 @@
 //!
 //! Player entity
 
+mod status_effect;
+mod torch;
+
+pub use status_effect::{ActiveStatusEffect, StatusEffect};
+pub use torch::{LightLevel, Torch, MAX_TORCH_FUEL};
+
+use super::{Artifact, Item};
+use crate::game::equipment::Equipment;
 use crate::game::inventory::Inventory;
-use crate::game::Hp;
+use crate::game::{Difficulty, Hp};
 
 const BASE_PLAYER_HEALTH: Hp = 10;
+/// Escape chance bonus gained for each level
+const ESCAPE_BONUS_PER_LEVEL: u8 = 3;
+/// Escape chance bonus given by the rabbit's foot
+const RABBIT_FOOT_ESCAPE_BONUS: u8 = 10;
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Player {
     /// Player's inventory
     pub inventory: Inventory,
+    /// Items worn by the player
+    #[serde(default)]
+    pub equipment: Equipment,
+    /// The torch lighting the maze around the player
+    #[serde(default)]
+    pub torch: Torch,
     health: Hp,
     max_health: Hp,
     state: State,
-    /// Turns to sleep for
-    sleep_counter: u8,
+    /// Timed status effects currently applied to the player
+    #[serde(default)]
+    status_effects: Vec<ActiveStatusEffect>,
+    #[serde(default = "default_level")]
+    level: u8,
+    /// Experience gained in the current level
+    #[serde(default)]
+    experience: u32,
+    /// Gold coins in the player's purse
+    #[serde(default)]
+    gold: u32,
+}
+
+fn default_level() -> u8 {
+    1
 }
 
 /// Player state
@@ -31,9 +62,14 @@ impl Default for Player {
         Player {
             health: BASE_PLAYER_HEALTH,
             inventory: Inventory::default(),
+            equipment: Equipment::default(),
+            torch: Torch::default(),
             max_health: BASE_PLAYER_HEALTH,
             state: State::Explore,
-            sleep_counter: 0,
+            status_effects: Vec::default(),
+            level: default_level(),
+            experience: 0,
+            gold: 0,
         }
     }
 }
@@ -50,27 +86,81 @@ impl Player {
         self.state = State::Explore;
     }
 
-    /// Set player state to fight
+    /// Set player state to fight.
+    /// Being attacked wakes the player up
     pub fn start_fighting(&mut self) {
         debug!("put player in fighting state");
         self.state = State::Fight;
+        self.status_effects
+            .retain(|x| x.effect != StatusEffect::Asleep);
     }
 
     /// Start sleeping for n turns and set state to asleep
     pub fn start_sleeping(&mut self, sleeping_for: u8) {
-        debug!("put player in sleeping state");
-        self.state = State::Asleep;
-        self.sleep_counter = sleeping_for;
+        self.apply_status_effect(StatusEffect::Asleep, sleeping_for);
+    }
+
+    /// Apply status effect for `turns`.
+    /// If the effect is already active, the longest duration is kept.
+    /// Sleep lasts half the time while carrying the rooster feather
+    pub fn apply_status_effect(&mut self, effect: StatusEffect, turns: u8) {
+        let turns = if effect == StatusEffect::Asleep && self.has_artifact(Artifact::RoosterFeather)
+        {
+            turns.div_ceil(2)
+        } else {
+            turns
+        };
+        debug!("applying status effect {:?} for {} turns", effect, turns);
+        match self.status_effects.iter_mut().find(|x| x.effect == effect) {
+            Some(active) => active.turns = active.turns.max(turns),
+            None => self
+                .status_effects
+                .push(ActiveStatusEffect::new(effect, turns)),
+        }
+        if effect == StatusEffect::Asleep {
+            debug!("put player in sleeping state");
+            self.state = State::Asleep;
+        }
     }
 
-    /// Decrease sleep counter
-    /// If sleep counter goes to 0; awake
-    pub fn decr_sleep_counter(&mut self) {
-        self.sleep_counter = self.sleep_counter.saturating_sub(1);
-        debug!("decreasing sleep counter; new value {}", self.sleep_counter);
-        if self.sleep_counter == 0 {
+    /// Remove status effect, if active
+    pub fn cure_status_effect(&mut self, effect: StatusEffect) {
+        debug!("curing status effect {:?}", effect);
+        self.status_effects.retain(|x| x.effect != effect);
+    }
+
+    /// Returns whether status effect is active
+    pub fn has_status_effect(&self, effect: StatusEffect) -> bool {
+        self.status_effects.iter().any(|x| x.effect == effect)
+    }
+
+    /// Get active status effects
+    pub fn status_effects(&self) -> &[ActiveStatusEffect] {
+        &self.status_effects
+    }
+
+    /// Decrease the remaining turns of each status effect by one.
+    /// Returns the expired status effects.
+    /// If the player is no longer asleep; awake
+    pub fn tick_status_effects(&mut self) -> Vec<StatusEffect> {
+        for active in self.status_effects.iter_mut() {
+            active.turns = active.turns.saturating_sub(1);
+        }
+        let expired: Vec<StatusEffect> = self
+            .status_effects
+            .iter()
+            .filter(|x| x.turns == 0)
+            .map(|x| x.effect)
+            .collect();
+        self.status_effects.retain(|x| x.turns > 0);
+        debug!(
+            "ticked status effects; expired: {:?}; still active: {:?}",
+            expired, self.status_effects
+        );
+        if self.state == State::Asleep && !self.has_status_effect(StatusEffect::Asleep) {
             self.start_exploring();
         }
+        expired
     }
 
     /// Heal player by qty
@@ -134,6 +224,79 @@ impl Player {
     pub fn is_dead(&self) -> bool {
         self.health == 0
     }
+
+    /// Get player's level
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Get experience gained in the current level
+    pub fn experience(&self) -> u32 {
+        self.experience
+    }
+
+    /// Gain experience. For each level gained, max health is increased by 1.
+    /// Returns the amount of levels gained
+    pub fn gain_experience(&mut self, xp: u32, difficulty: Difficulty) -> u8 {
+        self.experience = self.experience.saturating_add(xp);
+        let mut levels_gained = 0;
+        while let Some(required) = difficulty
+            .experience_to_level_up(self.level)
+            .filter(|required| self.experience >= *required)
+        {
+            self.experience -= required;
+            self.level += 1;
+            levels_gained += 1;
+            self.incr_max_health(1);
+        }
+        debug!(
+            "gained {} XP; level {} ({} XP)",
+            xp, self.level, self.experience
+        );
+        levels_gained
+    }
+
+    /// Gold coins in the player's purse
+    pub fn gold(&self) -> u32 {
+        self.gold
+    }
+
+    /// Put `coins` into the purse
+    pub fn earn_gold(&mut self, coins: u32) {
+        self.gold = self.gold.saturating_add(coins);
+        debug!("earned {} gold; purse has {} gold", coins, self.gold);
+    }
+
+    /// Take `coins` from the purse.
+    /// Returns false if the player can't afford to spend them
+    pub fn spend_gold(&mut self, coins: u32) -> bool {
+        if coins > self.gold {
+            return false;
+        }
+        self.gold -= coins;
+        debug!("spent {} gold; purse has {} gold", coins, self.gold);
+        true
+    }
+
+    /// Damage bonus dealt by the player, given by its level
+    pub fn damage_bonus(&self) -> Hp {
+        (self.level - 1) / 2
+    }
+
+    /// Escape chance bonus, given by the player's level and by the rabbit's foot
+    pub fn escape_bonus(&self) -> u8 {
+        let artifact_bonus = if self.has_artifact(Artifact::RabbitFoot) {
+            RABBIT_FOOT_ESCAPE_BONUS
+        } else {
+            0
+        };
+        (self.level - 1) * ESCAPE_BONUS_PER_LEVEL + artifact_bonus
+    }
+
+    /// Returns whether the player carries `artifact`
+    pub fn has_artifact(&self, artifact: Artifact) -> bool {
+        self.inventory.has(Item::Artifact(artifact))
+    }
 }
 
 #[cfg(test)]
@@ -150,7 +313,7 @@ mod test {
         assert_eq!(player.inventory.items().len(), 0);
         assert_eq!(player.max_health(), BASE_PLAYER_HEALTH);
         assert_eq!(player.state(), State::Explore);
-        assert_eq!(player.sleep_counter, 0);
+        assert!(player.status_effects().is_empty());
     }
 
     #[test]
@@ -190,14 +353,109 @@ mod test {
         player.start_exploring();
         assert_eq!(player.state(), State::Explore);
         player.start_sleeping(2);
-        assert_eq!(player.sleep_counter, 2);
+        assert_eq!(player.has_status_effect(StatusEffect::Asleep), true);
         assert_eq!(player.state(), State::Asleep);
-        player.decr_sleep_counter();
+        assert!(player.tick_status_effects().is_empty());
         assert_eq!(player.state(), State::Asleep);
-        assert_eq!(player.sleep_counter, 1);
-        player.decr_sleep_counter();
+        assert_eq!(player.tick_status_effects(), vec![StatusEffect::Asleep]);
         assert_eq!(player.state(), State::Explore);
-        assert_eq!(player.sleep_counter, 0);
+        assert_eq!(player.has_status_effect(StatusEffect::Asleep), false);
+    }
+
+    #[test]
+    fn should_apply_and_tick_status_effects() {
+        let mut player = Player::default();
+        player.apply_status_effect(StatusEffect::Poisoned, 2);
+        player.apply_status_effect(StatusEffect::Hasted, 1);
+        assert_eq!(player.status_effects().len(), 2);
+        // keep longest duration
+        player.apply_status_effect(StatusEffect::Poisoned, 1);
+        assert_eq!(
+            player.status_effects()[0],
+            ActiveStatusEffect::new(StatusEffect::Poisoned, 2)
+        );
+        assert_eq!(player.state(), State::Explore);
+        assert_eq!(player.tick_status_effects(), vec![StatusEffect::Hasted]);
+        assert_eq!(player.has_status_effect(StatusEffect::Hasted), false);
+        assert_eq!(player.has_status_effect(StatusEffect::Poisoned), true);
+        assert_eq!(player.tick_status_effects(), vec![StatusEffect::Poisoned]);
+        assert!(player.status_effects().is_empty());
+        // cure
+        player.apply_status_effect(StatusEffect::Poisoned, 3);
+        player.cure_status_effect(StatusEffect::Poisoned);
+        assert!(player.status_effects().is_empty());
+    }
+
+    #[test]
+    fn should_gain_experience() {
+        let mut player = Player::default();
+        assert_eq!(player.level(), 1);
+        assert_eq!(player.damage_bonus(), 0);
+        assert_eq!(player.escape_bonus(), 0);
+        assert_eq!(player.gain_experience(10, Difficulty::Normal), 0);
+        assert_eq!(player.experience(), 10);
+        // gain two levels at once
+        assert_eq!(player.gain_experience(50, Difficulty::Normal), 2);
+        assert_eq!(player.level(), 3);
+        assert_eq!(player.experience(), 5);
+        assert_eq!(player.max_health(), BASE_PLAYER_HEALTH + 2);
+        assert_eq!(player.damage_bonus(), 1);
+        assert_eq!(player.escape_bonus(), 6);
+    }
+
+    #[test]
+    fn should_apply_artifact_bonuses() {
+        let mut player = Player::default();
+        player
+            .inventory
+            .add(Item::Artifact(Artifact::RabbitFoot))
+            .unwrap();
+        assert_eq!(player.has_artifact(Artifact::RabbitFoot), true);
+        assert_eq!(player.escape_bonus(), RABBIT_FOOT_ESCAPE_BONUS);
+        // sleep lasts half the time
+        player
+            .inventory
+            .add(Item::Artifact(Artifact::RoosterFeather))
+            .unwrap();
+        player.start_sleeping(3);
+        assert_eq!(
+            player.status_effects()[0],
+            ActiveStatusEffect::new(StatusEffect::Asleep, 2)
+        );
+        // other effects last as usual
+        player.apply_status_effect(StatusEffect::Poisoned, 3);
+        assert_eq!(
+            player.status_effects()[1],
+            ActiveStatusEffect::new(StatusEffect::Poisoned, 3)
+        );
+    }
+
+    #[test]
+    fn should_not_level_up_beyond_max_level() {
+        let mut player = Player::default();
+        player.gain_experience(u32::MAX, Difficulty::Easy);
+        assert_eq!(player.level(), crate::game::difficulty::MAX_LEVEL);
+    }
+
+    #[test]
+    fn should_earn_and_spend_gold() {
+        let mut player = Player::default();
+        assert_eq!(player.gold(), 0);
+        player.earn_gold(15);
+        assert_eq!(player.spend_gold(20), false);
+        assert_eq!(player.spend_gold(10), true);
+        assert_eq!(player.gold(), 5);
+    }
+
+    #[test]
+    fn should_deserialize_legacy_player_with_level_1() {
+        let mut json = serde_json::to_value(Player::default()).unwrap();
+        let map = json.as_object_mut().unwrap();
+        map.remove("level");
+        map.remove("experience");
+        let player: Player = serde_json::from_value(json).unwrap();
+        assert_eq!(player.level(), 1);
+        assert_eq!(player.experience(), 0);
     }
 
     #[test]
@@ -0,0 +1,98 @@
+//! # Status effect
+//!
+//! Timed conditions which may affect the player
+
+/// A timed condition which affects the player for a certain amount of turns
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize, Hash)]
+pub enum StatusEffect {
+    /// Player can't do anything but sleep
+    Asleep,
+    /// Incoming damage is reduced by 1
+    Blessed,
+    /// Escape chance is halved
+    Blinded,
+    /// Escape chance is increased
+    Hasted,
+    /// Lose 1 HP each turn
+    Poisoned,
+    /// Restore 1 HP each turn
+    Regenerating,
+}
+
+impl StatusEffect {
+    /// Return the status effect name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Asleep => "Asleep",
+            Self::Blessed => "Blessed",
+            Self::Blinded => "Blinded",
+            Self::Hasted => "Hasted",
+            Self::Poisoned => "Poisoned",
+            Self::Regenerating => "Regenerating",
+        }
+    }
+
+    /// Returns whether the status effect is beneficial for the player
+    pub fn is_beneficial(&self) -> bool {
+        matches!(self, Self::Blessed | Self::Hasted | Self::Regenerating)
+    }
+}
+
+/// A status effect applied to the player with the amount of turns it'll last for
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ActiveStatusEffect {
+    pub effect: StatusEffect,
+    /// Remaining turns
+    pub turns: u8,
+}
+
+impl ActiveStatusEffect {
+    pub fn new(effect: StatusEffect, turns: u8) -> Self {
+        Self { effect, turns }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_get_status_effect_name() {
+        assert_eq!(StatusEffect::Asleep.name(), "Asleep");
+        assert_eq!(StatusEffect::Blessed.name(), "Blessed");
+        assert_eq!(StatusEffect::Blinded.name(), "Blinded");
+        assert_eq!(StatusEffect::Hasted.name(), "Hasted");
+        assert_eq!(StatusEffect::Poisoned.name(), "Poisoned");
+        assert_eq!(StatusEffect::Regenerating.name(), "Regenerating");
+    }
+
+    #[test]
+    fn should_tell_whether_status_effect_is_beneficial() {
+        assert_eq!(StatusEffect::Asleep.is_beneficial(), false);
+        assert_eq!(StatusEffect::Blessed.is_beneficial(), true);
+        assert_eq!(StatusEffect::Blinded.is_beneficial(), false);
+        assert_eq!(StatusEffect::Hasted.is_beneficial(), true);
+        assert_eq!(StatusEffect::Poisoned.is_beneficial(), false);
+        assert_eq!(StatusEffect::Regenerating.is_beneficial(), true);
+    }
+
+    #[test]
+    fn should_serialize() {
+        #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
+        struct Test {
+            effects: Vec<ActiveStatusEffect>,
+        }
+        let test = Test {
+            effects: vec![
+                ActiveStatusEffect::new(StatusEffect::Poisoned, 3),
+                ActiveStatusEffect::new(StatusEffect::Asleep, 1),
+            ],
+        };
+        let json = serde_json::to_string(&test).unwrap();
+        let decoded: Test = serde_json::from_str(&json).unwrap();
+        assert_eq!(test, decoded);
+    }
+}
@@ -0,0 +1,96 @@
+//! # Torch
+//!
+//! The light source carried by the player
+
+/// Max fuel of the torch; it burns one unit per turn
+pub const MAX_TORCH_FUEL: u8 = 200;
+/// Below this amount of fuel, the torch light gets dim
+const DIM_LIGHT_FUEL: u8 = 50;
+
+/// How much the player can see around
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LightLevel {
+    /// Torch is burnt out; the player can only see the room shape
+    Dark,
+    /// Torch is running out of fuel; walls are faded and graffiti can't be seen
+    Dim,
+    Bright,
+}
+
+/// Player's torch
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Torch {
+    fuel: u8,
+}
+
+impl Default for Torch {
+    fn default() -> Self {
+        Self {
+            fuel: MAX_TORCH_FUEL,
+        }
+    }
+}
+
+impl Torch {
+    /// Remaining fuel
+    pub fn fuel(&self) -> u8 {
+        self.fuel
+    }
+
+    /// Current light level
+    pub fn light_level(&self) -> LightLevel {
+        match self.fuel {
+            0 => LightLevel::Dark,
+            x if x <= DIM_LIGHT_FUEL => LightLevel::Dim,
+            _ => LightLevel::Bright,
+        }
+    }
+
+    /// Burn a unit of fuel.
+    /// Returns the new light level if it has changed
+    pub fn burn(&mut self) -> Option<LightLevel> {
+        let level = self.light_level();
+        self.fuel = self.fuel.saturating_sub(1);
+        Some(self.light_level()).filter(|x| *x != level)
+    }
+
+    /// Refuel torch by `qty`, up to max fuel
+    pub fn refuel(&mut self, qty: u8) {
+        self.fuel = self.fuel.saturating_add(qty).min(MAX_TORCH_FUEL);
+        debug!("torch refueled; fuel {}", self.fuel);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn should_burn_torch() {
+        let mut torch = Torch::default();
+        assert_eq!(torch.light_level(), LightLevel::Bright);
+        for _ in DIM_LIGHT_FUEL + 1..MAX_TORCH_FUEL {
+            assert_eq!(torch.burn(), None);
+        }
+        assert_eq!(torch.burn(), Some(LightLevel::Dim));
+        for _ in 1..DIM_LIGHT_FUEL {
+            assert_eq!(torch.burn(), None);
+        }
+        assert_eq!(torch.burn(), Some(LightLevel::Dark));
+        assert_eq!(torch.fuel(), 0);
+        assert_eq!(torch.burn(), None);
+        assert_eq!(torch.light_level(), LightLevel::Dark);
+    }
+
+    #[test]
+    fn should_refuel_torch() {
+        let mut torch = Torch { fuel: 0 };
+        torch.refuel(80);
+        assert_eq!(torch.fuel(), 80);
+        torch.refuel(MAX_TORCH_FUEL);
+        assert_eq!(torch.fuel(), MAX_TORCH_FUEL);
+    }
+}
@@ -0,0 +1,132 @@
+//! # Equipment
+//!
+//! Items currently worn by the player
+
+use super::entity::{Amulet, Armor, EquipmentSlot, Item, Weapon};
+
+/// Player's equipment; each slot can hold a single item
+#[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Equipment {
+    weapon: Option<Weapon>,
+    armor: Option<Armor>,
+    amulet: Option<Amulet>,
+}
+
+impl Equipment {
+    /// Equip item in its slot.
+    /// Returns the item previously equipped in the same slot, if any.
+    /// Panics if item is not equipment
+    pub fn equip(&mut self, item: Item) -> Option<Item> {
+        debug!("equipping {:?}", item);
+        match item {
+            Item::Amulet(amulet) => self.amulet.replace(amulet).map(Item::Amulet),
+            Item::Armor(armor) => self.armor.replace(armor).map(Item::Armor),
+            Item::Weapon(weapon) => self.weapon.replace(weapon).map(Item::Weapon),
+            _ => panic!("{:?} is not equipment", item),
+        }
+    }
+
+    /// Remove item from slot and return it
+    pub fn unequip(&mut self, slot: EquipmentSlot) -> Option<Item> {
+        debug!("unequipping {:?}", slot);
+        match slot {
+            EquipmentSlot::Amulet => self.amulet.take().map(Item::Amulet),
+            EquipmentSlot::Armor => self.armor.take().map(Item::Armor),
+            EquipmentSlot::Weapon => self.weapon.take().map(Item::Weapon),
+        }
+    }
+
+    /// Get equipped weapon
+    pub fn weapon(&self) -> Option<&Weapon> {
+        self.weapon.as_ref()
+    }
+
+    /// Get equipped armor
+    pub fn armor(&self) -> Option<&Armor> {
+        self.armor.as_ref()
+    }
+
+    /// Get equipped amulet
+    pub fn amulet(&self) -> Option<&Amulet> {
+        self.amulet.as_ref()
+    }
+
+    /// Wear equipped weapon.
+    /// If the weapon breaks, it is removed from the slot and returned
+    pub fn wear_weapon(&mut self) -> Option<Item> {
+        match self.weapon.as_mut().map(|x| x.wear()) {
+            Some(true) => self.unequip(EquipmentSlot::Weapon),
+            _ => None,
+        }
+    }
+
+    /// Wear equipped armor.
+    /// If the armor breaks, it is removed from the slot and returned
+    pub fn wear_armor(&mut self) -> Option<Item> {
+        match self.armor.as_mut().map(|x| x.wear()) {
+            Some(true) => self.unequip(EquipmentSlot::Armor),
+            _ => None,
+        }
+    }
+
+    /// Get equipped items
+    pub fn items(&self) -> Vec<Item> {
+        let mut items = Vec::with_capacity(3);
+        if let Some(weapon) = self.weapon {
+            items.push(Item::Weapon(weapon));
+        }
+        if let Some(armor) = self.armor {
+            items.push(Item::Armor(armor));
+        }
+        if let Some(amulet) = self.amulet {
+            items.push(Item::Amulet(amulet));
+        }
+        items
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::game::entity::WeaponKind;
+
+    #[test]
+    fn should_equip_and_unequip_items() {
+        let mut equipment = Equipment::default();
+        assert!(equipment.items().is_empty());
+        let sword = Item::Weapon(Weapon::new(WeaponKind::Sword));
+        let axe = Item::Weapon(Weapon::new(WeaponKind::Axe));
+        assert_eq!(equipment.equip(sword), None);
+        assert_eq!(equipment.equip(Item::Amulet(Amulet::Warding)), None);
+        assert_eq!(equipment.equip(axe), Some(sword));
+        assert_eq!(equipment.weapon(), Some(&Weapon::new(WeaponKind::Axe)));
+        assert_eq!(equipment.amulet(), Some(&Amulet::Warding));
+        assert_eq!(equipment.armor(), None);
+        assert_eq!(equipment.items(), vec![axe, Item::Amulet(Amulet::Warding)]);
+        assert_eq!(equipment.unequip(EquipmentSlot::Weapon), Some(axe));
+        assert_eq!(equipment.unequip(EquipmentSlot::Weapon), None);
+        assert_eq!(equipment.weapon(), None);
+    }
+
+    #[test]
+    fn should_break_equipment() {
+        let mut equipment = Equipment::default();
+        equipment.equip(Item::Armor(Armor::default()));
+        for _ in 1..Armor::default().max_durability() {
+            assert_eq!(equipment.wear_armor(), None);
+        }
+        assert!(equipment.wear_armor().is_some());
+        assert_eq!(equipment.armor(), None);
+        assert_eq!(equipment.wear_armor(), None);
+        assert_eq!(equipment.wear_weapon(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_panic_when_equipping_non_equipment() {
+        Equipment::default().equip(Item::Sonar);
+    }
+}
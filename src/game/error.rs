@@ -13,6 +13,8 @@ use crate::utils::saved_games::SavedGameError;
 pub enum Error {
     #[error("audio error: {0}")]
     Audio(AudioError),
+    #[error("io error: {0}")]
+    Io(std::io::Error),
     #[error("game save error: {0}")]
     SaveGame(SavedGameError),
     #[error("ui error: {0}")]
@@ -25,6 +27,12 @@ impl From<AudioError> for Error {
     }
 }
 
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
 impl From<SavedGameError> for Error {
     fn from(e: SavedGameError) -> Self {
         Self::SaveGame(e)
@@ -2,53 +2,97 @@
 //!
 //! Player's inventory
 
-use std::collections::HashMap;
+use thiserror::Error;
 
 use super::entity::Item;
 
+/// Max amount of items the player can carry
+pub const INVENTORY_CAPACITY: usize = 40;
+
+pub type InventoryResult<T> = Result<T, InventoryError>;
+
+/// Reason why an item can't be added to the inventory
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Error)]
+pub enum InventoryError {
+    #[error("the inventory is full")]
+    Full,
+    #[error("the item has reached its max quantity")]
+    StackFull,
+}
+
 /// Current player's inventory stores all the items collected by the player
 #[derive(Default, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Inventory {
-    /// Association between item type and quantity
-    items: HashMap<u32, u8>,
+    /// Items and their quantity, in the order they have been collected
+    items: Vec<(Item, u8)>,
 }
 
 impl Inventory {
     /// Add item to inventory.
-    /// If not in inventory, item is set to 1 as qty, otherwise qty is increased
-    pub fn add(&mut self, item: Item) {
-        let key = item.key();
-        let qty = self.items.get(&key).map(|x| x + 1).unwrap_or(1);
-        self.items.insert(key, qty);
+    /// If not in inventory, item is set to 1 as qty, otherwise qty is increased.
+    /// Fails if the inventory is full or if the item quantity has reached its max
+    pub fn add(&mut self, item: Item) -> InventoryResult<()> {
+        self.can_add(item)?;
+        match self.items.iter_mut().find(|(x, _)| *x == item) {
+            Some((_, qty)) => *qty += 1,
+            None => self.items.push((item, 1)),
+        }
+        Ok(())
+    }
+
+    /// Returns whether `item` can be added to the inventory
+    pub fn can_add(&self, item: Item) -> InventoryResult<()> {
+        if self.quantity(item) == u8::MAX {
+            Err(InventoryError::StackFull)
+        } else if self.is_full() {
+            Err(InventoryError::Full)
+        } else {
+            Ok(())
+        }
     }
 
     /// Consume item in inventory.
     /// Panics if not in inventory.
     /// If quantity is 1; item is deleted by inventory; otherwise is just decreased
     pub fn consume(&mut self, item: Item) {
-        let key = item.key();
-        match *self.items.get(&key).unwrap() {
+        let index = self.items.iter().position(|(x, _)| *x == item).unwrap();
+        match self.items[index].1 {
             1 => {
-                self.items.remove(&key);
+                self.items.remove(index);
             }
-            qty => {
-                self.items.insert(key, qty - 1);
+            _ => {
+                self.items[index].1 -= 1;
             }
         }
     }
 
     /// Returns whether inventory contains item
     pub fn has(&self, item: Item) -> bool {
-        self.items.contains_key(&item.key())
+        self.items.iter().any(|(x, _)| *x == item)
+    }
+
+    /// Returns the quantity of item in inventory
+    pub fn quantity(&self, item: Item) -> u8 {
+        self.items
+            .iter()
+            .find(|(x, _)| *x == item)
+            .map(|(_, qty)| *qty)
+            .unwrap_or_default()
+    }
+
+    /// Returns the total amount of items in the inventory
+    pub fn size(&self) -> usize {
+        self.items.iter().map(|(_, qty)| *qty as usize).sum()
+    }
+
+    /// Returns whether the inventory has reached its capacity
+    pub fn is_full(&self) -> bool {
+        self.size() >= INVENTORY_CAPACITY
     }
 
-    /// Get an iterator over inventory
+    /// Get the items in the inventory with their quantity, in the order they have been collected
     pub fn items(&self) -> Vec<(Item, u8)> {
-        let mut items = Vec::with_capacity(self.items.len());
-        for (key, qty) in self.items.iter() {
-            items.push((Item::from(*key), *qty));
-        }
-        items
+        self.items.clone()
     }
 }
 
@@ -58,39 +102,91 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::game::entity::Potion;
 
     #[test]
     fn should_make_inventory() {
         let mut inventory = Inventory::default();
         assert!(inventory.items.is_empty());
         assert_eq!(inventory.has(Item::AlchemyBook), false);
-        inventory.add(Item::AlchemyBook);
+        inventory.add(Item::AlchemyBook).unwrap();
         assert_eq!(inventory.has(Item::AlchemyBook), true);
         // consumable
-        inventory.add(Item::Talisman);
-        assert_eq!(*inventory.items.get(&Item::Talisman.key()).unwrap(), 1);
-        inventory.add(Item::Talisman);
-        assert_eq!(*inventory.items.get(&Item::Talisman.key()).unwrap(), 2);
+        inventory.add(Item::Talisman).unwrap();
+        assert_eq!(inventory.quantity(Item::Talisman), 1);
+        inventory.add(Item::Talisman).unwrap();
+        assert_eq!(inventory.quantity(Item::Talisman), 2);
         inventory.consume(Item::Talisman);
-        assert_eq!(*inventory.items.get(&Item::Talisman.key()).unwrap(), 1);
+        assert_eq!(inventory.quantity(Item::Talisman), 1);
+        inventory.consume(Item::Talisman);
+        assert!(!inventory.has(Item::Talisman));
+    }
+
+    #[test]
+    fn should_iter_inventory_in_stable_order() {
+        let mut inventory = Inventory::default();
+        inventory.add(Item::Talisman).unwrap();
+        inventory.add(Item::Talisman).unwrap();
+        inventory.add(Item::MazeKey).unwrap();
+        inventory.add(Item::AlchemyBook).unwrap();
+        assert_eq!(
+            inventory.items(),
+            vec![
+                (Item::Talisman, 2),
+                (Item::MazeKey, 1),
+                (Item::AlchemyBook, 1)
+            ]
+        );
         inventory.consume(Item::Talisman);
-        assert!(inventory.items.get(&Item::Talisman.key()).is_none());
+        assert_eq!(
+            inventory.items(),
+            vec![
+                (Item::Talisman, 1),
+                (Item::MazeKey, 1),
+                (Item::AlchemyBook, 1)
+            ]
+        );
     }
 
     #[test]
-    fn should_iter_inventory() {
+    fn should_tell_whether_inventory_is_full() {
         let mut inventory = Inventory::default();
-        inventory.add(Item::Talisman);
-        inventory.add(Item::Talisman);
-        inventory.add(Item::MazeKey);
-        assert_eq!(inventory.items().len(), 2);
+        inventory.add(Item::MazeKey).unwrap();
+        for _ in 1..INVENTORY_CAPACITY {
+            assert_eq!(inventory.is_full(), false);
+            inventory.add(Item::Potion(Potion::Mead)).unwrap();
+        }
+        assert_eq!(inventory.size(), INVENTORY_CAPACITY);
+        assert_eq!(inventory.is_full(), true);
+        inventory.consume(Item::MazeKey);
+        assert_eq!(inventory.is_full(), false);
+    }
+
+    #[test]
+    fn should_not_add_items_beyond_limits() {
+        let mut inventory = Inventory::default();
+        for _ in 0..INVENTORY_CAPACITY {
+            assert_eq!(inventory.add(Item::Potion(Potion::Mead)), Ok(()));
+        }
+        assert_eq!(
+            inventory.add(Item::Potion(Potion::Mead)),
+            Err(InventoryError::Full)
+        );
+        assert_eq!(inventory.add(Item::Sonar), Err(InventoryError::Full));
+        assert_eq!(inventory.size(), INVENTORY_CAPACITY);
+        // quantity from a crafted save
+        let mut inventory = Inventory {
+            items: vec![(Item::Sonar, u8::MAX)],
+        };
+        assert!(inventory.add(Item::Sonar).is_err());
+        assert_eq!(inventory.quantity(Item::Sonar), u8::MAX);
     }
 
     #[test]
     #[should_panic]
     fn should_panic_if_consuming_unexisting_inventory() {
         let mut inventory = Inventory::default();
-        inventory.consume(Item::Armor);
+        inventory.consume(Item::Sonar);
     }
 
     #[test]
@@ -100,8 +196,8 @@ mod test {
             inventory: Inventory,
         }
         let mut inventory = Inventory::default();
-        inventory.add(Item::AlchemyBook);
-        inventory.add(Item::Potion(crate::game::entity::Potion::Chamomille));
+        inventory.add(Item::AlchemyBook).unwrap();
+        inventory.add(Item::Potion(Potion::Chamomille)).unwrap();
         let test = Test { inventory };
         let json = serde_json::to_string(&test).unwrap();
         let decoded: Test = serde_json::from_str(&json).unwrap();
@@ -5,15 +5,18 @@
 mod generator;
 mod room;
 
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 
 use generator::Generator;
 use petgraph::graph::UnGraph;
 use petgraph::stable_graph::DefaultIx;
 use petgraph::visit::EdgeRef;
 use room::Room;
+#[cfg(test)]
+pub use room::MAX_ENEMIES_IN_ROOM;
 
-use super::entity::{Enemy, Item};
+use super::entity::{Chest, Enemy, Item, Npc};
 use crate::utils::graphq;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -61,6 +64,96 @@ impl Maze {
             .collect()
     }
 
+    /// Returns the rooms reachable from `room` walking through at most `max_distance` rooms,
+    /// along with their distance from `room`
+    pub fn distances_from(&self, room: DefaultIx, max_distance: u8) -> HashMap<DefaultIx, u8> {
+        self.walk_from(HashMap::from([(room, 0)]), room, max_distance)
+    }
+
+    /// Returns the rooms reachable from `room` walking through `through`, an adjacent room,
+    /// and at most `max_distance` rooms, along with their distance from `room`
+    pub fn distances_through(
+        &self,
+        room: DefaultIx,
+        through: DefaultIx,
+        max_distance: u8,
+    ) -> HashMap<DefaultIx, u8> {
+        let mut distances = self.walk_from(
+            HashMap::from([(room, 0), (through, 1)]),
+            through,
+            max_distance,
+        );
+        distances.remove(&room);
+        distances
+    }
+
+    /// Breadth-first walk starting from `start`, whose distance is already in `distances`
+    fn walk_from(
+        &self,
+        mut distances: HashMap<DefaultIx, u8>,
+        start: DefaultIx,
+        max_distance: u8,
+    ) -> HashMap<DefaultIx, u8> {
+        let mut queue = VecDeque::from([start]);
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            if distance >= max_distance {
+                continue;
+            }
+            for (adjacent, _) in self.adjacent_rooms(current) {
+                if let Entry::Vacant(entry) = distances.entry(adjacent) {
+                    entry.insert(distance + 1);
+                    queue.push_back(adjacent);
+                }
+            }
+        }
+        distances
+    }
+
+    /// Returns the shortest path from the player's room to `room`, walking only through the rooms
+    /// matching `predicate`. The path doesn't include the player's room, but it ends with `room`
+    pub fn path_to<F>(&self, room: DefaultIx, predicate: F) -> Option<Vec<DefaultIx>>
+    where
+        F: Fn(DefaultIx) -> bool,
+    {
+        if room == self.player {
+            return Some(vec![]);
+        }
+        let mut parents = HashMap::from([(self.player, self.player)]);
+        let mut queue = VecDeque::from([self.player]);
+        while let Some(current) = queue.pop_front() {
+            if current == room {
+                let mut path = vec![room];
+                let mut node = room;
+                while parents[&node] != self.player {
+                    node = parents[&node];
+                    path.push(node);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for (adjacent, _) in self.adjacent_rooms(current) {
+                if (adjacent == room || predicate(adjacent)) && !parents.contains_key(&adjacent) {
+                    parents.insert(adjacent, current);
+                    queue.push_back(adjacent);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the distance from the player's room to the nearest room matching `predicate`
+    pub fn distance_to<F>(&self, predicate: F) -> Option<u8>
+    where
+        F: Fn(&Room) -> bool,
+    {
+        self.distances_from(self.player, u8::MAX)
+            .into_iter()
+            .filter(|(room, _)| predicate(self.rooms.get(room).unwrap()))
+            .map(|(_, distance)| distance)
+            .min()
+    }
+
     /// Get list of rooms
     pub fn rooms(&self) -> Vec<(DefaultIx, &Room)> {
         self.rooms.iter().map(|(key, room)| (*key, room)).collect()
@@ -91,24 +184,67 @@ impl Maze {
         self.room(self.player).and_then(|x| x.item.as_ref())
     }
 
+    /// Get reference to the chest holding the item in the player's room (if any)
+    pub fn chest(&self) -> Option<&Chest> {
+        self.room(self.player).and_then(|x| x.chest.as_ref())
+    }
+
+    /// Get mutable reference to the chest holding the item in the player's room (if any)
+    pub fn chest_mut(&mut self) -> Option<&mut Chest> {
+        self.room_mut(self.player).and_then(|x| x.chest.as_mut())
+    }
+
+    /// Open the chest in the player's room; its item lies on the floor then
+    pub fn open_chest(&mut self) {
+        self.room_mut(self.player).unwrap().chest = None;
+    }
+
     /// Take the item from the player's room
     pub fn take_item(&mut self) -> Option<Item> {
-        self.room_mut(self.player).as_mut().unwrap().item.take()
+        self.room_mut(self.player).as_mut().unwrap().take_item()
+    }
+
+    /// Drop item in the player's room
+    pub fn drop_item(&mut self, item: Item) {
+        self.room_mut(self.player).unwrap().drop_item(item);
     }
 
-    /// Take enemy from the player's room
-    pub fn take_enemy(&mut self) -> Option<Enemy> {
-        self.room_mut(self.player).as_mut().unwrap().enemy.take()
+    /// Get reference to the npc in the player's room (if any)
+    pub fn npc(&self) -> Option<&Npc> {
+        self.room(self.player).and_then(|x| x.npc.as_ref())
     }
 
-    /// Get reference to fighting enemy (if any)
+    /// Get mutable reference to the npc in the player's room (if any)
+    pub fn npc_mut(&mut self) -> Option<&mut Npc> {
+        self.room_mut(self.player).and_then(|x| x.npc.as_mut())
+    }
+
+    /// Take enemy at `target` from the player's room
+    pub fn take_enemy(&mut self, target: usize) -> Option<Enemy> {
+        let room = self.room_mut(self.player).unwrap();
+        if target < room.enemies.len() {
+            Some(room.enemies.remove(target))
+        } else {
+            None
+        }
+    }
+
+    /// Get reference to the enemy in front of the player (if any)
     pub fn fighting_enemy(&self) -> Option<&Enemy> {
-        self.room(self.player).and_then(|x| x.enemy.as_ref())
+        self.room(self.player).and_then(|x| x.enemies.first())
+    }
+
+    /// Get all the enemies the player is fighting with
+    pub fn fighting_enemies(&self) -> &[Enemy] {
+        self.room(self.player)
+            .map(|x| x.enemies.as_slice())
+            .unwrap_or_default()
     }
 
-    /// Get mutable reference to fighting enemy
-    pub fn fighting_enemy_mut(&mut self) -> Option<&mut Enemy> {
-        self.room_mut(self.player).and_then(|x| x.enemy.as_mut())
+    /// Get mutable reference to the fighting enemy at `target`
+    pub fn fighting_enemy_mut(&mut self, target: usize) -> Option<&mut Enemy> {
+        self.room_mut(self.player)
+            .and_then(|x| x.enemies.get_mut(target))
     }
 }
 
@@ -130,13 +266,112 @@ impl PartialEq for Maze {
     }
 }
 
+#[cfg(test)]
+impl Maze {
+    pub fn mocked() -> Maze {
+        fn fake_maze_graph() -> UnGraph<u32, u32> {
+            /*
+             * 0
+             *  - 1
+             *  \
+             *   -> 3
+             *   -> 9
+             *
+             * - 2
+             *  \
+             *   -> 4
+             *    \ -> 5
+             *    \ -> 6
+             *    \ -> 7
+             *         \
+             *          -> 8 -> 10
+             *          -> exit
+             */
+            let mut nodes: UnGraph<u32, u32> = UnGraph::default();
+            nodes.add_node(0);
+            nodes.add_node(1);
+            nodes.add_node(2);
+            nodes.add_node(3);
+            nodes.add_node(4);
+            nodes.add_node(5);
+            nodes.add_node(6);
+            nodes.add_node(7);
+            nodes.add_node(8);
+            nodes.add_node(9);
+            nodes.add_node(10);
+            nodes.add_edge(0.into(), 1.into(), 0);
+            nodes.add_edge(0.into(), 2.into(), 0);
+            nodes.add_edge(1.into(), 3.into(), 0);
+            nodes.add_edge(1.into(), 9.into(), 0);
+            nodes.add_edge(2.into(), 4.into(), 0);
+            nodes.add_edge(4.into(), 5.into(), 0);
+            nodes.add_edge(4.into(), 6.into(), 0);
+            nodes.add_edge(4.into(), 7.into(), 0);
+            nodes.add_edge(7.into(), 8.into(), 0);
+            nodes.add_edge(8.into(), 10.into(), 0);
+            nodes
+        }
+        let nodes = fake_maze_graph();
+        // add rooms
+        let mut rooms = HashMap::new();
+        rooms.insert(0, Room::default());
+        rooms.insert(
+            1,
+            Room {
+                enemies: vec![],
+                is_exit: false,
+                item: Some(Item::Armor(crate::game::entity::Armor::default())),
+                chest: None,
+                pile: vec![],
+                npc: None,
+            },
+        );
+        rooms.insert(
+            2,
+            Room {
+                enemies: vec![Enemy::Daemon(crate::game::entity::Daemon::new(4))],
+                is_exit: false,
+                item: None,
+                chest: None,
+                pile: vec![],
+                npc: None,
+            },
+        );
+        rooms.insert(3, Room::default());
+        rooms.insert(9, Room::default());
+        rooms.insert(4, Room::default());
+        rooms.insert(5, Room::default());
+        rooms.insert(6, Room::default());
+        rooms.insert(
+            7,
+            Room {
+                enemies: vec![],
+                is_exit: true,
+                item: None,
+                chest: None,
+                pile: vec![],
+                npc: None,
+            },
+        );
+        rooms.insert(8, Room::default());
+        rooms.insert(10, Room::default());
+
+        Maze {
+            nodes,
+            rooms,
+            player: 0,
+            seed: String::from("test"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
 
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::game::entity::{Enemy, Item};
+    use crate::game::entity::{Armor, Enemy, Item};
 
     #[test]
     fn should_tell_whether_room_is_adjacent() {
@@ -157,9 +392,12 @@ mod test {
             (
                 2,
                 &Room {
-                    enemy: Some(Enemy::Daemon(crate::game::entity::Daemon::new(4))),
+                    enemies: vec![Enemy::Daemon(crate::game::entity::Daemon::new(4))],
                     is_exit: false,
                     item: None,
+                    chest: None,
+                    pile: vec![],
+                    npc: None,
                 }
             )
         );
@@ -170,14 +408,58 @@ mod test {
             (
                 7,
                 &Room {
-                    enemy: None,
+                    enemies: vec![],
                     is_exit: true,
                     item: None,
+                    chest: None,
+                    pile: vec![],
+                    npc: None,
                 }
             )
         );
     }
 
+    #[test]
+    fn should_get_distances_from_room() {
+        let maze = Maze::mocked();
+        assert_eq!(maze.distances_from(4, 0), HashMap::from([(4, 0)]));
+        assert_eq!(
+            maze.distances_from(4, 2),
+            HashMap::from([(4, 0), (2, 1), (5, 1), (6, 1), (7, 1), (0, 2), (8, 2)])
+        );
+    }
+
+    #[test]
+    fn should_get_distances_through_room() {
+        let maze = Maze::mocked();
+        assert_eq!(
+            maze.distances_through(4, 2, 2),
+            HashMap::from([(2, 1), (0, 2)])
+        );
+        assert_eq!(maze.distances_through(4, 5, 3), HashMap::from([(5, 1)]));
+    }
+
+    #[test]
+    fn should_get_distance_to_room() {
+        let mut maze = Maze::mocked();
+        assert_eq!(maze.distance_to(|room| room.is_exit()), Some(3));
+        assert_eq!(
+            maze.distance_to(|room| room.contains_item(Item::Sonar)),
+            None
+        );
+        maze.room_mut(4).unwrap().drop_item(Item::Talisman);
+        maze.room_mut(4).unwrap().drop_item(Item::Sonar);
+        assert_eq!(
+            maze.distance_to(|room| room.contains_item(Item::Sonar)),
+            Some(2)
+        );
+        maze.player = 4;
+        assert_eq!(
+            maze.distance_to(|room| room.contains_item(Item::Sonar)),
+            Some(0)
+        );
+    }
+
     #[test]
     fn should_get_rooms() {
         let maze = Maze::mocked();
@@ -209,7 +491,7 @@ mod test {
         assert_eq!(maze.item_in_room(), None);
         maze.player = 1;
         assert_eq!(maze.has_item(), true);
-        assert_eq!(maze.item_in_room().unwrap(), &Item::Armor);
+        assert_eq!(maze.item_in_room().unwrap(), &Item::Armor(Armor::default()));
     }
 
     #[test]
@@ -217,20 +499,31 @@ mod test {
         let mut maze = Maze::mocked();
         assert_eq!(maze.take_item(), None);
         maze.player = 1;
-        assert_eq!(maze.take_item(), Some(Item::Armor));
+        assert_eq!(maze.take_item(), Some(Item::Armor(Armor::default())));
         assert_eq!(maze.take_item(), None);
     }
 
+    #[test]
+    fn should_find_path_to_room() {
+        let mut maze = Maze::mocked();
+        maze.player = 7;
+        assert_eq!(maze.path_to(0, |_| true), Some(vec![4, 2, 0]));
+        assert_eq!(maze.path_to(0, |room| room != 4), None);
+        assert_eq!(maze.path_to(4, |_| false), Some(vec![4]));
+        assert_eq!(maze.path_to(7, |_| true), Some(vec![]));
+    }
+
     #[test]
     fn should_take_enemy() {
         let mut maze = Maze::mocked();
-        assert_eq!(maze.take_enemy(), None);
+        assert_eq!(maze.take_enemy(0), None);
         maze.player = 2;
+        assert_eq!(maze.take_enemy(1), None);
         assert_eq!(
-            maze.take_enemy(),
+            maze.take_enemy(0),
             Some(Enemy::Daemon(crate::game::entity::Daemon::new(4)))
         );
-        assert_eq!(maze.take_enemy(), None);
+        assert_eq!(maze.take_enemy(0), None);
     }
 
     #[test]
@@ -242,12 +535,16 @@ mod test {
             maze.fighting_enemy(),
             Some(&Enemy::Daemon(crate::game::entity::Daemon::new(4)))
         );
-        let enemy = maze.fighting_enemy_mut().unwrap();
+        let enemy = maze.fighting_enemy_mut(0).unwrap();
         enemy.damage(1);
-        assert_eq!(
-            maze.fighting_enemy(),
-            Some(&Enemy::Daemon(crate::game::entity::Daemon::new(3)))
-        );
+        assert_eq!(maze.fighting_enemy().unwrap().health(), 3);
+        assert!(maze.fighting_enemy_mut(1).is_none());
+        maze.room_mut(2)
+            .unwrap()
+            .enemies
+            .push(Enemy::Shadow(crate::game::entity::Shadow::new(2)));
+        assert_eq!(maze.fighting_enemies().len(), 2);
+        assert_eq!(maze.fighting_enemies()[1].name(), "Shadow");
     }
 
     #[test]
@@ -264,93 +561,3 @@ mod test {
         assert_eq!(test, decoded);
     }
 }
-
-#[cfg(test)]
-impl Maze {
-    pub fn mocked() -> Maze {
-        fn fake_maze_graph() -> UnGraph<u32, u32> {
-            /*
-             * 0
-             *  - 1
-             *  \
-             *   -> 3
-             *   -> 9
-             *
-             * - 2
-             *  \
-             *   -> 4
-             *    \ -> 5
-             *    \ -> 6
-             *    \ -> 7
-             *         \
-             *          -> 8 -> 10
-             *          -> exit
-             */
-            let mut nodes: UnGraph<u32, u32> = UnGraph::default();
-            nodes.add_node(0);
-            nodes.add_node(1);
-            nodes.add_node(2);
-            nodes.add_node(3);
-            nodes.add_node(4);
-            nodes.add_node(5);
-            nodes.add_node(6);
-            nodes.add_node(7);
-            nodes.add_node(8);
-            nodes.add_node(9);
-            nodes.add_node(10);
-            nodes.add_edge(0.into(), 1.into(), 0);
-            nodes.add_edge(0.into(), 2.into(), 0);
-            nodes.add_edge(1.into(), 3.into(), 0);
-            nodes.add_edge(1.into(), 9.into(), 0);
-            nodes.add_edge(2.into(), 4.into(), 0);
-            nodes.add_edge(4.into(), 5.into(), 0);
-            nodes.add_edge(4.into(), 6.into(), 0);
-            nodes.add_edge(4.into(), 7.into(), 0);
-            nodes.add_edge(7.into(), 8.into(), 0);
-            nodes.add_edge(8.into(), 10.into(), 0);
-            nodes
-        }
-        let nodes = fake_maze_graph();
-        // add rooms
-        let mut rooms = HashMap::new();
-        rooms.insert(0, Room::default());
-        rooms.insert(
-            1,
-            Room {
-                enemy: None,
-                is_exit: false,
-                item: Some(Item::Armor),
-            },
-        );
-        rooms.insert(
-            2,
-            Room {
-                enemy: Some(Enemy::Daemon(crate::game::entity::Daemon::new(4))),
-                is_exit: false,
-                item: None,
-            },
-        );
-        rooms.insert(3, Room::default());
-        rooms.insert(9, Room::default());
-        rooms.insert(4, Room::default());
-        rooms.insert(5, Room::default());
-        rooms.insert(6, Room::default());
-        rooms.insert(
-            7,
-            Room {
-                enemy: None,
-                is_exit: true,
-                item: None,
-            },
-        );
-        rooms.insert(8, Room::default());
-        rooms.insert(10, Room::default());
-
-        Maze {
-            nodes,
-            rooms,
-            player: 0,
-            seed: String::from("test"),
-        }
-    }
-}
@@ -13,11 +13,22 @@ use rand_seeder::Seeder;
 
 use super::room::Room;
 use super::Maze;
-use crate::game::entity::{Daemon, Enemy, Item, Potion, Shadow};
+use crate::game::entity::{
+    Amulet, Armor, Artifact, Chest, Daemon, DonMaze, Enemy, Herb, Item, Merchant, Npc, Potion,
+    Relic, Shadow, Speaker, Weapon, WeaponKind,
+};
 use crate::utils::random;
 
 const MIN_ROOMS: usize = 96;
 const MAX_ROOMS: usize = 128;
+/// Probability for each artifact to be placed in the maze
+const ARTIFACT_PROBABILITY: u8 = 50;
+/// Probability for a chest to be locked
+const LOCKED_CHEST_PROBABILITY: u8 = 15;
+/// Probability for a chest to be trapped
+const TRAPPED_CHEST_PROBABILITY: u8 = 10;
+/// Probability for a chest to be a mimic
+const MIMIC_PROBABILITY: u8 = 5;
 
 /// Rangom maze generator
 pub struct Generator {
@@ -73,8 +84,14 @@ impl Generator {
         assert!(rooms_to_connect.is_empty());
         // place items in maze
         self.place_items_in_maze(&mut rooms, items_to_place);
+        // put the items in chests
+        self.place_chests_in_maze(&mut rooms);
         // place enemies in maze
         self.place_enemies_in_maze(&mut rooms, enemies_to_place);
+        // place the merchant
+        self.place_merchant_in_maze(&mut rooms);
+        // place the npcs who talk to the player
+        self.place_speakers_in_maze(&mut rooms);
         // place exit
         self.place_maze_exit(&nodes, &mut rooms);
 
@@ -193,21 +210,59 @@ impl Generator {
     ) {
         // keep placing items, until all items have been placed
         while let Some(item) = items_to_place.pop() {
-            // get rooms which are still without any item and NOT room 0
+            // get rooms which are still without any item and NOT room 0; once every room has an item, items are piled up
+            let all_rooms_have_items = rooms
+                .iter()
+                .all(|(node, room)| room.item.is_some() || *node == 0u32);
             let mut rooms_without_items: Vec<u32> = rooms
                 .iter()
-                .filter(|(node, room)| room.item.is_none() && **node != 0u32)
+                .filter(|(node, room)| {
+                    (all_rooms_have_items || room.item.is_none()) && **node != 0u32
+                })
                 .map(|(node, _)| *node)
                 .collect();
             rooms_without_items.sort(); // NOTE: sorting is necessary, since otherwise rooms are randomly sorted based on hashmap order
                                         // choose the room where the item should be placed
             let room = rooms_without_items[self.rand.random_range(0..rooms_without_items.len())];
             let room_data = rooms.get_mut(&room).unwrap();
-            room_data.item = Some(item);
+            room_data.drop_item(item);
             debug!("placed item {:?} in room {}", item, room);
         }
     }
 
+    /// put each room item in a chest, which may be locked, trapped or even a mimic
+    fn place_chests_in_maze(&mut self, rooms: &mut HashMap<DefaultIx, Room>) {
+        let mut rooms_with_items: Vec<u32> = rooms
+            .iter()
+            .filter(|(_, room)| room.item.is_some())
+            .map(|(node, _)| *node)
+            .collect();
+        rooms_with_items.sort(); // NOTE: sorting is necessary, since otherwise rooms are randomly sorted based on hashmap order
+        for room in rooms_with_items {
+            let chest = match self.rand.random_range(0..100) {
+                value if value < LOCKED_CHEST_PROBABILITY => Chest::Locked {
+                    forcing_turns: self.rand.random_range(2..=4),
+                },
+                value if value < LOCKED_CHEST_PROBABILITY + TRAPPED_CHEST_PROBABILITY => {
+                    Chest::Trapped {
+                        damage: self.rand.random_range(1..=3),
+                    }
+                }
+                value
+                    if value
+                        < LOCKED_CHEST_PROBABILITY
+                            + TRAPPED_CHEST_PROBABILITY
+                            + MIMIC_PROBABILITY =>
+                {
+                    Chest::Mimic
+                }
+                _ => Chest::Plain,
+            };
+            debug!("placed chest {:?} in room {}", chest, room);
+            rooms.get_mut(&room).unwrap().chest = Some(chest);
+        }
+    }
+
     /// place enemies in maze randomly
     fn place_enemies_in_maze(
         &mut self,
@@ -219,7 +274,7 @@ impl Generator {
             // get rooms which are still without any ENEMY and NOT room 0
             let mut rooms_without_enemies: Vec<u32> = rooms
                 .iter()
-                .filter(|(node, room)| room.enemy.is_none() && **node != 0u32)
+                .filter(|(node, room)| !room.has_enemies() && **node != 0u32)
                 .map(|(node, _)| *node)
                 .collect();
             rooms_without_enemies.sort(); // NOTE: sorting is necessary, since otherwise rooms are randomly sorted based on hashmap order
@@ -227,11 +282,52 @@ impl Generator {
             let room =
                 rooms_without_enemies[self.rand.random_range(0..rooms_without_enemies.len())];
             let room_data = rooms.get_mut(&room).unwrap();
-            room_data.enemy = Some(enemy);
+            room_data.enemies.push(enemy);
             debug!("placed enemy {:?} in room {}", enemy, room);
         }
     }
 
+    /// place the wandering merchant in a random room, except room 0
+    fn place_merchant_in_maze(&mut self, rooms: &mut HashMap<DefaultIx, Room>) {
+        let merchant = self.generate_merchant();
+        let room = self.rand.random_range(1..rooms.len() as u32);
+        debug!("placed merchant {:?} in room {}", merchant, room);
+        rooms.get_mut(&room).unwrap().npc = Some(Npc::Merchant(merchant));
+    }
+
+    /// place the ghost and the prisoner in random rooms without npcs, except room 0
+    fn place_speakers_in_maze(&mut self, rooms: &mut HashMap<DefaultIx, Room>) {
+        for npc in [
+            Npc::Ghost(Speaker::default()),
+            Npc::Prisoner(Speaker::default()),
+        ] {
+            let mut free_rooms: Vec<u32> = rooms
+                .iter()
+                .filter(|(node, room)| room.npc.is_none() && **node != 0u32)
+                .map(|(node, _)| *node)
+                .collect();
+            free_rooms.sort(); // NOTE: sorting is necessary, since otherwise rooms are randomly sorted based on hashmap order
+            let room = free_rooms[self.rand.random_range(0..free_rooms.len())];
+            debug!("placed npc {:?} in room {}", npc, room);
+            rooms.get_mut(&room).unwrap().npc = Some(npc);
+        }
+    }
+
+    /// Generate the merchant with his stock of potions, armors and sonars
+    fn generate_merchant(&mut self) -> Merchant {
+        let mut stock = Vec::new();
+        for _ in 0..self.rand.random_range(3..7) {
+            stock.push(self.generate_potion());
+        }
+        for _ in 0..self.rand.random_range(1..3) {
+            stock.push(Item::Armor(Armor::default()));
+        }
+        for _ in 0..self.rand.random_range(1..4) {
+            stock.push(Item::Sonar);
+        }
+        Merchant::new(stock)
+    }
+
     /// Place maze exit.
     /// Exit can be placed in ANY room WITH LESS THAN 4 EDGES and with ID > 40
     fn place_maze_exit(
@@ -256,7 +352,7 @@ impl Generator {
     /// generate enemies to place in the maze
     fn enemies_to_place(&mut self) -> Vec<Enemy> {
         debug!("generating enemies to place...");
-        let mut enemies = vec![Enemy::DonMaze];
+        let mut enemies = vec![Enemy::DonMaze(DonMaze::default())];
         let daemons_to_place = self.rand.random_range(5..16);
         debug!("generating {} daemons...", daemons_to_place);
         for _ in 0..daemons_to_place {
@@ -286,11 +382,27 @@ impl Generator {
     /// generate items to place in the maze; the amount is variable, exception made for some items which are always there
     fn items_to_place(&mut self) -> Vec<Item> {
         debug!("generating items to place...");
-        let mut items = vec![Item::MazeKey, Item::AlchemyBook, Item::PaintCan];
+        let mut items = vec![
+            Item::MazeKey,
+            Item::AlchemyBook,
+            Item::PaintCan,
+            Item::Compass,
+        ];
+        // the relics required to forge the mazebane
+        items.extend(Relic::ALL.map(Item::Relic));
         let potions_amount = self.rand.random_range(12..41);
-        let armors_amount = self.rand.random_range(4..9);
+        let armors_amount = self.rand.random_range(2..5);
+        let weapons_amount = self.rand.random_range(3..7);
+        let amulets_amount = self.rand.random_range(1..3);
         let sonars_amount = self.rand.random_range(5..8);
         let talismans_amount = self.rand.random_range(2..5);
+        let torch_oils_amount = self.rand.random_range(4..8);
+        let gold_amount = self.rand.random_range(8..16);
+        let herbs_amount = self.rand.random_range(4..9);
+        let map_scrolls_amount = self.rand.random_range(2..5);
+        let teleport_stones_amount = self.rand.random_range(1..4);
+        let ropes_amount = self.rand.random_range(1..4);
+        let lockpicks_amount = self.rand.random_range(3..7);
         // gen potions
         debug!("generating {} potions...", potions_amount);
         for _ in 0..potions_amount {
@@ -298,7 +410,15 @@ impl Generator {
         }
         debug!("generating {} armors...", armors_amount);
         for _ in 0..armors_amount {
-            items.push(Item::Armor);
+            items.push(Item::Armor(Armor::default()));
+        }
+        debug!("generating {} weapons...", weapons_amount);
+        for _ in 0..weapons_amount {
+            items.push(self.generate_weapon());
+        }
+        debug!("generating {} amulets...", amulets_amount);
+        for _ in 0..amulets_amount {
+            items.push(self.generate_amulet());
         }
         debug!("generating {} sonars...", sonars_amount);
         for _ in 0..sonars_amount {
@@ -308,11 +428,65 @@ impl Generator {
         for _ in 0..talismans_amount {
             items.push(Item::Talisman);
         }
+        debug!("generating {} torch oils...", torch_oils_amount);
+        for _ in 0..torch_oils_amount {
+            items.push(Item::TorchOil);
+        }
+        debug!("generating {} gold pouches...", gold_amount);
+        for _ in 0..gold_amount {
+            items.push(Item::Gold);
+        }
+        debug!("generating {} herbs...", herbs_amount);
+        for _ in 0..herbs_amount {
+            let herb = Herb::ALL[self.rand.random_range(0..Herb::ALL.len())];
+            items.push(Item::Herb(herb));
+        }
+        debug!("generating {} map scrolls...", map_scrolls_amount);
+        for _ in 0..map_scrolls_amount {
+            items.push(Item::MapScroll);
+        }
+        debug!("generating {} teleport stones...", teleport_stones_amount);
+        for _ in 0..teleport_stones_amount {
+            items.push(Item::TeleportStone);
+        }
+        debug!("generating {} ropes...", ropes_amount);
+        for _ in 0..ropes_amount {
+            items.push(Item::Rope);
+        }
+        debug!("generating {} lockpicks...", lockpicks_amount);
+        for _ in 0..lockpicks_amount {
+            items.push(Item::Lockpick);
+        }
+        // artifacts are unique: at most one of each
+        for artifact in Artifact::ALL {
+            if self.rand.random_range(0..100) < ARTIFACT_PROBABILITY {
+                debug!("generating artifact {:?}...", artifact);
+                items.push(Item::Artifact(artifact));
+            }
+        }
         debug!("shuffling items...");
         items.shuffle(&mut self.rand);
         items
     }
 
+    /// Generate random weapon to place
+    fn generate_weapon(&mut self) -> Item {
+        Item::Weapon(Weapon::new(match self.rand.random_range(0..100) {
+            value if value < 40 => WeaponKind::Dagger, // 40%
+            value if value < 75 => WeaponKind::Sword,  // 35%
+            _ => WeaponKind::Axe,                      // 25%
+        }))
+    }
+
+    /// Generate random amulet to place
+    fn generate_amulet(&mut self) -> Item {
+        Item::Amulet(if self.rand.random_bool(0.5) {
+            Amulet::Swiftness
+        } else {
+            Amulet::Warding
+        })
+    }
+
     /// Generate random potion to place
     fn generate_potion(&mut self) -> Item {
         Item::Potion(match self.rand.random_range(0..100) {
@@ -342,6 +516,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::game::entity::EquipmentSlot;
 
     #[test]
     fn should_generate_a_valid_maze() {
@@ -350,35 +525,76 @@ mod test {
         assert!(maze.rooms.len() >= MIN_ROOMS && maze.rooms.len() <= MAX_ROOMS);
         // should have items
         assert_item_in_maze(&maze, Item::AlchemyBook, 1, 1);
-        assert_item_in_maze(&maze, Item::Armor, 4, 8);
+        assert_item_in_maze(&maze, Item::Armor(Armor::default()), 2, 4);
+        assert_equipment_in_maze(&maze, EquipmentSlot::Weapon, 3, 6);
+        assert_equipment_in_maze(&maze, EquipmentSlot::Amulet, 1, 2);
         assert_item_in_maze(&maze, Item::MazeKey, 1, 1);
         assert_item_in_maze(&maze, Item::Sonar, 5, 7);
         assert_item_in_maze(&maze, Item::Talisman, 2, 4);
+        assert_item_in_maze(&maze, Item::TorchOil, 4, 7);
+        assert_item_in_maze(&maze, Item::Gold, 8, 15);
         assert_potion_in_maze(&maze, 12, 40);
+        assert_items_in_maze(&maze, |x| matches!(x, Item::Herb(_)), 4, 8);
         assert_item_in_maze(&maze, Item::PaintCan, 1, 1);
+        assert_item_in_maze(&maze, Item::Compass, 1, 1);
+        assert_item_in_maze(&maze, Item::MapScroll, 2, 4);
+        assert_item_in_maze(&maze, Item::TeleportStone, 1, 3);
+        assert_item_in_maze(&maze, Item::Rope, 1, 3);
+        assert_item_in_maze(&maze, Item::Lockpick, 3, 6);
+        for relic in Relic::ALL {
+            assert_item_in_maze(&maze, Item::Relic(relic), 1, 1);
+        }
+        for artifact in Artifact::ALL {
+            assert_item_in_maze(&maze, Item::Artifact(artifact), 0, 1);
+        }
         assert_item_in_maze(&maze, Item::MazeKey, 1, 1);
+        // every item should be in a chest
+        for (_, room) in maze.rooms() {
+            assert_eq!(room.item.is_some(), room.chest.is_some());
+        }
         // should have enemies
         let mut found = 0;
         for (_, room) in maze.rooms() {
-            if matches!(room.enemy, Some(Enemy::Daemon(_))) {
+            if matches!(room.enemies.as_slice(), [Enemy::Daemon(_)]) {
                 found += 1;
             }
         }
         assert!((5..=15).contains(&found));
         let mut found = 0;
         for (_, room) in maze.rooms() {
-            if matches!(room.enemy, Some(Enemy::Shadow(_))) {
+            if matches!(room.enemies.as_slice(), [Enemy::Shadow(_)]) {
                 found += 1;
             }
         }
         assert!((10..=20).contains(&found));
         let mut found = 0;
         for (_, room) in maze.rooms() {
-            if matches!(room.enemy, Some(Enemy::DonMaze)) {
+            if matches!(room.enemies.as_slice(), [Enemy::DonMaze(_)]) {
                 found += 1;
             }
         }
         assert_eq!(found, 1);
+        // should have the merchant
+        let merchants: Vec<(u32, &Room)> = maze
+            .rooms()
+            .into_iter()
+            .filter(|(_, room)| matches!(room.npc, Some(Npc::Merchant(_))))
+            .collect();
+        assert_eq!(merchants.len(), 1);
+        assert_ne!(merchants[0].0, 0);
+        // should have the ghost and the prisoner
+        for is_speaker in [
+            |npc: &Option<Npc>| matches!(npc, Some(Npc::Ghost(_))),
+            |npc: &Option<Npc>| matches!(npc, Some(Npc::Prisoner(_))),
+        ] {
+            let speakers: Vec<(u32, &Room)> = maze
+                .rooms()
+                .into_iter()
+                .filter(|(_, room)| is_speaker(&room.npc))
+                .collect();
+            assert_eq!(speakers.len(), 1);
+            assert_ne!(speakers[0].0, 0);
+        }
         // should have exit
         let mut found = 0;
         let mut exit = None;
@@ -410,22 +626,28 @@ mod test {
     }
 
     fn assert_item_in_maze(maze: &Maze, item: Item, min_qty: usize, max_qty: usize) {
-        let mut found = 0;
-        for (_, room) in maze.rooms() {
-            if Some(item) == room.item {
-                found += 1;
-            }
-        }
-        assert!(min_qty <= found && found <= max_qty);
+        assert_items_in_maze(maze, |x| x == item, min_qty, max_qty);
+    }
+
+    fn assert_equipment_in_maze(maze: &Maze, slot: EquipmentSlot, min_qty: usize, max_qty: usize) {
+        assert_items_in_maze(maze, |x| x.slot() == Some(slot), min_qty, max_qty);
     }
 
     fn assert_potion_in_maze(maze: &Maze, min_qty: usize, max_qty: usize) {
-        let mut found = 0;
-        for (_, room) in maze.rooms() {
-            if matches!(room.item, Some(Item::Potion(_))) {
-                found += 1;
-            }
-        }
+        assert_items_in_maze(maze, |x| matches!(x, Item::Potion(_)), min_qty, max_qty);
+    }
+
+    /// Count the items matching `predicate` both on the floor and in the piles
+    fn assert_items_in_maze<F>(maze: &Maze, predicate: F, min_qty: usize, max_qty: usize)
+    where
+        F: Fn(Item) -> bool,
+    {
+        let found = maze
+            .rooms()
+            .into_iter()
+            .flat_map(|(_, room)| room.item.iter().chain(room.pile.iter()))
+            .filter(|x| predicate(**x))
+            .count();
         assert!(min_qty <= found && found <= max_qty);
     }
 }
@@ -1,13 +1,67 @@
 //! # Room
 
-use crate::game::entity::{Enemy, Item};
+use crate::game::entity::{Chest, Enemy, Item, Npc};
+
+/// Max amount of enemies which can be in the same room
+pub const MAX_ENEMIES_IN_ROOM: usize = 3;
 
 /// room stores the information related to a node in the maze graph
 #[derive(Debug, Default, Clone, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Room {
-    pub enemy: Option<Enemy>,
+    /// Enemies in the room; the first one is the one in front of the player
+    pub enemies: Vec<Enemy>,
     pub(super) is_exit: bool,
     pub item: Option<Item>,
+    /// The chest holding `item`; items dropped in the room lie on the floor, with no chest
+    #[serde(default)]
+    pub chest: Option<Chest>,
+    /// Items dropped in the room while it already had an item; they show up one at a time once `item` is taken
+    #[serde(default)]
+    pub(super) pile: Vec<Item>,
+    /// Non-playable character in the room
+    #[serde(default)]
+    pub npc: Option<Npc>,
+}
+
+impl Room {
+    /// Drop item in the room. If the room already has an item, the new one is put on the pile
+    pub fn drop_item(&mut self, item: Item) {
+        if self.item.is_none() {
+            self.item = Some(item);
+        } else {
+            self.pile.push(item);
+        }
+    }
+
+    /// Take the item from the room, opening its chest; the next item in the pile, if any, takes its place
+    pub fn take_item(&mut self) -> Option<Item> {
+        let item = self.item.take();
+        self.chest = None;
+        if !self.pile.is_empty() {
+            self.item = Some(self.pile.remove(0));
+        }
+        item
+    }
+
+    /// Returns whether the room is the maze exit
+    pub fn is_exit(&self) -> bool {
+        self.is_exit
+    }
+
+    /// Returns whether `item` lies in the room, either on the floor or in the pile
+    pub fn contains_item(&self, item: Item) -> bool {
+        self.item == Some(item) || self.pile.contains(&item)
+    }
+
+    /// Returns whether there's at least an enemy in the room
+    pub fn has_enemies(&self) -> bool {
+        !self.enemies.is_empty()
+    }
+
+    /// Returns whether the room can't host any other enemy
+    pub fn is_crowded(&self) -> bool {
+        self.enemies.len() >= MAX_ENEMIES_IN_ROOM
+    }
 }
 
 #[cfg(test)]
@@ -16,6 +70,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::game::entity::{DonMaze, Shadow};
 
     #[test]
     fn should_serialize() {
@@ -25,13 +80,54 @@ mod test {
         }
         let test = Test {
             room: Room {
-                enemy: Some(Enemy::DonMaze),
+                enemies: vec![
+                    Enemy::DonMaze(DonMaze::default()),
+                    Enemy::Shadow(Shadow::new(3)),
+                ],
                 is_exit: true,
                 item: Some(Item::AlchemyBook),
+                chest: None,
+                pile: vec![Item::Sonar],
+                npc: None,
             },
         };
         let json = serde_json::to_string(&test).unwrap();
         let decoded: Test = serde_json::from_str(&json).unwrap();
         assert_eq!(test, decoded);
     }
+
+    #[test]
+    fn should_drop_items_on_pile() {
+        let mut room = Room::default();
+        room.drop_item(Item::Sonar);
+        room.drop_item(Item::Talisman);
+        room.drop_item(Item::PaintCan);
+        assert_eq!(room.pile.len(), 2);
+        assert_eq!(room.item, Some(Item::Sonar));
+        assert_eq!(room.take_item(), Some(Item::Sonar));
+        assert_eq!(room.take_item(), Some(Item::Talisman));
+        assert_eq!(room.item, Some(Item::PaintCan));
+        assert_eq!(room.take_item(), Some(Item::PaintCan));
+        assert_eq!(room.take_item(), None);
+    }
+
+    #[test]
+    fn should_tell_whether_room_is_crowded() {
+        let mut room = Room::default();
+        for _ in 0..MAX_ENEMIES_IN_ROOM {
+            assert_eq!(room.is_crowded(), false);
+            room.enemies.push(Enemy::Shadow(Shadow::new(2)));
+        }
+        assert!(room.has_enemies());
+        assert!(room.is_crowded());
+    }
+
+    #[test]
+    fn should_deserialize_room_without_pile() {
+        let room: Room =
+            serde_json::from_str(r#"{"enemies":[],"is_exit":false,"item":{"type":"Sonar"}}"#)
+                .unwrap();
+        assert_eq!(room.item, Some(Item::Sonar));
+        assert!(room.pile.is_empty());
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! Main game core engine and logics
 
+mod difficulty;
 pub mod entity;
+pub mod equipment;
 mod error;
 pub mod inventory;
 mod maze;
@@ -10,6 +12,7 @@ mod options;
 mod runtime;
 pub mod session;
 
+pub use difficulty::Difficulty;
 pub use error::Error as GameError;
 pub use options::Options;
 pub use runtime::Runtime;
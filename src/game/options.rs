@@ -4,7 +4,15 @@
 
 use std::path::PathBuf;
 
+use super::Difficulty;
+
 pub struct Options {
+    /// Autosave every this amount of turns; 0 disables the periodic autosave
+    pub autosave_turns: u64,
+    /// Directory where the run lock is written
+    pub config_dir: PathBuf,
+    /// Difficulty selected for new games
+    pub difficulty: Difficulty,
     /// Enable music
     pub music: bool,
     pub saved_games_dir: PathBuf,
@@ -17,6 +25,9 @@ pub struct Options {
 impl Default for Options {
     fn default() -> Self {
         Self {
+            autosave_turns: 20,
+            config_dir: PathBuf::default(),
+            difficulty: Difficulty::default(),
             music: true,
             sound: true,
             saved_games_dir: PathBuf::default(),
@@ -26,6 +37,21 @@ impl Default for Options {
 }
 
 impl Options {
+    pub fn autosave_turns(mut self, turns: u64) -> Self {
+        self.autosave_turns = turns;
+        self
+    }
+
+    pub fn config_dir(mut self, dir: PathBuf) -> Self {
+        self.config_dir = dir;
+        self
+    }
+
+    pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
+        self.difficulty = difficulty;
+        self
+    }
+
     pub fn music(mut self, m: bool) -> Self {
         self.music = m;
         self
@@ -59,10 +85,16 @@ mod test {
     #[test]
     fn should_create_options() {
         let opts = Options::default()
+            .autosave_turns(10)
+            .config_dir(PathBuf::from("/etc"))
+            .difficulty(Difficulty::Hard)
             .music(true)
             .saved_games_dir(PathBuf::from("/tmp"))
             .sound(true)
             .ticks(30);
+        assert_eq!(opts.autosave_turns, 10);
+        assert_eq!(opts.config_dir.as_path(), Path::new("/etc"));
+        assert_eq!(opts.difficulty, Difficulty::Hard);
         assert_eq!(opts.music, true);
         assert_eq!(opts.sound, true);
         assert_eq!(opts.saved_games_dir.as_path(), Path::new("/tmp"));
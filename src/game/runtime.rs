@@ -7,19 +7,34 @@ use std::time::Duration;
 use tuirealm::props::{Color, Shape};
 
 use super::entity::Enemy;
-use super::session::Action;
-use super::{GameResult, Options, Session};
+use super::entity::PlayerState;
+use super::session::{Action, ExploreAction, Message};
+use super::{Difficulty, GameResult, Options, Session};
 use crate::audio::{AudioEngine, Sound, Theme};
-use crate::game::entity::Item;
+use crate::game::entity::{Chest, Item, LightLevel, Npc};
 use crate::gfx::{ascii_art, Render, Room as RoomToRender};
 use crate::ui::{GameMsg, GameOverMsg, Id, LoadGameMsg, MenuId, MenuMsg, Msg, Ui, VictoryMsg};
-use crate::utils::room_resolver;
-use crate::utils::saved_games::SavedGameFiles;
+use crate::utils::run_lock::RunLock;
+use crate::utils::saved_games::{SavedGameError, SavedGameFiles, AUTOSAVE_NAME};
+use crate::utils::{dirs, room_resolver};
+
+/// Color of the room walls when the torch light is dim
+const DIM_LIGHT_COLOR: Color = Color::Rgb(96, 96, 96);
+/// Color of the room walls when the torch is burnt out
+const DARK_COLOR: Color = Color::Rgb(40, 40, 40);
 
 /// Game runtime
 pub struct Runtime {
     audio: Option<AudioEngine>,
+    /// Autosave every this amount of turns; 0 disables the periodic autosave
+    autosave_turns: u64,
+    /// Difficulty selected for new games
+    difficulty: Difficulty,
     music: bool,
+    /// Whether the autosave left by a run which didn't exit cleanly can be recovered
+    recoverable: bool,
+    /// Marker of the running game; released on a clean exit
+    run_lock: Option<RunLock>,
     saved_games_dir: PathBuf,
     render: Render,
     running: bool,
@@ -44,13 +59,32 @@ impl Runtime {
         let (width, height) = ui.sizes()?;
         let render = Render::new(width, height);
         info!("Render engine configured to work on {}x{}", width, height);
+        let (run_lock, crashed) =
+            match RunLock::acquire(&dirs::get_run_lock_path(&options.config_dir)) {
+                Ok((run_lock, crashed)) => (Some(run_lock), crashed),
+                Err(err) => {
+                    error!(
+                        "failed to acquire run lock: {}; crashes won't be detected",
+                        err
+                    );
+                    (None, false)
+                }
+            };
+        let recoverable = crashed && options.saved_games_dir.join(AUTOSAVE_NAME).is_file();
+        if recoverable {
+            info!("the previous run didn't exit cleanly; the autosave can be recovered");
+        }
         // loading menu
         debug!("loading menu");
-        ui.load_menu()?;
+        ui.load_menu(recoverable, options.difficulty)?;
         info!("menu loaded");
         Ok(Self {
             audio,
+            autosave_turns: options.autosave_turns,
+            difficulty: options.difficulty,
             music: options.music,
+            recoverable,
+            run_lock,
             saved_games_dir: options.saved_games_dir,
             render,
             running: true,
@@ -79,6 +113,9 @@ impl Runtime {
         // Finalize terminal and stop sound
         debug!("stopping theme...");
         self.play_theme(Theme::None)?;
+        if let Some(run_lock) = self.run_lock.take() {
+            run_lock.release()?;
+        }
 
         Ok(())
     }
@@ -102,9 +139,16 @@ impl Runtime {
 
     /// Start gameplay in the maze
     fn start_maze(&mut self, session: Session) -> GameResult<()> {
+        // NOTE: the autosave of the previous run is replaced right away by the new game
+        self.recoverable = false;
         self.ui.load_game(&session)?;
+        if let Some(version) = session.migrated_from() {
+            self.ui
+                .update_game_messages(&[Message::SaveMigrated(version)], &session)?;
+        }
         self.play_theme(Theme::Maze)?;
         self.session = Some(session);
+        self.autosave();
         self.render_shapes()?;
 
         Ok(())
@@ -114,34 +158,97 @@ impl Runtime {
     fn load_game(&mut self, game_file: &Path) -> GameResult<()> {
         debug!("loading game {}", game_file.display());
         let session = match SavedGameFiles::load_game(game_file) {
-            Ok(s) if s.is_version_compatible() => s,
-            Ok(_) => {
-                error!("failed to load game: incompatible game version");
-                self.ui.show_load_game_error("incompatible game version")?;
-                return Ok(());
-            }
+            Ok(s) => s,
             Err(e) => {
                 error!("failed to load game: {}", e);
-                self.ui
-                    .show_load_game_error(format!("failed to load game: {}", e))?;
-                return Ok(());
+                return self.show_load_game_error(game_file, e);
             }
         };
         self.start_maze(session)
     }
 
-    /// Save game as name
-    fn save_game(&mut self, name: &str) -> GameResult<()> {
-        if let Some(session) = self.session.as_ref() {
-            debug!("saving game as {}", name);
-            SavedGameFiles::save_game(name, &self.saved_games_dir, session)?;
+    /// Report the failure loading `game_file`; if the save has backups, offer to restore them
+    fn show_load_game_error(&mut self, game_file: &Path, e: SavedGameError) -> GameResult<()> {
+        let text = format!("failed to load game: {}", e);
+        if SavedGameFiles::backups(game_file).is_empty() {
+            self.ui.show_load_game_error(text)?;
+        } else {
+            self.ui.show_load_game_restore(text, game_file)?;
         }
         Ok(())
     }
 
+    /// Save game as name; if `overwrite` is set, the existing save with the same name is replaced.
+    /// If the save already exists, asks the player whether to overwrite it
+    fn save_game(&mut self, name: &str, overwrite: bool) -> GameResult<()> {
+        let Some(session) = self.session.as_ref() else {
+            return Ok(());
+        };
+        debug!("saving game as {}", name);
+        let result = if overwrite {
+            SavedGameFiles::overwrite_game(name, &self.saved_games_dir, session)
+        } else {
+            SavedGameFiles::save_game(name, &self.saved_games_dir, session)
+        };
+        match result {
+            Ok(()) => self.play_action(Action::SaveGame)?,
+            Err(SavedGameError::AlreadyExists(name)) => {
+                debug!(
+                    "save {} already exists; asking whether to overwrite it",
+                    name
+                );
+                self.ui.show_game_overwrite_popup(&name)?;
+            }
+            Err(err) => {
+                error!("failed to save game: {}", err);
+                self.ui
+                    .show_game_error_popup(format!("failed to save game: {}", err))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the autosave; failures are only logged, since they mustn't interrupt the game
+    fn autosave(&self) {
+        if let Some(session) = self.session.as_ref() {
+            debug!("autosaving game at turn {}", session.stats().turn);
+            if let Err(err) = SavedGameFiles::autosave(&self.saved_games_dir, session) {
+                error!("failed to autosave game: {}", err);
+            }
+        }
+    }
+
+    /// Recover the autosave left by the last run
+    fn recover_session(&mut self) -> GameResult<()> {
+        let path = self.saved_games_dir.join(AUTOSAVE_NAME);
+        info!("recovering last session from {}", path.display());
+        match SavedGameFiles::load_game(&path).or_else(|err| {
+            warn!("failed to load autosave: {}; trying backups", err);
+            SavedGameFiles::restore_backup(&path)
+        }) {
+            Ok(session) => self.start_maze(session),
+            Err(err) => {
+                error!("failed to recover last session: {}", err);
+                self.play_sound(Sound::Error);
+                self.ui
+                    .show_menu_recover_error(format!("failed to recover last session: {}", err))?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Delete the autosave of a finished game, so that it can't be recovered;
+    /// failures are only logged
+    fn discard_autosave(&self) {
+        if let Err(err) = SavedGameFiles::discard_autosave(&self.saved_games_dir) {
+            error!("failed to discard autosave: {}", err);
+        }
+    }
+
     /// Play action in game
     fn play_action(&mut self, action: Action) -> GameResult<()> {
         debug!("playing action {:?}", action);
+        let was_fighting = self.session.as_ref().unwrap().player().state() == PlayerState::Fight;
         let effect = self.session.as_mut().unwrap().play_turn(action);
         // change theme if state changed
         // play sounds
@@ -150,17 +257,20 @@ impl Runtime {
         }
         self.switch_maze_theme()?;
         // show messages
+        let npc_met = effect
+            .messages
+            .iter()
+            .any(|x| matches!(x, Message::NpcMet(_)));
         debug!("updating messages: {:?}", effect.messages);
         self.ui
             .update_game_messages(&effect.messages, self.session.as_ref().unwrap())?;
         debug!("updating player health");
         self.ui
-            .update_game_player_health(self.session.as_ref().unwrap().player().health())?;
-        let fighting_enemy = self.session.as_ref().unwrap().get_fighting_enemy();
-        if let Some(enemy) = fighting_enemy {
-            debug!("updating enemy data: {:?}", enemy);
-            self.ui
-                .update_game_enemy_data(enemy.health(), enemy.name())?;
+            .update_game_player_health(self.session.as_ref().unwrap())?;
+        let fighting_enemies = self.session.as_ref().unwrap().get_fighting_enemies();
+        if !fighting_enemies.is_empty() {
+            debug!("updating enemy data: {:?}", fighting_enemies);
+            self.ui.update_game_enemy_data(fighting_enemies)?;
         } else {
             debug!("hiding enemy data");
             self.ui.hide_game_enemy_data()?;
@@ -168,49 +278,105 @@ impl Runtime {
 
         if self.session.as_ref().unwrap().game_over() {
             info!("player is dead; show game over");
+            self.discard_autosave();
             self.ui.show_game_gameover_popup()?;
             self.play_theme(Theme::GameOver)?;
             return Ok(());
         }
         if self.session.as_ref().unwrap().has_won() {
             info!("player has won; show victory");
+            self.discard_autosave();
             let session = self.session.take().unwrap();
             self.ui.load_victory(&session)?;
             self.play_theme(Theme::Victory)?;
             return Ok(());
         }
+        // autosave periodically and when a fight starts
+        let session = self.session.as_ref().unwrap();
+        let fight_started = !was_fighting && session.player().state() == PlayerState::Fight;
+        if fight_started
+            || (self.autosave_turns > 0 && session.stats().turn.is_multiple_of(self.autosave_turns))
+        {
+            self.autosave();
+        }
         // update actions
         debug!("updating possible actions");
         self.ui
             .update_game_actions(self.session.as_ref().unwrap())?;
         // update canvas
         self.render_shapes()?;
+        if npc_met {
+            debug!("player met an npc; show npc popup");
+            self.show_npc_popup()?;
+        }
         sleep(Duration::from_millis(300));
         Ok(())
     }
 
+    /// Show the popup to interact with the npc in the room: trade with the merchant or talk to the others
+    fn show_npc_popup(&mut self) -> GameResult<()> {
+        match self.session.as_ref() {
+            Some(session) if session.can_trade() => self.ui.show_game_trade(session)?,
+            Some(session) if session.can_talk() => self.ui.show_game_dialogue(session, 0)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// render shapes in canvas
     fn render_shapes(&mut self) -> GameResult<()> {
         debug!("rendering shapes");
         let room_to_render = self.room_to_render();
-        let room = self.render.render_room(room_to_render);
+        let light_level = self.session.as_ref().unwrap().player().torch.light_level();
+        let room = match light_level {
+            LightLevel::Bright => self.render.render_room(room_to_render),
+            LightLevel::Dim => self
+                .render
+                .fade(self.render.render_room(room_to_render), DIM_LIGHT_COLOR),
+            LightLevel::Dark => self
+                .render
+                .fade(self.render.render_room(room_to_render), DARK_COLOR),
+        };
         debug!("room rendered");
+        // the enemy fighting the player can be seen even in the dark
         let entity = if let Some(enemy) = self.session.as_ref().unwrap().get_fighting_enemy() {
             debug!("rendering enemy {:?}", enemy);
             self.render_enemy(enemy)?
+        } else if light_level == LightLevel::Dark {
+            debug!("it's too dark to see anything else in the room");
+            vec![]
+        } else if let Some(npc) = self.session.as_ref().unwrap().npc() {
+            debug!("rendering npc {}", npc.name());
+            let (art, color) = match npc {
+                Npc::Ghost(_) => (ascii_art::GHOST, Color::Gray),
+                Npc::Merchant(_) => (ascii_art::MERCHANT, Color::LightYellow),
+                Npc::Prisoner(_) => (ascii_art::PRISONER, Color::LightBlue),
+            };
+            let (x, y) = self.shape_position(art)?;
+            self.render.ascii_art(x, y, art, color)
         } else if let Some(item) = self.session.as_ref().unwrap().get_item_in_the_room() {
             debug!("rendering item {:?}", item);
-            let (x, y) = self.shape_position(ascii_art::CHEST)?;
-            self.render.ascii_art(x, y, ascii_art::CHEST, Color::Yellow)
+            let art = match self.session.as_ref().unwrap().chest() {
+                None => ascii_art::OPEN_CHEST,
+                Some(Chest::Locked { .. }) => ascii_art::LOCKED_CHEST,
+                Some(Chest::Mimic | Chest::Plain | Chest::Trapped { .. }) => ascii_art::CHEST,
+            };
+            let (x, y) = self.shape_position(art)?;
+            self.render.ascii_art(x, y, art, Color::Yellow)
         } else {
             vec![]
         };
-        debug!("rendering graffitis");
-        let graffiti = self
-            .render
-            .graffiti(self.session.as_ref().unwrap().player_room(), room_to_render);
-        // Rendering wall mark
-        let wall_mark = self.render_wall_mark(room_to_render);
+        // graffiti and wall mark can be seen only with a bright light
+        let (graffiti, wall_mark) = if light_level == LightLevel::Bright {
+            debug!("rendering graffitis");
+            let graffiti = self
+                .render
+                .graffiti(self.session.as_ref().unwrap().player_room(), room_to_render);
+            // Rendering wall mark
+            (graffiti, self.render_wall_mark(room_to_render))
+        } else {
+            (vec![], vec![])
+        };
         debug!("stacking shapes and rendering canvas");
         let shapes = self.render.stack(vec![room, graffiti, wall_mark, entity]);
         self.ui.update_game_canvas(&shapes)?;
@@ -242,7 +408,8 @@ impl Runtime {
     fn render_enemy(&self, enemy: &Enemy) -> GameResult<Vec<Shape>> {
         let (art, color) = match enemy {
             Enemy::Daemon(_) => (ascii_art::DAEMON, Color::Red),
-            Enemy::DonMaze => (ascii_art::DON_MAZE, Color::Blue),
+            Enemy::DonMaze(_) => (ascii_art::DON_MAZE, Color::Blue),
+            Enemy::Mimic(_) => (ascii_art::MIMIC, Color::Yellow),
             Enemy::Shadow(_) => (ascii_art::SHADOW, Color::Magenta),
         };
 
@@ -266,34 +433,13 @@ impl Runtime {
     }
 
     fn switch_maze_theme(&mut self) -> GameResult<()> {
-        if self
-            .session
-            .as_ref()
-            .unwrap()
-            .get_fighting_enemy()
-            .is_some()
-            && self
-                .audio
-                .as_ref()
-                .map(|x| x.theme())
-                .unwrap_or(Theme::Fight)
-                != Theme::Fight
-        {
-            self.play_theme(Theme::Fight)?;
-        } else if self
-            .session
-            .as_ref()
-            .unwrap()
-            .get_fighting_enemy()
-            .is_none()
-            && self
-                .audio
-                .as_ref()
-                .map(|x| x.theme())
-                .unwrap_or(Theme::Maze)
-                != Theme::Maze
-        {
-            self.play_theme(Theme::Maze)?;
+        let theme = match self.session.as_ref().unwrap().get_fighting_enemy() {
+            Some(Enemy::DonMaze(_)) => Theme::BossFight,
+            Some(_) => Theme::Fight,
+            None => Theme::Maze,
+        };
+        if self.audio.as_ref().map(|x| x.theme()).unwrap_or(theme) != theme {
+            self.play_theme(theme)?;
         }
         Ok(())
     }
@@ -324,6 +470,59 @@ impl Runtime {
                 self.ui.close_game_inventory()?;
                 self.play_sound(Sound::Input);
             }
+            GameMsg::Brew(action) => {
+                self.play_sound(Sound::Input);
+                self.play_action(action)?;
+                // keep brewing as long as the player can
+                match self.session.as_ref() {
+                    Some(session) if session.can_brew() => self.ui.show_game_brew(session)?,
+                    _ => self.ui.close_game_brew()?,
+                }
+            }
+            GameMsg::CloseBrew => {
+                self.ui.close_game_brew()?;
+                self.play_sound(Sound::Input);
+                // go back to the inventory
+                if let Some(session) = self.session.as_ref() {
+                    if session.can_use_items() {
+                        self.ui.show_game_inventory(session)?;
+                    }
+                }
+            }
+            GameMsg::CloseDialogue => {
+                self.ui.close_game_dialogue()?;
+                self.play_sound(Sound::Input);
+            }
+            GameMsg::DialogueChoice(node, choice) => {
+                self.play_sound(Sound::Input);
+                let Some(choice_data) = self
+                    .session
+                    .as_ref()
+                    .and_then(|x| x.dialogue())
+                    .and_then(|x| x.choice(node, choice))
+                else {
+                    self.ui.close_game_dialogue()?;
+                    return Ok(());
+                };
+                // only choices with an effect take a turn
+                if choice_data.effect.is_some() {
+                    self.play_action(Action::Explore(ExploreAction::Talk(node, choice)))?;
+                }
+                match (self.session.as_ref(), choice_data.next) {
+                    (Some(session), Some(next)) if session.can_talk() => {
+                        self.ui.show_game_dialogue(session, next)?
+                    }
+                    _ => self.ui.close_game_dialogue()?,
+                }
+            }
+            GameMsg::CloseTrade => {
+                self.ui.close_game_trade()?;
+                self.play_sound(Sound::Input);
+            }
+            GameMsg::CloseOverwritePopup => {
+                self.ui.close_game_overwrite_popup()?;
+                self.play_sound(Sound::Input);
+            }
             GameMsg::CloseQuitPopup => {
                 self.ui.close_game_quit_popup()?;
                 self.play_sound(Sound::Input);
@@ -341,7 +540,8 @@ impl Runtime {
             GameMsg::Quit(save) => {
                 self.play_sound(Sound::Input);
                 if save {
-                    if let Err(err) = self.save_game("autosave") {
+                    let session = self.session.as_ref().unwrap();
+                    if let Err(err) = SavedGameFiles::autosave(&self.saved_games_dir, session) {
                         error!("failed to save game: {}", err);
                         self.ui
                             .show_game_error_popup(format!("failed to save game: {}", err))?;
@@ -350,17 +550,26 @@ impl Runtime {
                 }
                 self.session = None;
                 self.play_theme(Theme::Menu)?;
-                self.ui.load_menu()?;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
+            }
+            GameMsg::OverwriteGame(name) => {
+                self.ui.close_game_overwrite_popup()?;
+                self.play_sound(Sound::Input);
+                self.save_game(&name, true)?;
             }
             GameMsg::SaveGame(name) => {
                 self.ui.close_game_save_file_name()?;
                 self.play_sound(Sound::Input);
-                if let Err(err) = self.save_game(&name) {
-                    error!("failed to save game: {}", err);
-                    self.ui
-                        .show_game_error_popup(format!("failed to save game: {}", err))?;
+                self.save_game(&name, false)?;
+            }
+            GameMsg::ShowBrew => {
+                self.play_sound(Sound::Input);
+                if let Some(session) = self.session.as_ref() {
+                    if session.can_brew() {
+                        self.ui.close_game_inventory()?;
+                        self.ui.show_game_brew(session)?;
+                    }
                 }
-                self.play_action(Action::SaveGame)?;
             }
             GameMsg::ShowInventory => {
                 self.play_sound(Sound::Input);
@@ -370,6 +579,19 @@ impl Runtime {
                     }
                 }
             }
+            GameMsg::ShowNpc => {
+                self.play_sound(Sound::Input);
+                self.show_npc_popup()?;
+            }
+            GameMsg::Trade(action) => {
+                self.play_sound(Sound::Input);
+                self.play_action(action)?;
+                // keep trading as long as the merchant is in the room
+                match self.session.as_ref() {
+                    Some(session) if session.can_trade() => self.ui.show_game_trade(session)?,
+                    _ => self.ui.close_game_trade()?,
+                }
+            }
             GameMsg::ShowQuitPopup => {
                 self.play_sound(Sound::Input);
                 self.ui.show_game_quit_popup()?;
@@ -378,6 +600,16 @@ impl Runtime {
                 self.play_sound(Sound::Input);
                 self.ui.show_game_save_file_name()?;
             }
+            GameMsg::UnequipItem(slot) => {
+                self.play_sound(Sound::Input);
+                self.play_action(Action::UnequipItem(slot))?;
+                self.ui.close_game_inventory()?;
+            }
+            GameMsg::DropItem(item) => {
+                self.play_sound(Sound::Input);
+                self.play_action(Action::DropItem(item))?;
+                self.ui.close_game_inventory()?;
+            }
             GameMsg::UseItem(item) => {
                 self.play_sound(Sound::Input);
                 self.play_action(Action::UseItem(item))?;
@@ -392,7 +624,7 @@ impl Runtime {
             GameOverMsg::GoToMenu => {
                 self.play_sound(Sound::Input);
                 self.play_theme(Theme::Menu)?;
-                self.ui.load_menu()?;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
             }
         }
 
@@ -407,30 +639,49 @@ impl Runtime {
             }
             LoadGameMsg::GoToMenu => {
                 self.play_sound(Sound::Input);
-                self.ui.load_menu()?;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
             }
-            LoadGameMsg::GameChanged(p) => match SavedGameFiles::load_game(&p) {
+            LoadGameMsg::GameChanged(p) => match SavedGameFiles::read_header(&p) {
                 Err(e) => {
                     self.play_sound(Sound::Input);
                     error!("failed to load game: {}", e);
-                    self.ui
-                        .show_load_game_error(format!("failed to load game: {}", e))?;
+                    self.show_load_game_error(&p, e)?;
                 }
-                Ok(session) => {
+                Ok(header) => {
                     self.play_sound(Sound::Input);
-                    self.ui.set_load_game_save_metadata(&session)?;
+                    self.ui.set_load_game_save_metadata(&header)?;
                 }
             },
             LoadGameMsg::LoadGame(game_file) => {
                 self.play_sound(Sound::Input);
                 self.load_game(&game_file)?;
             }
+            LoadGameMsg::RestoreBackup(game_file) => {
+                self.play_sound(Sound::Input);
+                match SavedGameFiles::restore_backup(&game_file)
+                    .and_then(|_| SavedGameFiles::read_header(&game_file))
+                {
+                    Ok(header) => {
+                        self.ui.close_load_game_error()?;
+                        self.ui.set_load_game_save_metadata(&header)?;
+                    }
+                    Err(e) => {
+                        error!("failed to restore game: {}", e);
+                        self.ui
+                            .show_load_game_error(format!("failed to restore game: {}", e))?;
+                    }
+                }
+            }
         }
         Ok(())
     }
 
     fn update_menu(&mut self, msg: MenuMsg) -> GameResult<()> {
         match msg {
+            MenuMsg::ActiveDifficulty => {
+                self.play_sound(Sound::Input);
+                self.ui.active(Id::Menu(MenuId::Difficulty));
+            }
             MenuMsg::ActiveExit => {
                 self.play_sound(Sound::Input);
                 self.ui.active(Id::Menu(MenuId::Exit));
@@ -443,10 +694,28 @@ impl Runtime {
                 self.play_sound(Sound::Input);
                 self.ui.active(Id::Menu(MenuId::NewGame));
             }
+            MenuMsg::ActiveRecover => {
+                self.play_sound(Sound::Input);
+                self.ui.active(Id::Menu(MenuId::Recover));
+            }
             MenuMsg::ActiveSeed => {
                 self.play_sound(Sound::Input);
                 self.ui.active(Id::Menu(MenuId::Seed));
             }
+            MenuMsg::CloseRecoverError => {
+                self.play_sound(Sound::Input);
+                self.recoverable = false;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
+            }
+            MenuMsg::DiscardAutosave => {
+                self.play_sound(Sound::Input);
+                if let Err(err) = SavedGameFiles::discard_autosave(&self.saved_games_dir) {
+                    error!("failed to discard autosave: {}", err);
+                    self.play_sound(Sound::Error);
+                }
+                self.recoverable = false;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
+            }
             MenuMsg::LoadGame => {
                 let saved_games = SavedGameFiles::saved_games(&self.saved_games_dir)?;
                 if saved_games.is_empty() {
@@ -455,7 +724,7 @@ impl Runtime {
                 } else {
                     let game_0 = match saved_games.first() {
                         None => None,
-                        Some(p) => SavedGameFiles::load_game(p).ok(),
+                        Some(p) => SavedGameFiles::read_header(p).ok(),
                     };
                     self.ui.load_game_loader(&saved_games, game_0.as_ref())?;
                 }
@@ -464,8 +733,16 @@ impl Runtime {
                 self.play_sound(Sound::Input);
                 // create a new session
                 let seed = self.ui.get_menu_seed()?;
-                debug!("initializing new session with seed {:?}", seed);
-                self.start_maze(Session::new(seed))?;
+                self.difficulty = self.ui.get_menu_difficulty()?;
+                debug!(
+                    "initializing new session with seed {:?} on {:?}",
+                    seed, self.difficulty
+                );
+                self.start_maze(Session::new(seed, self.difficulty))?;
+            }
+            MenuMsg::Recover => {
+                self.play_sound(Sound::Input);
+                self.recover_session()?;
             }
             MenuMsg::Quit => {
                 self.play_sound(Sound::Input);
@@ -481,7 +758,7 @@ impl Runtime {
             VictoryMsg::GoToMenu => {
                 self.play_sound(Sound::Input);
                 self.play_theme(Theme::Menu)?;
-                self.ui.load_menu()?;
+                self.ui.load_menu(self.recoverable, self.difficulty)?;
             }
         }
         Ok(())
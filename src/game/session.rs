@@ -5,25 +5,54 @@
 use std::collections::HashSet;
 
 use chrono::Local;
+use rand::Rng;
+use rand_pcg::Pcg64;
+use rand_seeder::Seeder;
 
-use super::entity::{Enemy, Item, Player, PlayerState};
+use super::entity::{
+    Chest, Dialogue, Enemy, Item, Merchant, Npc, Player, PlayerState, Potion, PotionKnowledge,
+    Recipe, StatusEffect,
+};
 use super::inventory::Inventory;
 use super::maze::Maze;
+use super::{Difficulty, Hp};
 use crate::audio::Sound;
+use crate::utils::room_resolver::{self, Direction};
+
+const ESCAPE_PROBABILITY: u8 = 50;
+const HASTED_ESCAPE_BONUS: u8 = 25;
+/// Probability for the player to counterattack while defending
+pub const COUNTERATTACK_PROBABILITY: u8 = 30;
+const BARE_HANDED_ACCURACY: u8 = 90;
+const BLINDED_ACCURACY_MALUS: u8 = 30;
+const HASTED_ACCURACY_BONUS: u8 = 10;
+const CRITICAL_PROBABILITY: u8 = 10;
+/// Noise made by steps; noise is measured as the amount of rooms it spreads through
+const STEPS_NOISE: u8 = 2;
+/// Noise made by drinking a potion
+const POTION_NOISE: u8 = 1;
+/// Noise made by fighting; it's the loudest noise the player can make
+pub const MAX_NOISE: u8 = 3;
+/// Amount of turns the player can choose to rest for
+const REST_TURNS: [u8; 3] = [5, 10, 20];
 
 mod action;
 mod action_replay;
+mod awareness;
 mod cpu;
 mod effect;
+mod migration;
 mod stats;
 mod version;
 
 pub use action::{Action, ExploreAction, FightAction};
 use action_replay::ActionReplay;
+use awareness::Awareness;
 use cpu::Cpu;
 pub use effect::{Effect, Message, Reveal};
+pub use migration::{MigrationError, MigrationResult};
 pub use stats::Stats;
-use version::Version;
+pub use version::Version;
 
 /// The session contains all the game states.
 /// It must be serializable since it is used to save and load games
@@ -33,36 +62,80 @@ pub struct Session {
     maze: Maze,
     #[cfg(test)]
     pub maze: Maze,
+    /// Game difficulty
+    #[serde(default)]
+    difficulty: Difficulty,
     /// The last room the player's been
     last_room: Option<u32>,
     player: Player,
     /// Game stats
     stats: Stats,
-    /// Game version; used to check whether this version loaded is compatible
+    /// Game version; used to migrate saves from older versions
     version: Version,
+    /// The version the session has been migrated from when loaded
+    #[serde(skip)]
+    migrated_from: Option<Version>,
     /// List of visited rooms
     visited_rooms: HashSet<u32>,
     /// has the player won
     won: bool,
+    /// Whether the player has won by defeating Don Maze
+    #[serde(default)]
+    don_maze_defeated: bool,
+    /// Whether the player is defending in the current turn
+    #[serde(skip)]
+    defending: bool,
+    /// Position of the fighting enemy put asleep in the current turn, if any
+    #[serde(skip)]
+    enemy_asleep: Option<usize>,
+    /// Noise made by the player in the last turn
+    #[serde(skip)]
+    noise: u8,
+    /// Turns spent resting, if the player is resting
+    #[serde(default)]
+    rest: Option<u8>,
+    /// Alchemy recipes discovered by the player
+    #[serde(default)]
+    recipes: Vec<Recipe>,
+    /// Potions identified by drinking them
+    #[serde(default)]
+    identified_potions: HashSet<Potion>,
 }
 
 impl Session {
     /// Create a new session
-    pub fn new(seed: Option<String>) -> Self {
+    pub fn new(seed: Option<String>, difficulty: Difficulty) -> Self {
         Self {
             maze: Maze::generate(seed),
+            difficulty,
             last_room: None,
             player: Player::default(),
             stats: Stats::default(),
-            version: Version::V010,
+            version: Version::CURRENT,
+            migrated_from: None,
             visited_rooms: HashSet::default(),
             won: false,
+            don_maze_defeated: false,
+            defending: false,
+            enemy_asleep: None,
+            noise: 0,
+            rest: None,
+            recipes: Vec::default(),
+            identified_potions: HashSet::default(),
         }
     }
 
-    /// Returns whether session version is compatible with game
-    pub fn is_version_compatible(&self) -> bool {
-        self.version.is_compatible()
+    /// Load session from a saved game, migrating it from older versions
+    pub fn from_save(mut save: serde_json::Value) -> MigrationResult<Self> {
+        let migrated_from = migration::migrate(&mut save)?;
+        let mut session: Self = serde_json::from_value(save)?;
+        session.migrated_from = migrated_from;
+        Ok(session)
+    }
+
+    /// Returns the version the session has been migrated from when loaded, if any
+    pub fn migrated_from(&self) -> Option<Version> {
+        self.migrated_from
     }
 
     /// Returns whether previous room is set
@@ -75,6 +148,11 @@ impl Session {
         self.last_room
     }
 
+    /// Get game difficulty
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
     /// Get maze seed
     pub fn maze_seed(&self) -> &str {
         self.maze.seed()
@@ -95,6 +173,11 @@ impl Session {
         self.won
     }
 
+    /// Report whether player has won by defeating Don Maze
+    pub fn has_defeated_don_maze(&self) -> bool {
+        self.don_maze_defeated
+    }
+
     /// Returns whether is game over
     pub fn game_over(&self) -> bool {
         self.player.is_dead()
@@ -105,6 +188,11 @@ impl Session {
         &self.player
     }
 
+    /// Noise made by the player in the last turn
+    pub fn noise(&self) -> u8 {
+        self.noise
+    }
+
     /// Return player's room
     pub fn player_room(&self) -> u32 {
         self.maze.player
@@ -130,15 +218,25 @@ impl Session {
         self.visited_rooms.len()
     }
 
-    /// Get fighting enemy
+    /// Get the enemy in front of the player
     pub fn get_fighting_enemy(&self) -> Option<&Enemy> {
         self.maze.fighting_enemy()
     }
 
+    /// Get all the enemies the player is fighting with
+    pub fn get_fighting_enemies(&self) -> &[Enemy] {
+        self.maze.fighting_enemies()
+    }
+
     pub fn get_item_in_the_room(&self) -> Option<&Item> {
         self.maze.item_in_room()
     }
 
+    /// Get the chest holding the item in the player's room, if any
+    pub fn chest(&self) -> Option<Chest> {
+        self.maze.chest().copied()
+    }
+
     /// Returns whether current room is exit
     pub fn is_exit(&self) -> bool {
         self.maze.is_exit()
@@ -164,16 +262,152 @@ impl Session {
         self.stats.last_turn = Local::now();
         debug!("playing turn {}...", self.stats.turn);
         let mut effect = Effect::default();
+        let player_room = self.maze.player;
+        self.noise = 0;
+        if action != Action::Die {
+            self.elapse_turn(&mut effect);
+            if self.game_over() {
+                debug!("player died due to status effects");
+                return effect;
+            }
+        }
         ActionReplay::new(self).play(action, &mut effect);
+        if let Some(npc) = self.maze.npc().filter(|_| self.maze.player != player_room) {
+            debug!("player entered the room of {}", npc.name());
+            effect.message(Message::NpcMet(npc.clone()));
+        }
         // Check whether player has won; otherwise play cpu turn
         if action == Action::Explore(ExploreAction::LeaveMaze) {
             self.won = true;
-        } else {
+        } else if !self.won {
             Cpu::new(self).play(&mut effect);
         }
+        // sneaking costs an extra turn, where the player does nothing
+        if matches!(action, Action::Explore(ExploreAction::Sneak(_))) && !self.game_over() {
+            debug!("sneaking; playing an extra turn for cpu");
+            self.stats.turn += 1;
+            self.elapse_turn(&mut effect);
+            if !self.game_over() {
+                Cpu::new(self).play(&mut effect);
+            }
+        }
+        if !self.won && !self.game_over() {
+            Awareness::new(self).play(&mut effect);
+        }
         effect
     }
 
+    /// Tick status effects and burn the torch
+    fn elapse_turn(&mut self, effect: &mut Effect) {
+        self.tick_status_effects(effect);
+        if self.game_over() {
+            return;
+        }
+        if let Some(light_level) = self.player.torch.burn() {
+            debug!("light level is now {:?}", light_level);
+            effect.message(Message::LightChanged(light_level));
+        }
+    }
+
+    /// Put the player in fight. Being attacked wakes the player up, interrupting the rest
+    fn start_fighting(&mut self) {
+        self.player.start_fighting();
+        self.rest = None;
+    }
+
+    /// Make noise which spreads through `noise` rooms from the player room.
+    /// The loudest noise made in the turn is kept
+    fn make_noise(&mut self, noise: u8) {
+        debug!("player made noise {}", noise);
+        self.noise = self.noise.max(noise);
+    }
+
+    /// Deal damage to the fighting enemy at `target`.
+    /// Defeats the enemy if its health reaches 0, otherwise reports whether the boss entered a new phase
+    fn damage_fighting_enemy(&mut self, target: usize, damage: Hp, effect: &mut Effect) {
+        let enemy = self.maze.fighting_enemy_mut(target).unwrap();
+        let phase = enemy.phase();
+        enemy.damage(damage);
+        self.stats.damage_inflicted += damage as u64;
+        debug!("new enemy HP: {}", enemy.health());
+        if enemy.health() == 0 {
+            self.defeat_fighting_enemy(target, effect);
+        } else if let Some(new_phase) = enemy.phase().filter(|x| Some(*x) != phase) {
+            debug!("boss entered phase {}", new_phase);
+            effect.message(Message::BossPhaseChanged(new_phase));
+        }
+    }
+
+    /// Remove the fighting enemy at `target` from the room and award experience.
+    /// The player goes back exploring once all the enemies in the room have been defeated.
+    /// Defeating Don Maze wins the game
+    fn defeat_fighting_enemy(&mut self, target: usize, effect: &mut Effect) {
+        debug!("enemy at {} defeated", target);
+        let enemy = self.maze.take_enemy(target).unwrap();
+        self.stats.enemies_killed += 1;
+        if self.maze.fighting_enemies().is_empty() {
+            self.player.start_exploring();
+        }
+        if matches!(enemy, Enemy::DonMaze(_)) {
+            debug!("don maze defeated; player has won");
+            effect.message(Message::DonMazeDefeated);
+            self.don_maze_defeated = true;
+            self.won = true;
+        } else {
+            effect.message(Message::EnemyDefeated);
+        }
+        if let Some(item) = enemy.loot(&mut rand::rng()) {
+            debug!("enemy dropped {:?}", item);
+            self.maze.drop_item(item);
+            effect.message(Message::EnemyDroppedLoot(item));
+        }
+        effect.sound(Sound::EnemyScream);
+        self.gain_experience(enemy.experience(), effect);
+    }
+
+    /// Award experience to player and report gained levels
+    fn gain_experience(&mut self, xp: u32, effect: &mut Effect) {
+        let levels_gained = self.player.gain_experience(xp, self.difficulty);
+        effect.message(Message::ExperienceGained(xp));
+        if levels_gained > 0 {
+            debug!("player reached level {}", self.player.level());
+            effect.message(Message::LevelUp(self.player.level()));
+        }
+    }
+
+    /// Apply per-turn status effects to the player and tick their duration
+    fn tick_status_effects(&mut self, effect: &mut Effect) {
+        if self.player.has_status_effect(StatusEffect::Poisoned) {
+            debug!("player is poisoned; dealing 1 HP");
+            self.player.damage(1);
+            self.stats.damage_suffered += 1;
+            effect.message(Message::PoisonDamage(1));
+            if self.player.is_dead() {
+                debug!("player is dead due to poison. Game over...");
+                effect.sound(Sound::PlayerDead);
+                effect.message(Message::PlayerDead);
+                return;
+            }
+        }
+        if self.player.has_status_effect(StatusEffect::Regenerating)
+            && self.player.health() < self.player.max_health()
+        {
+            debug!("player is regenerating; restoring 1 HP");
+            self.player.heal(1);
+            effect.message(Message::Regenerated(1));
+        }
+        for expired in self.player.tick_status_effects() {
+            if expired == StatusEffect::Asleep {
+                debug!("wake up!");
+                self.rest = None;
+                effect.message(Message::WakeUp);
+                effect.sound(Sound::WakeUp);
+            } else {
+                effect.message(Message::StatusEffectExpired(expired));
+            }
+        }
+    }
+
     /// Returns player's available actions for the current turn
     pub fn available_actions(&self) -> Vec<Action> {
         // game over
@@ -191,10 +425,283 @@ impl Session {
         match self.player.state() {
             PlayerState::Asleep => vec![Action::Sleep],
             PlayerState::Explore => self.available_exploring_actions(),
-            PlayerState::Fight => vec![
-                Action::Fight(FightAction::Fight),
-                Action::Fight(FightAction::Escape),
-            ],
+            PlayerState::Fight => self.available_fighting_actions(),
+        }
+    }
+
+    /// Returns available fighting actions
+    fn available_fighting_actions(&self) -> Vec<Action> {
+        let mut actions: Vec<Action> = (0..self.maze.fighting_enemies().len())
+            .map(|target| Action::Fight(FightAction::Fight(target)))
+            .collect();
+        actions.push(Action::Fight(FightAction::Defend));
+        actions.push(Action::Fight(FightAction::Escape));
+        for room in self.adjacent_rooms() {
+            actions.push(Action::Fight(FightAction::Retreat(room)));
+        }
+        let mut potions: Vec<Potion> = self
+            .player_inventory()
+            .items()
+            .into_iter()
+            .filter_map(|(item, _)| match item {
+                Item::Potion(potion) => Some(potion),
+                _ => None,
+            })
+            .collect();
+        potions.sort_by_key(|x| x.key());
+        if self.player_inventory().has(Item::Talisman) {
+            for target in 0..self.maze.fighting_enemies().len() {
+                actions.push(Action::Fight(FightAction::UseTalisman(target)));
+            }
+        }
+        for potion in potions {
+            for target in 0..self.maze.fighting_enemies().len() {
+                actions.push(Action::Fight(FightAction::Throw(potion, target)));
+            }
+        }
+        actions
+    }
+
+    /// Probability for the player to escape from the fight to a random room,
+    /// based on player's level, status effects and amulet.
+    /// Being hasted increases the probability, while being blinded halves it
+    pub fn escape_probability(&self) -> u8 {
+        let mut probability = ESCAPE_PROBABILITY + self.player.escape_bonus();
+        if self.player.has_status_effect(StatusEffect::Hasted) {
+            probability += HASTED_ESCAPE_BONUS;
+        }
+        if let Some(amulet) = self.player.equipment.amulet() {
+            probability += amulet.escape_bonus();
+        }
+        if self.player.has_status_effect(StatusEffect::Blinded) {
+            probability /= 2;
+        }
+        probability.min(100)
+    }
+
+    /// Probability for the player to retreat from the fight to the adjacent `room`.
+    /// Going back where the player came from is easier than heading into a side room,
+    /// while fast enemies are harder to leave behind; in group fights the fastest enemy counts
+    pub fn retreat_probability(&self, room: u32) -> u8 {
+        let direction_modifier: i16 = if Some(room) == self.last_room {
+            15
+        } else {
+            match room_resolver::resolve_room_direction(room, self) {
+                Direction::Ahead => 0,
+                Direction::Left | Direction::Right => -10,
+            }
+        };
+        let enemy_modifier = self
+            .get_fighting_enemies()
+            .iter()
+            .map(|x| x.retreat_modifier())
+            .min()
+            .unwrap_or_default();
+        (self.escape_probability() as i16 + direction_modifier + enemy_modifier).clamp(5, 95) as u8
+    }
+
+    /// Probability for the player to hit the fighting enemy at `target`,
+    /// based on weapon accuracy, status effects and enemy evasion
+    pub fn hit_probability(&self, target: usize) -> u8 {
+        let mut probability = self
+            .player
+            .equipment
+            .weapon()
+            .map(|x| x.kind.accuracy())
+            .unwrap_or(BARE_HANDED_ACCURACY) as i16;
+        if self.player.has_status_effect(StatusEffect::Hasted) {
+            probability += HASTED_ACCURACY_BONUS as i16;
+        }
+        if self.player.has_status_effect(StatusEffect::Blinded) {
+            probability -= BLINDED_ACCURACY_MALUS as i16;
+        }
+        if let Some(enemy) = self.get_fighting_enemies().get(target) {
+            probability -= enemy.evasion() as i16;
+        }
+        probability.clamp(5, 100) as u8
+    }
+
+    /// Probability for the player to land a critical hit, dealing double damage
+    pub fn critical_probability(&self) -> u8 {
+        CRITICAL_PROBABILITY
+            + self
+                .player
+                .equipment
+                .weapon()
+                .map(|x| x.kind.critical_bonus())
+                .unwrap_or_default()
+    }
+
+    /// Get the npc in the player's room, if any
+    pub fn npc(&self) -> Option<&Npc> {
+        self.maze.npc()
+    }
+
+    /// Get the merchant in the player's room, if any
+    pub fn merchant(&self) -> Option<&Merchant> {
+        match self.maze.npc() {
+            Some(Npc::Merchant(merchant)) => Some(merchant),
+            _ => None,
+        }
+    }
+
+    fn merchant_mut(&mut self) -> Option<&mut Merchant> {
+        match self.maze.npc_mut() {
+            Some(Npc::Merchant(merchant)) => Some(merchant),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the player can trade with the merchant
+    pub fn can_trade(&self) -> bool {
+        self.player.state() == PlayerState::Explore && self.merchant().is_some()
+    }
+
+    /// Get the dialogue of the npc in the player's room, if the player can talk to it
+    pub fn dialogue(&self) -> Option<&'static Dialogue> {
+        self.maze
+            .npc()
+            .filter(|_| self.player.state() == PlayerState::Explore)
+            .and_then(|x| x.dialogue())
+    }
+
+    /// Returns whether the player can talk to the npc in the room
+    pub fn can_talk(&self) -> bool {
+        self.dialogue().is_some()
+    }
+
+    /// Distance in rooms from the player to the maze key, if it's still in the maze
+    pub fn maze_key_distance(&self) -> Option<u8> {
+        self.maze
+            .distance_to(|room| room.contains_item(Item::MazeKey))
+    }
+
+    /// Distance in rooms from the player to the exit
+    pub fn exit_distance(&self) -> Option<u8> {
+        self.maze.distance_to(|room| room.is_exit())
+    }
+
+    /// Price in gold the merchant asks for `item`, if it can be traded.
+    /// Prices change with the maze seed and the difficulty
+    pub fn buy_price(&self, item: Item) -> Option<u16> {
+        let value = item.value()? as u32;
+        let mut rng: Pcg64 =
+            Seeder::from(format!("{}:{}", self.maze.seed(), item.key())).into_rng();
+        let seed_factor: u32 = rng.random_range(80..=120);
+        let price = value * seed_factor * self.difficulty.price_factor() as u32 / 10_000;
+        Some(price.max(1) as u16)
+    }
+
+    /// Price in gold the merchant pays for `item`, if it can be traded
+    pub fn sell_price(&self, item: Item) -> Option<u16> {
+        self.buy_price(item).map(|price| (price / 2).max(1))
+    }
+
+    /// Returns the trades the player can make with the merchant in the room:
+    /// the items in stock the player can afford, if there's room in the inventory, and the items in the inventory the merchant is interested in
+    pub fn available_trades(&self) -> Vec<Action> {
+        let Some(merchant) = self.merchant().filter(|_| self.can_trade()) else {
+            return vec![];
+        };
+        let mut buy: Vec<Item> = merchant
+            .stock()
+            .iter()
+            .copied()
+            .filter(|x| self.player.inventory.can_add(*x).is_ok())
+            .filter(|x| {
+                self.buy_price(*x)
+                    .is_some_and(|price| price as u32 <= self.player.gold())
+            })
+            .collect();
+        buy.sort_by_key(|x| x.key());
+        buy.dedup();
+        let mut sell: Vec<Item> = self
+            .player
+            .inventory
+            .items()
+            .into_iter()
+            .map(|(item, _)| item)
+            .filter(|x| x.value().is_some())
+            .collect();
+        sell.sort_by_key(|x| x.key());
+        buy.into_iter()
+            .map(|x| Action::Explore(ExploreAction::Buy(x)))
+            .chain(
+                sell.into_iter()
+                    .map(|x| Action::Explore(ExploreAction::Sell(x))),
+            )
+            .collect()
+    }
+
+    /// Get what the player knows about the potions in this run
+    pub fn potion_knowledge(&self) -> PotionKnowledge<'_> {
+        PotionKnowledge::new(
+            self.maze.seed(),
+            &self.identified_potions,
+            self.player.inventory.has(Item::AlchemyBook),
+        )
+    }
+
+    /// Identify `potion` for the rest of the run
+    fn identify_potion(&mut self, potion: Potion) {
+        if self.identified_potions.insert(potion) {
+            debug!("identified potion {:?}", potion);
+        }
+    }
+
+    /// Returns whether the player can drop items from the inventory
+    pub fn can_drop_items(&self) -> bool {
+        self.player.state() == PlayerState::Explore
+    }
+
+    /// Returns whether the player can brew potions
+    pub fn can_brew(&self) -> bool {
+        self.player.state() == PlayerState::Explore && self.player.inventory.has(Item::AlchemyBook)
+    }
+
+    /// Alchemy recipes discovered by the player
+    pub fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+
+    /// Get the potion brewed out of `first` and `second`, if the player has already discovered the recipe
+    pub fn known_recipe(&self, first: Item, second: Item) -> Option<Potion> {
+        self.recipes
+            .iter()
+            .find(|x| x.uses(first, second))
+            .map(|x| x.potion)
+    }
+
+    /// Returns the brews the player can try with the ingredients in the inventory
+    pub fn available_brews(&self) -> Vec<Action> {
+        if !self.can_brew() {
+            return vec![];
+        }
+        let mut ingredients: Vec<(Item, u8)> = self
+            .player
+            .inventory
+            .items()
+            .into_iter()
+            .filter(|(item, _)| item.is_ingredient())
+            .collect();
+        ingredients.sort_by_key(|(item, _)| item.key());
+        let mut brews = Vec::new();
+        for (i, (first, qty)) in ingredients.iter().enumerate() {
+            if *qty > 1 {
+                brews.push(Action::Brew(*first, *first));
+            }
+            for (second, _) in ingredients.iter().skip(i + 1) {
+                brews.push(Action::Brew(*first, *second));
+            }
+        }
+        brews
+    }
+
+    /// Put `recipe` among the discovered ones
+    fn discover_recipe(&mut self, recipe: Recipe) {
+        if !self.recipes.contains(&recipe) {
+            debug!("discovered recipe {:?}", recipe);
+            self.recipes.push(recipe);
         }
     }
 
@@ -203,7 +710,19 @@ impl Session {
     fn available_exploring_actions(&self) -> Vec<Action> {
         let mut actions = Vec::with_capacity(6);
         if self.maze.has_item() {
-            actions.push(Action::Explore(ExploreAction::CollectItem));
+            match self.chest() {
+                Some(Chest::Locked { .. }) => {
+                    if self.player_inventory().has(Item::Lockpick) {
+                        actions.push(Action::Explore(ExploreAction::PickLock));
+                    }
+                    actions.push(Action::Explore(ExploreAction::ForceChest));
+                }
+                Some(_) => {
+                    actions.push(Action::Explore(ExploreAction::CollectItem));
+                    actions.push(Action::Explore(ExploreAction::InspectChest));
+                }
+                None => actions.push(Action::Explore(ExploreAction::CollectItem)),
+            }
         }
         // push adjacent rooms, except last room
         for (node, _) in self
@@ -217,6 +736,27 @@ impl Session {
         if self.is_previous_room_set() {
             actions.push(Action::Explore(ExploreAction::GoToPreviousRoom));
         }
+        // sneak into adjacent rooms; previous room last
+        let mut sneak_rooms: Vec<u32> = self
+            .maze
+            .adjacent_rooms(self.maze.player)
+            .iter()
+            .map(|(node, _)| *node)
+            .collect();
+        sneak_rooms.sort_by_key(|node| Some(*node) == self.last_room);
+        actions.extend(
+            sneak_rooms
+                .into_iter()
+                .map(|node| Action::Explore(ExploreAction::Sneak(node))),
+        );
+        // rest only when wounded
+        if self.player.health() < self.player.max_health() {
+            actions.extend(
+                REST_TURNS
+                    .into_iter()
+                    .map(|turns| Action::Explore(ExploreAction::Rest(turns))),
+            );
+        }
 
         actions
     }
@@ -235,12 +775,30 @@ impl Session {
     pub fn mock_with_maze(maze: Maze) -> Self {
         Self {
             maze,
+            difficulty: Difficulty::default(),
             last_room: None,
             player: Player::default(),
             stats: Stats::default(),
-            version: Version::V010,
+            version: Version::CURRENT,
+            migrated_from: None,
             visited_rooms: HashSet::default(),
             won: false,
+            don_maze_defeated: false,
+            defending: false,
+            enemy_asleep: None,
+            noise: 0,
+            rest: None,
+            recipes: Vec::default(),
+            identified_potions: HashSet::default(),
+        }
+    }
+
+    /// Remove the enemies from every room of the maze
+    #[cfg(test)]
+    pub fn clear_enemies(&mut self) {
+        let rooms: Vec<u32> = self.maze.rooms().iter().map(|(id, _)| *id).collect();
+        for room in rooms {
+            self.maze.room_mut(room).unwrap().enemies.clear();
         }
     }
 }
@@ -251,11 +809,21 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::game::entity::{
+        Artifact, Daemon, DonMaze, EquipmentSlot, Herb, LightLevel, Merchant, Mimic, Relic, Shadow,
+        Speaker, Weapon, WeaponKind, MAX_TORCH_FUEL,
+    };
+    use crate::game::inventory::INVENTORY_CAPACITY;
+    use crate::game::maze::MAX_ENEMIES_IN_ROOM;
+    use crate::utils::room_resolver;
 
     #[test]
-    fn should_tell_whether_version_is_compatible() {
+    fn should_load_session_from_save() {
         let session = Session::mock();
-        assert!(session.is_version_compatible());
+        let save = serde_json::to_value(&session).unwrap();
+        let loaded = Session::from_save(save).unwrap();
+        assert_eq!(loaded, session);
+        assert_eq!(loaded.migrated_from(), None);
     }
 
     #[test]
@@ -333,6 +901,8 @@ mod test {
             vec![
                 Action::Explore(ExploreAction::ChangeRoom(2)),
                 Action::Explore(ExploreAction::ChangeRoom(1)),
+                Action::Explore(ExploreAction::Sneak(2)),
+                Action::Explore(ExploreAction::Sneak(1)),
             ]
         );
         // go to room 1
@@ -345,6 +915,9 @@ mod test {
                 Action::Explore(ExploreAction::ChangeRoom(9)),
                 Action::Explore(ExploreAction::ChangeRoom(3)),
                 Action::Explore(ExploreAction::GoToPreviousRoom),
+                Action::Explore(ExploreAction::Sneak(9)),
+                Action::Explore(ExploreAction::Sneak(3)),
+                Action::Explore(ExploreAction::Sneak(0)),
             ]
         );
         // set asleep
@@ -352,17 +925,48 @@ mod test {
         assert_eq!(session.available_actions(), vec![Action::Sleep]);
         // start fight
         session.maze.player = 2;
-        session.player.decr_sleep_counter();
         session.player.start_fighting();
         assert_eq!(
             session.available_actions(),
             vec![
-                Action::Fight(FightAction::Fight),
+                Action::Fight(FightAction::Fight(0)),
+                Action::Fight(FightAction::Defend),
                 Action::Fight(FightAction::Escape),
+                Action::Fight(FightAction::Retreat(4)),
+                Action::Fight(FightAction::Retreat(0)),
+            ]
+        );
+        // potions can be thrown
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Vinegar))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Mead))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Mead))
+            .unwrap();
+        assert_eq!(
+            &session.available_actions()[5..],
+            &[
+                Action::Fight(FightAction::Throw(Potion::Mead, 0)),
+                Action::Fight(FightAction::Throw(Potion::Vinegar, 0)),
             ]
         );
+        session
+            .player
+            .inventory
+            .consume(Item::Potion(Potion::Vinegar));
+        session.player.inventory.consume(Item::Potion(Potion::Mead));
+        session.player.inventory.consume(Item::Potion(Potion::Mead));
         // win
-        session.player.inventory.add(Item::MazeKey);
+        session.player.inventory.add(Item::MazeKey).unwrap();
         session.player.start_exploring();
         session.maze.player = 7;
         assert_eq!(session.is_exit(), true);
@@ -380,13 +984,976 @@ mod test {
         assert!(!session.can_use_items());
     }
 
+    #[test]
+    fn should_tick_status_effects() {
+        let mut session = Session::mock();
+        session
+            .player
+            .apply_status_effect(StatusEffect::Poisoned, 1);
+        session
+            .player
+            .apply_status_effect(StatusEffect::Regenerating, 2);
+        let mut effect = Effect::default();
+        session.tick_status_effects(&mut effect);
+        assert_eq!(
+            effect.messages,
+            vec![
+                Message::PoisonDamage(1),
+                Message::Regenerated(1),
+                Message::StatusEffectExpired(StatusEffect::Poisoned),
+            ]
+        );
+        assert_eq!(session.player.health(), session.player.max_health());
+        assert_eq!(session.stats.damage_suffered, 1);
+        // wake up
+        session.player.start_sleeping(1);
+        let mut effect = Effect::default();
+        session.tick_status_effects(&mut effect);
+        assert_eq!(
+            effect.messages,
+            vec![
+                Message::StatusEffectExpired(StatusEffect::Regenerating),
+                Message::WakeUp,
+            ]
+        );
+        assert_eq!(session.player.state(), PlayerState::Explore);
+    }
+
+    #[test]
+    fn should_die_of_poison() {
+        let mut session = Session::mock();
+        session.player.damage(session.player.health() - 1);
+        session
+            .player
+            .apply_status_effect(StatusEffect::Poisoned, 3);
+        let effect = session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        assert_eq!(
+            effect.messages,
+            vec![Message::PoisonDamage(1), Message::PlayerDead]
+        );
+        assert!(session.game_over());
+        assert_eq!(session.player_room(), 0);
+    }
+
+    #[test]
+    fn should_equip_and_unequip_items() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        let dagger = Item::Weapon(Weapon::new(WeaponKind::Dagger));
+        let sword = Item::Weapon(Weapon::new(WeaponKind::Sword));
+        session.player.inventory.add(dagger).unwrap();
+        session.player.inventory.add(sword).unwrap();
+        let effect = session.play_turn(Action::UseItem(dagger));
+        assert_eq!(effect.messages[0], Message::ItemEquipped(dagger));
+        assert!(!session.player_inventory().has(dagger));
+        // replace weapon
+        session.play_turn(Action::UseItem(sword));
+        assert!(session.player_inventory().has(dagger));
+        assert!(!session.player_inventory().has(sword));
+        assert_eq!(
+            session.player().equipment.weapon(),
+            Some(&Weapon::new(WeaponKind::Sword))
+        );
+        // unequip
+        let effect = session.play_turn(Action::UnequipItem(EquipmentSlot::Weapon));
+        assert_eq!(effect.messages[0], Message::ItemUnequipped(sword));
+        assert!(session.player_inventory().has(sword));
+        assert_eq!(session.player().equipment.weapon(), None);
+    }
+
+    #[test]
+    fn should_gain_experience_on_kill() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.maze.room_mut(2).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(1))];
+        session.player.start_fighting();
+        let messages = fight_until(&mut session, 0, |s| s.get_fighting_enemy().is_none());
+        assert!(messages.contains(&Message::EnemyDefeated));
+        assert!(messages.contains(&Message::ExperienceGained(7)));
+        assert_eq!(session.player().experience(), 7);
+        assert_eq!(session.player().level(), 1);
+    }
+
+    #[test]
+    fn should_calc_escape_and_retreat_probability() {
+        let mut session = Session::mock();
+        session.last_room = Some(0);
+        session.maze.player = 2;
+        session.player.start_fighting();
+        assert_eq!(session.escape_probability(), 50);
+        // daemon in room 2
+        assert_eq!(session.retreat_probability(0), 65);
+        assert_eq!(session.retreat_probability(4), 50);
+        session.player.apply_status_effect(StatusEffect::Blinded, 3);
+        assert_eq!(session.escape_probability(), 25);
+        assert_eq!(session.retreat_probability(0), 40);
+    }
+
+    #[test]
+    fn should_fight_group_of_enemies() {
+        let mut session = Session::mock();
+        session.last_room = Some(0);
+        session.maze.player = 2;
+        session.maze.room_mut(2).unwrap().enemies =
+            vec![Enemy::Daemon(Daemon::new(1)), Enemy::Shadow(Shadow::new(1))];
+        session.player.start_fighting();
+        let actions = session.available_actions();
+        assert_eq!(actions[0], Action::Fight(FightAction::Fight(0)));
+        assert_eq!(actions[1], Action::Fight(FightAction::Fight(1)));
+        assert_eq!(actions[2], Action::Fight(FightAction::Defend));
+        // the shadow is the hardest to leave behind
+        assert_eq!(session.retreat_probability(0), 50);
+        // bare-handed hits always deal at least 1 HP
+        let messages = fight_until(&mut session, 1, |s| s.get_fighting_enemies().len() == 1);
+        assert!(messages.contains(&Message::EnemyDefeated));
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        assert_eq!(
+            session.get_fighting_enemies(),
+            &[Enemy::Daemon(Daemon::new(1))]
+        );
+        fight_until(&mut session, 0, |s| s.get_fighting_enemies().is_empty());
+        assert_eq!(session.player().state(), PlayerState::Explore);
+    }
+
+    #[test]
+    fn should_be_attacked_by_every_enemy_in_room() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.maze.room_mut(2).unwrap().enemies = vec![Enemy::Shadow(Shadow::new(5)); 3];
+        session.player.start_fighting();
+        let effect = session.play_turn(Action::Fight(FightAction::Defend));
+        assert_eq!(
+            effect
+                .messages
+                .iter()
+                .filter(|x| matches!(x, Message::DamageSuffered(_, _) | Message::EnemyMissed))
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn should_calc_hit_and_critical_probability() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.player.start_fighting();
+        // daemon in room 2 has 5% evasion
+        assert_eq!(session.hit_probability(0), 85);
+        assert_eq!(session.critical_probability(), 10);
+        session
+            .player
+            .equipment
+            .equip(Item::Weapon(Weapon::new(WeaponKind::Dagger)));
+        assert_eq!(session.hit_probability(0), 95);
+        assert_eq!(session.critical_probability(), 25);
+        session.player.apply_status_effect(StatusEffect::Hasted, 3);
+        assert_eq!(session.hit_probability(0), 100);
+        session.player.apply_status_effect(StatusEffect::Blinded, 3);
+        assert_eq!(session.hit_probability(0), 75);
+    }
+
+    #[test]
+    fn should_burn_and_refuel_torch() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.clear_enemies();
+        session.player.inventory.add(Item::TorchOil).unwrap();
+        session.player.inventory.add(Item::Sonar).unwrap();
+        while session.player().torch.fuel() > 51 {
+            session.player.torch.burn();
+        }
+        // torch gets dim, then it's refueled
+        let effect = session.play_turn(Action::UseItem(Item::TorchOil));
+        assert_eq!(effect.messages[0], Message::LightChanged(LightLevel::Dim));
+        assert!(effect.messages.contains(&Message::ItemUsed(Item::TorchOil)));
+        assert_eq!(session.player().torch.fuel(), 130);
+        assert_eq!(session.player().torch.light_level(), LightLevel::Bright);
+        // sonar doesn't work in the dark
+        while session.player().torch.fuel() > 0 {
+            session.player.torch.burn();
+        }
+        let effect = session.play_turn(Action::UseItem(Item::Sonar));
+        assert_eq!(effect.messages[0], Message::SonarInDarkness);
+        assert!(session.player_inventory().has(Item::Sonar));
+    }
+
+    #[test]
+    fn should_drop_items_and_respect_inventory_capacity() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session.player.inventory.add(Item::Sonar).unwrap();
+        let effect = session.play_turn(Action::DropItem(Item::Sonar));
+        assert_eq!(effect.messages[0], Message::ItemDropped(Item::Sonar));
+        assert!(!session.player_inventory().has(Item::Sonar));
+        assert_eq!(session.get_item_in_the_room(), Some(&Item::Sonar));
+        // fill the inventory; the item can't be collected anymore
+        for _ in 0..INVENTORY_CAPACITY {
+            session
+                .player
+                .inventory
+                .add(Item::Potion(Potion::Mead))
+                .unwrap();
+        }
+        let effect = session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        assert_eq!(effect.messages[0], Message::InventoryFull);
+        assert_eq!(session.get_item_in_the_room(), Some(&Item::Sonar));
+        // drop a potion to make room; the item goes on the pile
+        session.play_turn(Action::DropItem(Item::Potion(Potion::Mead)));
+        assert!(session
+            .maze
+            .room(0)
+            .unwrap()
+            .contains_item(Item::Potion(Potion::Mead)));
+        session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        assert!(session.player_inventory().has(Item::Sonar));
+        assert!(session.player_inventory().is_full());
+        assert_eq!(
+            session.get_item_in_the_room(),
+            Some(&Item::Potion(Potion::Mead))
+        );
+    }
+
+    #[test]
+    fn should_apply_artifact_effects() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        let escape_probability = session.escape_probability();
+        session
+            .player
+            .inventory
+            .add(Item::Artifact(Artifact::RabbitFoot))
+            .unwrap();
+        assert_eq!(session.escape_probability(), escape_probability + 10);
+        // the troll heart regenerates the player every 20 turns
+        session
+            .player
+            .inventory
+            .add(Item::Artifact(Artifact::TrollHeart))
+            .unwrap();
+        session.player.damage(2);
+        session.stats.turn = 18;
+        let effect = session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        assert!(!effect.messages.contains(&Message::Regenerated(1)));
+        let effect = session.play_turn(Action::Explore(ExploreAction::GoToPreviousRoom));
+        assert!(effect.messages.contains(&Message::Regenerated(1)));
+        assert_eq!(session.player().health(), session.player().max_health() - 1);
+        // the owl's eye senses the enemies next to the player
+        let daemon = Enemy::Daemon(Daemon::new(4));
+        session.maze.room_mut(3).unwrap().enemies.push(daemon);
+        session
+            .player
+            .inventory
+            .add(Item::Artifact(Artifact::OwlEye))
+            .unwrap();
+        let effect = session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        assert!(effect.messages.contains(&Message::EnemySensed(3, daemon)));
+    }
+
+    #[test]
+    fn should_rest() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.clear_enemies();
+        // can't rest while healthy
+        assert!(!session
+            .available_actions()
+            .contains(&Action::Explore(ExploreAction::Rest(5))));
+        session.player.damage(3);
+        assert!(session
+            .available_actions()
+            .contains(&Action::Explore(ExploreAction::Rest(5))));
+        let effect = session.play_turn(Action::Explore(ExploreAction::Rest(5)));
+        assert_eq!(effect.messages[0], Message::RestStarted(5));
+        assert_eq!(session.available_actions(), vec![Action::Sleep]);
+        // 1 HP every 2 turns
+        for _ in 0..5 {
+            session.play_turn(Action::Sleep);
+        }
+        assert_eq!(session.player().state(), PlayerState::Explore);
+        assert_eq!(session.player().health(), session.player().max_health() - 1);
+        assert_eq!(session.stats().times_rested, 1);
+        assert_eq!(session.stats().health_restored_resting, 2);
+        assert_eq!(session.rest, None);
+        // enemies catch the player resting
+        session.play_turn(Action::Explore(ExploreAction::Rest(10)));
+        session
+            .maze
+            .room_mut(0)
+            .unwrap()
+            .enemies
+            .push(Enemy::Daemon(Daemon::new(4)));
+        let effect = session.play_turn(Action::Sleep);
+        assert!(effect.messages.contains(&Message::SurprisedWhileResting));
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        assert_eq!(session.rest, None);
+        // every enemy entering the room catches the player resting, even identical ones
+        session.clear_enemies();
+        session.player.start_exploring();
+        session.player.heal(session.player().max_health());
+        session.player.damage(3);
+        session.play_turn(Action::Explore(ExploreAction::Rest(10)));
+        session.maze.room_mut(0).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(4)); 2];
+        let effect = session.play_turn(Action::Sleep);
+        assert_eq!(
+            effect
+                .messages
+                .iter()
+                .filter(|x| **x == Message::SurprisedWhileResting)
+                .count(),
+            2
+        );
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        // the daemon joins the resting player; mimics don't wander, so it has nowhere else to go
+        session.clear_enemies();
+        session.player.start_exploring();
+        session.player.heal(session.player().max_health());
+        session.player.damage(3);
+        session.maze.room_mut(1).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(4))];
+        for room in [3, 9] {
+            session.maze.room_mut(room).unwrap().enemies =
+                vec![Enemy::Mimic(Mimic::new(4)); MAX_ENEMIES_IN_ROOM];
+        }
+        let effect = session.play_turn(Action::Explore(ExploreAction::Rest(10)));
+        assert!(effect.messages.contains(&Message::SurprisedWhileResting));
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        // once woken up and escaped, the next fights are not surprises
+        let mut messages = Vec::new();
+        while !messages
+            .iter()
+            .any(|x| matches!(x, Message::EscapeSucceeded(_)))
+        {
+            session.player.heal(session.player().max_health());
+            messages.extend(
+                session
+                    .play_turn(Action::Fight(FightAction::Escape))
+                    .messages,
+            );
+        }
+        // NOTE: the daemon may follow the player
+        session.clear_enemies();
+        session.player.start_exploring();
+        let room = session.player_room();
+        session
+            .maze
+            .room_mut(room)
+            .unwrap()
+            .enemies
+            .push(Enemy::Shadow(Shadow::new(4)));
+        messages.extend(session.play_turn(Action::SaveGame).messages);
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        assert!(!messages.contains(&Message::SurprisedWhileResting));
+    }
+
+    #[test]
+    fn should_open_chests() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.clear_enemies();
+        // a locked chest must be forced or picked
+        let room = session.maze.room_mut(0).unwrap();
+        room.drop_item(Item::Sonar);
+        room.chest = Some(Chest::Locked { forcing_turns: 2 });
+        assert!(!session
+            .available_actions()
+            .contains(&Action::Explore(ExploreAction::CollectItem)));
+        assert!(!session
+            .available_actions()
+            .contains(&Action::Explore(ExploreAction::PickLock)));
+        let effect = session.play_turn(Action::Explore(ExploreAction::ForceChest));
+        assert_eq!(effect.messages[0], Message::ChestForced(1));
+        assert_eq!(session.chest(), Some(Chest::Locked { forcing_turns: 1 }));
+        let effect = session.play_turn(Action::Explore(ExploreAction::ForceChest));
+        assert_eq!(effect.messages[0], Message::ChestForced(0));
+        assert_eq!(session.chest(), None);
+        session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        assert!(session.player_inventory().has(Item::Sonar));
+        // pick the lock
+        let room = session.maze.room_mut(0).unwrap();
+        room.drop_item(Item::Talisman);
+        room.chest = Some(Chest::Locked { forcing_turns: 2 });
+        session.player.inventory.add(Item::Lockpick).unwrap();
+        assert!(session
+            .available_actions()
+            .contains(&Action::Explore(ExploreAction::PickLock)));
+        let effect = session.play_turn(Action::Explore(ExploreAction::PickLock));
+        assert_eq!(effect.messages[0], Message::LockPicked);
+        assert!(!session.player_inventory().has(Item::Lockpick));
+        assert_eq!(session.chest(), None);
+        session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        // trapped chest springs when collecting
+        let room = session.maze.room_mut(0).unwrap();
+        room.drop_item(Item::TorchOil);
+        room.chest = Some(Chest::Trapped { damage: 2 });
+        let effect = session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        assert_eq!(effect.messages[0], Message::TrapSprung(2));
+        assert_eq!(session.player().health(), session.player().max_health() - 2);
+        assert!(session.player_inventory().has(Item::TorchOil));
+        // inspecting disarms the trap
+        let room = session.maze.room_mut(0).unwrap();
+        room.drop_item(Item::Gold);
+        room.chest = Some(Chest::Trapped { damage: 2 });
+        let effect = session.play_turn(Action::Explore(ExploreAction::InspectChest));
+        assert_eq!(effect.messages[0], Message::TrapDisarmed);
+        assert_eq!(session.chest(), None);
+        assert_eq!(session.get_item_in_the_room(), Some(&Item::Gold));
+        session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        // the mimic attacks the player
+        let room = session.maze.room_mut(0).unwrap();
+        room.drop_item(Item::Talisman);
+        room.chest = Some(Chest::Mimic);
+        let effect = session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        assert_eq!(effect.messages[0], Message::MimicRevealed);
+        assert_eq!(session.chest(), None);
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        assert!(matches!(session.get_fighting_enemies(), [Enemy::Mimic(_)]));
+        assert_eq!(session.get_item_in_the_room(), Some(&Item::Talisman));
+    }
+
+    #[test]
+    fn should_teleport_into_room_with_four_exits() {
+        let mut session = Session::mock();
+        session.clear_enemies();
+        session.player.inventory.add(Item::TeleportStone).unwrap();
+        // room 4 has four exits
+        session.visited_rooms = HashSet::from([4]);
+        session.play_turn(Action::UseItem(Item::TeleportStone));
+        assert_eq!(session.player_room(), 4);
+        assert_eq!(session.adjacent_rooms().len(), 4);
+        assert_eq!(session.get_last_room(), Some(2));
+        // every room can be resolved to a direction
+        for action in session.available_actions() {
+            if let Action::Explore(ExploreAction::ChangeRoom(room)) = action {
+                room_resolver::resolve_room_direction(room, &session);
+            }
+        }
+    }
+
+    #[test]
+    fn should_use_exploration_items() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.clear_enemies();
+        session.player.inventory.add(Item::Compass).unwrap();
+        session.player.inventory.add(Item::MapScroll).unwrap();
+        session.player.inventory.add(Item::TeleportStone).unwrap();
+        session.player.inventory.add(Item::Rope).unwrap();
+        // compass points towards the exit and it's not consumed
+        let effect = session.play_turn(Action::UseItem(Item::Compass));
+        assert!(effect.messages.contains(&Message::CompassPoint(Some(2))));
+        assert!(session.player_inventory().has(Item::Compass));
+        // map reveals the rooms beyond the adjacent ones
+        let effect = session.play_turn(Action::UseItem(Item::MapScroll));
+        assert!(effect.messages.contains(&Message::MapRevealed(1, 3, false)));
+        assert!(effect.messages.contains(&Message::MapRevealed(2, 5, true)));
+        assert!(!session.player_inventory().has(Item::MapScroll));
+        for room in [1, 2, 3, 4, 5, 6, 7, 9] {
+            assert!(session.room_visited(room));
+        }
+        assert!(!session.room_visited(8));
+        session.visited_rooms.clear();
+        // there's no visited room to teleport to and we're already at the entrance
+        let effect = session.play_turn(Action::UseItem(Item::TeleportStone));
+        assert!(effect
+            .messages
+            .contains(&Message::ItemUseless(Item::TeleportStone)));
+        assert!(session.player_inventory().has(Item::TeleportStone));
+        let effect = session.play_turn(Action::UseItem(Item::Rope));
+        assert!(effect.messages.contains(&Message::ItemUseless(Item::Rope)));
+        assert!(session.player_inventory().has(Item::Rope));
+        // teleport to the only visited room
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(2)));
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(4)));
+        session.visited_rooms.remove(&2);
+        session.play_turn(Action::UseItem(Item::TeleportStone));
+        assert_eq!(session.player_room(), 0);
+        assert_eq!(session.get_last_room(), Some(1));
+        assert!(session.room_visited(4));
+        assert!(!session.player_inventory().has(Item::TeleportStone));
+        // walk back to the entrance along the explored rooms
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(2)));
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(4)));
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(7)));
+        session.play_turn(Action::UseItem(Item::Rope));
+        assert_eq!(session.player_room(), 0);
+        assert_eq!(session.get_last_room(), Some(2));
+        assert!(session.room_visited(7));
+        assert!(!session.player_inventory().has(Item::Rope));
+        // enemies on the way stop the player
+        session.player.inventory.add(Item::Rope).unwrap();
+        session.maze.player = 7;
+        session.maze.room_mut(4).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(4))];
+        session.play_turn(Action::UseItem(Item::Rope));
+        assert_eq!(session.player_room(), 4);
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        session.maze.room_mut(4).unwrap().enemies.clear();
+        session.player.start_exploring();
+        // the compass spins at the exit
+        session.maze.player = 7;
+        let effect = session.play_turn(Action::UseItem(Item::Compass));
+        assert!(effect.messages.contains(&Message::CompassPoint(None)));
+    }
+
+    #[test]
+    fn should_attract_enemies_with_noise() {
+        let mut session = Session::mock();
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session
+            .maze
+            .room_mut(4)
+            .unwrap()
+            .enemies
+            .push(Enemy::Daemon(Daemon::new(4)));
+        // steps are heard two rooms away; the daemon joins the player
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(2)));
+        assert_eq!(session.noise(), STEPS_NOISE);
+        assert_eq!(session.player().state(), PlayerState::Fight);
+        assert_eq!(session.get_fighting_enemies().len(), 1);
+        // fighting is loud
+        session.play_turn(Action::Fight(FightAction::Defend));
+        assert_eq!(session.noise(), MAX_NOISE);
+    }
+
+    #[test]
+    fn should_sneak() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        let effect = session.play_turn(Action::Explore(ExploreAction::Sneak(1)));
+        assert_eq!(effect.messages.len(), 1);
+        assert_eq!(session.player_room(), 1);
+        assert_eq!(session.noise(), 1);
+        // sneaking takes two turns
+        assert_eq!(session.stats().turn, 2);
+        assert_eq!(session.player().torch.fuel(), MAX_TORCH_FUEL - 2);
+    }
+
+    #[test]
+    fn should_meet_and_trade_with_merchant() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session.maze.room_mut(1).unwrap().item = Some(Item::Gold);
+        session.maze.room_mut(1).unwrap().npc = Some(Npc::Merchant(Merchant::new(vec![
+            Item::Sonar,
+            Item::Potion(Potion::Red),
+        ])));
+        assert!(!session.can_trade());
+        let effect = session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        assert!(effect
+            .messages
+            .iter()
+            .any(|x| matches!(x, Message::NpcMet(Npc::Merchant(_)))));
+        assert!(session.can_trade());
+        assert!(!session.can_talk());
+        // collect gold
+        let effect = session.play_turn(Action::Explore(ExploreAction::CollectItem));
+        let gold = session.player().gold();
+        assert_eq!(effect.messages[0], Message::GoldCollected(gold));
+        assert!((5..=20).contains(&gold));
+        // buy
+        let price = session.buy_price(Item::Sonar).unwrap();
+        session.player.earn_gold(price as u32);
+        assert!(session
+            .available_trades()
+            .contains(&Action::Explore(ExploreAction::Buy(Item::Sonar))));
+        let effect = session.play_turn(Action::Explore(ExploreAction::Buy(Item::Sonar)));
+        assert_eq!(effect.messages[0], Message::ItemBought(Item::Sonar, price));
+        assert_eq!(session.player().gold(), gold);
+        assert!(session.player_inventory().has(Item::Sonar));
+        assert!(!session.merchant().unwrap().has(Item::Sonar));
+        // sell
+        let price = session.sell_price(Item::Sonar).unwrap();
+        let effect = session.play_turn(Action::Explore(ExploreAction::Sell(Item::Sonar)));
+        assert_eq!(effect.messages[0], Message::ItemSold(Item::Sonar, price));
+        assert_eq!(session.player().gold(), gold + price as u32);
+        assert!(!session.player_inventory().has(Item::Sonar));
+        assert!(session.merchant().unwrap().has(Item::Sonar));
+        // there's no room in the inventory for the bought item
+        for _ in 0..INVENTORY_CAPACITY {
+            session
+                .player
+                .inventory
+                .add(Item::Potion(Potion::Mead))
+                .unwrap();
+        }
+        let gold = session.player().gold();
+        session.player.earn_gold(100);
+        assert!(!session
+            .available_trades()
+            .contains(&Action::Explore(ExploreAction::Buy(Item::Sonar))));
+        let effect = session.play_turn(Action::Explore(ExploreAction::Buy(Item::Sonar)));
+        assert_eq!(effect.messages[0], Message::InventoryFull);
+        assert_eq!(session.player().gold(), gold + 100);
+        assert!(session.merchant().unwrap().has(Item::Sonar));
+    }
+
+    #[test]
+    fn should_talk_to_npcs() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session.maze.room_mut(1).unwrap().npc = Some(Npc::Prisoner(Speaker::default()));
+        assert!(!session.can_talk());
+        let effect = session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        assert!(effect
+            .messages
+            .contains(&Message::NpcMet(Npc::Prisoner(Speaker::default()))));
+        assert!(session.can_talk());
+        assert!(!session.can_trade());
+        assert_eq!(session.dialogue(), Some(Dialogue::prisoner()));
+        // choices without effect do nothing
+        let effect = session.play_turn(Action::Explore(ExploreAction::Talk(0, 0)));
+        assert!(effect.messages.is_empty());
+        // free the prisoner
+        let effect = session.play_turn(Action::Explore(ExploreAction::Talk(0, 2)));
+        assert_eq!(effect.messages[0], Message::ItemReceived(Item::Talisman));
+        assert!(session.player_inventory().has(Item::Talisman));
+        // the effect is triggered only once
+        let effect = session.play_turn(Action::Explore(ExploreAction::Talk(0, 3)));
+        assert_eq!(effect.messages[0], Message::NothingToGive);
+        assert!(session.player().status_effects().is_empty());
+    }
+
+    #[test]
+    fn should_get_key_and_exit_distance() {
+        let mut session = Session::mock();
+        assert_eq!(session.maze_key_distance(), None);
+        assert_eq!(session.exit_distance(), Some(3));
+        session.maze.room_mut(7).unwrap().item = Some(Item::MazeKey);
+        assert_eq!(session.maze_key_distance(), Some(3));
+    }
+
+    #[test]
+    fn should_calc_prices() {
+        let mut session = Session::mock();
+        let price = session.buy_price(Item::Talisman).unwrap();
+        // prices depend on the seed
+        assert!((24..=36).contains(&price));
+        assert_eq!(session.buy_price(Item::Talisman), Some(price));
+        assert_eq!(session.sell_price(Item::Talisman), Some(price / 2));
+        assert_eq!(session.buy_price(Item::MazeKey), None);
+        // and on the difficulty
+        session.difficulty = Difficulty::Hard;
+        assert!(session.buy_price(Item::Talisman).unwrap() > price);
+    }
+
+    #[test]
+    fn should_defend() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.player.start_fighting();
+        let effect = session.play_turn(Action::Fight(FightAction::Defend));
+        assert_eq!(effect.messages[0], Message::Defending);
+        // defending lasts for a single turn
+        assert_eq!(session.defending, false);
+        // daemon deals at most 2 HP; reduced by 2
+        assert_eq!(session.player().health(), session.player().max_health());
+    }
+
+    #[test]
+    fn should_identify_potions_by_drinking_them() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Mead))
+            .unwrap();
+        assert!(!session.potion_knowledge().is_identified(Potion::Mead));
+        assert_eq!(
+            Item::Potion(Potion::Mead).name(&session.potion_knowledge()),
+            session.potion_knowledge().appearance(Potion::Mead).name
+        );
+        session.play_turn(Action::UseItem(Item::Potion(Potion::Mead)));
+        assert!(session.potion_knowledge().is_identified(Potion::Mead));
+        assert!(!session.potion_knowledge().is_identified(Potion::Red));
+        assert_eq!(
+            Item::Potion(Potion::Mead).name(&session.potion_knowledge()),
+            "Mead"
+        );
+        // knowledge is saved
+        let json = serde_json::to_string(&session).unwrap();
+        let decoded: Session = serde_json::from_str(&json).unwrap();
+        assert!(decoded.potion_knowledge().is_identified(Potion::Mead));
+        // alchemy book identifies everything
+        session.player.inventory.add(Item::AlchemyBook).unwrap();
+        assert!(session.potion_knowledge().is_identified(Potion::Red));
+    }
+
+    #[test]
+    fn should_brew_potions() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Vinegar))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Mead))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Herb(Herb::Sage))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Herb(Herb::Lavender))
+            .unwrap();
+        // alchemy book is required
+        assert!(!session.can_brew());
+        assert!(session.available_brews().is_empty());
+        session.player.inventory.add(Item::AlchemyBook).unwrap();
+        assert!(session.can_brew());
+        assert_eq!(session.available_brews().len(), 6);
+        // brew antidote
+        assert_eq!(
+            session.known_recipe(Item::Potion(Potion::Mead), Item::Potion(Potion::Vinegar)),
+            None
+        );
+        let effect = session.play_turn(Action::Brew(
+            Item::Potion(Potion::Mead),
+            Item::Potion(Potion::Vinegar),
+        ));
+        assert_eq!(effect.messages[0], Message::PotionBrewed(Potion::Antidote));
+        assert!(session
+            .player_inventory()
+            .has(Item::Potion(Potion::Antidote)));
+        assert!(!session.player_inventory().has(Item::Potion(Potion::Mead)));
+        assert!(!session
+            .player_inventory()
+            .has(Item::Potion(Potion::Vinegar)));
+        assert_eq!(
+            session.known_recipe(Item::Potion(Potion::Vinegar), Item::Potion(Potion::Mead)),
+            Some(Potion::Antidote)
+        );
+        assert_eq!(session.recipes().len(), 1);
+        // failed brew wastes the ingredients
+        let effect = session.play_turn(Action::Brew(
+            Item::Herb(Herb::Lavender),
+            Item::Herb(Herb::Sage),
+        ));
+        assert_eq!(effect.messages[0], Message::BrewFailed);
+        assert!(!session.player_inventory().has(Item::Herb(Herb::Sage)));
+        assert_eq!(session.recipes().len(), 1);
+        // the same ingredient twice requires two of them
+        assert!(!session.available_brews().contains(&Action::Brew(
+            Item::Potion(Potion::Antidote),
+            Item::Potion(Potion::Antidote)
+        )));
+        // antidote cures poison
+        session
+            .player
+            .apply_status_effect(StatusEffect::Poisoned, 3);
+        session.play_turn(Action::UseItem(Item::Potion(Potion::Antidote)));
+        assert!(!session.player().has_status_effect(StatusEffect::Poisoned));
+    }
+
+    #[test]
+    fn should_throw_potions() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.player.start_fighting();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Chamomille))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::SnakePoison))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Mead))
+            .unwrap();
+        // sleep
+        let effect = session.play_turn(Action::Fight(FightAction::Throw(Potion::Chamomille, 0)));
+        assert_eq!(
+            effect.messages,
+            vec![
+                Message::PotionThrown(Potion::Chamomille),
+                Message::EnemyFellAsleep,
+                Message::EnemySleeping,
+            ]
+        );
+        assert!(!session
+            .player_inventory()
+            .has(Item::Potion(Potion::Chamomille)));
+        // damage
+        let effect = session.play_turn(Action::Fight(FightAction::Throw(Potion::SnakePoison, 0)));
+        // NOTE: status effects inflicted by the enemy may report messages first
+        assert!(effect.messages.contains(&Message::DamageDealt(3)));
+        assert_eq!(session.get_fighting_enemy().unwrap().health(), 1);
+        // heal
+        let effect = session.play_turn(Action::Fight(FightAction::Throw(Potion::Mead, 0)));
+        assert!(effect.messages.contains(&Message::EnemyHealed(2)));
+        assert_eq!(session.get_fighting_enemy().unwrap().health(), 3);
+    }
+
+    #[test]
+    fn should_throw_potions_at_target() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.maze.room_mut(2).unwrap().enemies =
+            vec![Enemy::Shadow(Shadow::new(5)), Enemy::Shadow(Shadow::new(5))];
+        session.player.start_fighting();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::Chamomille))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Potion(Potion::SnakePoison))
+            .unwrap();
+        assert!(session
+            .available_actions()
+            .contains(&Action::Fight(FightAction::Throw(Potion::Chamomille, 1))));
+        // only the second enemy falls asleep
+        let effect = session.play_turn(Action::Fight(FightAction::Throw(Potion::Chamomille, 1)));
+        assert_eq!(
+            effect
+                .messages
+                .iter()
+                .filter(|x| matches!(x, Message::DamageSuffered(_, _) | Message::EnemyMissed))
+                .count(),
+            1
+        );
+        assert!(effect.messages.contains(&Message::EnemySleeping));
+        // damage the second enemy
+        session.play_turn(Action::Fight(FightAction::Throw(Potion::SnakePoison, 1)));
+        assert_eq!(session.get_fighting_enemies()[0].health(), 5);
+        assert_eq!(session.get_fighting_enemies()[1].health(), 2);
+    }
+
+    #[test]
+    fn should_use_talisman_on_target() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        session.maze.room_mut(2).unwrap().enemies = vec![
+            Enemy::Daemon(Daemon::new(5)),
+            Enemy::Shadow(Shadow::new(5)),
+            Enemy::Daemon(Daemon::new(3)),
+        ];
+        assert_eq!(session.get_fighting_enemies().len(), MAX_ENEMIES_IN_ROOM);
+        session.player.start_fighting();
+        session.player.inventory.add(Item::Talisman).unwrap();
+        let actions = session.available_actions();
+        for target in 0..MAX_ENEMIES_IN_ROOM {
+            assert!(actions.contains(&Action::Fight(FightAction::UseTalisman(target))));
+        }
+        let effect = session.play_turn(Action::Fight(FightAction::UseTalisman(1)));
+        assert!(effect.messages.contains(&Message::EnemyVanished));
+        assert!(!session.player_inventory().has(Item::Talisman));
+        assert_eq!(
+            session.get_fighting_enemies(),
+            &[Enemy::Daemon(Daemon::new(5)), Enemy::Daemon(Daemon::new(3))]
+        );
+        assert_eq!(session.stats().enemies_killed, 1);
+        assert!(!session
+            .available_actions()
+            .iter()
+            .any(|x| matches!(x, Action::Fight(FightAction::UseTalisman(_)))));
+    }
+
+    #[test]
+    fn should_forge_mazebane_from_relics() {
+        let mut session = Session::mock();
+        // NOTE: make sure no enemy can join the player
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session
+            .player
+            .inventory
+            .add(Item::Relic(Relic::Blade))
+            .unwrap();
+        let effect = session.play_turn(Action::UseItem(Item::Relic(Relic::Blade)));
+        assert_eq!(effect.messages[0], Message::RelicsMissing(2));
+        assert!(session.player_inventory().has(Item::Relic(Relic::Blade)));
+        session
+            .player
+            .inventory
+            .add(Item::Relic(Relic::Gem))
+            .unwrap();
+        session
+            .player
+            .inventory
+            .add(Item::Relic(Relic::Hilt))
+            .unwrap();
+        let mazebane = Item::Weapon(Weapon::new(WeaponKind::Mazebane));
+        let effect = session.play_turn(Action::UseItem(Item::Relic(Relic::Hilt)));
+        assert_eq!(effect.messages[0], Message::RelicsCombined(mazebane));
+        assert!(session.player_inventory().has(mazebane));
+        for relic in Relic::ALL {
+            assert!(!session.player_inventory().has(Item::Relic(relic)));
+        }
+    }
+
+    #[test]
+    fn should_win_by_defeating_don_maze() {
+        let mut session = Session::mock();
+        session.maze.player = 2;
+        let mut don_maze = Enemy::DonMaze(DonMaze::default());
+        don_maze.damage(don_maze.max_health() - 1);
+        session.maze.room_mut(2).unwrap().enemies = vec![don_maze];
+        session.player.start_fighting();
+        // don maze can't be hurt without the mazebane
+        session
+            .player
+            .equipment
+            .equip(Item::Weapon(Weapon::new(WeaponKind::Sword)));
+        session.play_turn(Action::Fight(FightAction::Fight(0)));
+        assert_eq!(session.get_fighting_enemy().unwrap().health(), 1);
+        session
+            .player
+            .equipment
+            .equip(Item::Weapon(Weapon::new(WeaponKind::Mazebane)));
+        fight_until(&mut session, 0, |s| s.has_won());
+        assert!(session.has_won());
+        assert!(session.has_defeated_don_maze());
+        assert!(session.get_fighting_enemy().is_none());
+    }
+
+    /// Keep fighting the enemy at `target` until `done` is satisfied; returns all the messages.
+    /// The player is made tough enough to survive the fight
+    fn fight_until(
+        session: &mut Session,
+        target: usize,
+        done: impl Fn(&Session) -> bool,
+    ) -> Vec<Message> {
+        session.player.incr_max_health(200);
+        session.player.heal_max();
+        let mut messages = Vec::new();
+        while !done(session) {
+            assert!(!session.game_over());
+            messages.extend(
+                session
+                    .play_turn(Action::Fight(FightAction::Fight(target)))
+                    .messages,
+            );
+        }
+        messages
+    }
+
     #[test]
     fn should_serialize() {
         #[derive(Serialize, Deserialize, Debug, PartialEq)]
         struct Test {
             session: Session,
         }
-        let mut session = Session::new(None);
+        let mut session = Session::new(None, Difficulty::default());
         session.visit_room(1);
         session.last_room = Some(0);
         let test = Test { session };
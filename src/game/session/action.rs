@@ -2,7 +2,7 @@
 //!
 //! The action defines what the player performs in a turn
 
-use crate::game::entity::Item;
+use crate::game::entity::{EquipmentSlot, Item, Potion};
 
 /// Defines the action performed by the player in a turn
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -13,6 +13,12 @@ pub enum Action {
     Fight(FightAction),
     /// Use item
     UseItem(Item),
+    /// Remove item from equipment slot and put it back into the inventory
+    UnequipItem(EquipmentSlot),
+    /// Drop item from the inventory into the player's room. Can be used only while exploring
+    DropItem(Item),
+    /// Brew two ingredients into a potion. Requires the alchemy book
+    Brew(Item, Item),
     /// Game has been saved; consume turn
     SaveGame,
     /// Sleep, don't do anything. Can be used only when state is `Sleep`
@@ -26,19 +32,45 @@ pub enum Action {
 pub enum ExploreAction {
     /// Change room to provided node
     ChangeRoom(u32),
-    /// Collect the item in the room
+    /// Collect the item in the room, opening its chest
     CollectItem,
+    /// Inspect the chest in the room before opening it, looking for traps
+    InspectChest,
+    /// Open the locked chest in the room with a lockpick
+    PickLock,
+    /// Force the lock of the chest in the room for a turn, making a lot of noise
+    ForceChest,
     /// Leave maze and win
     LeaveMaze,
     /// Go to previous room
     GoToPreviousRoom,
+    /// Change room to provided node making less noise, but taking an extra turn
+    Sneak(u32),
+    /// Sleep for the provided amount of turns, regaining health.
+    /// Enemies entering the room while resting get free hits before the player wakes up
+    Rest(u8),
+    /// Buy item from the merchant in the room
+    Buy(Item),
+    /// Sell item to the merchant in the room
+    Sell(Item),
+    /// Answer the npc in the room with the choice at the provided position of the provided dialogue node.
+    /// Triggers the effect of the choice, if any
+    Talk(usize, usize),
 }
 
-/// Defines the action which can be performed while state is `Explore`
+/// Defines the action which can be performed while state is `Fight`
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum FightAction {
-    /// Fight enemy
-    Fight,
-    /// Try to escape from fight
+    /// Fight the enemy at the provided position among the enemies in the room
+    Fight(usize),
+    /// Lower incoming damage for this turn, with a chance to counterattack
+    Defend,
+    /// Try to escape from fight to a random room
     Escape,
+    /// Try to retreat to the provided adjacent room
+    Retreat(u32),
+    /// Throw potion at the enemy at the provided position among the enemies in the room
+    Throw(Potion, usize),
+    /// Use the talisman on the enemy at the provided position among the enemies in the room
+    UseTalisman(usize),
 }
@@ -1,14 +1,32 @@
 //! # Action replay
 
+use std::ops::RangeInclusive;
+
+use rand::rngs::ThreadRng;
 use rand::Rng;
 
 use super::{
     Action, Effect, ExploreAction, FightAction, Message, PlayerState, Reveal, Session, Sound,
+    MAX_NOISE, POTION_NOISE, STEPS_NOISE,
+};
+use crate::game::entity::{
+    Artifact, Chest, DialogueEffect, Enemy, EquipmentSlot, Item, LightLevel, Mimic, Potion, Recipe,
+    Relic, StatusEffect, ThrownEffect, Weapon, WeaponKind,
 };
-use crate::game::entity::{Enemy, Item, Potion};
+use crate::game::inventory::InventoryError;
+use crate::game::Hp;
 use crate::utils::{random, room_resolver};
 
-const ESCAPE_PROBABILITY: u8 = 50;
+/// Fuel restored to the torch by a torch oil
+const TORCH_OIL_FUEL: u8 = 80;
+/// Rooms revealed by the map scroll, walking away from the player
+const MAP_SCROLL_RANGE: u8 = 3;
+/// Coins found in a gold pouch
+const GOLD_IN_POUCH: RangeInclusive<u32> = 5..=20;
+/// Health of a mimic once revealed
+const MIMIC_HEALTH: RangeInclusive<Hp> = 4..=6;
+/// While resting, the player restores 1 HP every this amount of turns
+const REST_HEALING_TURNS: u8 = 2;
 
 /// The action replay is used to play actions performed by the player
 pub struct ActionReplay<'a> {
@@ -24,11 +42,14 @@ impl<'a> ActionReplay<'a> {
     pub fn play(mut self, action: Action, effect: &mut Effect) {
         debug!("playing player action: {:?}", action);
         match action {
+            Action::Brew(first, second) => self.brew(first, second, effect),
             Action::Die => self.die(effect),
+            Action::DropItem(item) => self.drop_item(item, effect),
             Action::Explore(explore) => self.play_explore_action(explore, effect),
             Action::Fight(fight) => self.play_fight_action(fight, effect),
             Action::SaveGame => self.save_game(effect),
             Action::Sleep => self.sleep(effect),
+            Action::UnequipItem(slot) => self.unequip_item(slot, effect),
             Action::UseItem(item) => self.use_item(item, effect),
         }
     }
@@ -51,10 +72,30 @@ impl<'a> ActionReplay<'a> {
         assert_eq!(self.session.player.state(), PlayerState::Explore);
         debug!("playing explore action: {:?}", action);
         match action {
-            ExploreAction::ChangeRoom(room) => self.change_room(room, effect),
+            ExploreAction::ChangeRoom(room) => {
+                self.session.make_noise(STEPS_NOISE);
+                self.change_room(room, effect)
+            }
             ExploreAction::CollectItem => self.collect_item(effect),
-            ExploreAction::GoToPreviousRoom => self.go_to_previous_room(effect),
+            ExploreAction::ForceChest => {
+                self.session.make_noise(MAX_NOISE);
+                self.force_chest(effect)
+            }
+            ExploreAction::InspectChest => self.inspect_chest(effect),
+            ExploreAction::PickLock => self.pick_lock(effect),
+            ExploreAction::GoToPreviousRoom => {
+                self.session.make_noise(STEPS_NOISE);
+                self.go_to_previous_room(effect)
+            }
             ExploreAction::LeaveMaze => self.leave_maze(effect),
+            ExploreAction::Sneak(room) => {
+                self.session.make_noise(STEPS_NOISE / 2);
+                self.change_room(room, effect)
+            }
+            ExploreAction::Rest(turns) => self.rest(turns, effect),
+            ExploreAction::Buy(item) => self.buy(item, effect),
+            ExploreAction::Sell(item) => self.sell(item, effect),
+            ExploreAction::Talk(node, choice) => self.talk(node, choice, effect),
         }
     }
 
@@ -62,9 +103,14 @@ impl<'a> ActionReplay<'a> {
     fn play_fight_action(&mut self, action: FightAction, effect: &mut Effect) {
         assert_eq!(self.session.player.state(), PlayerState::Fight);
         debug!("playing fight action: {:?}", action);
+        self.session.make_noise(MAX_NOISE);
         match action {
+            FightAction::Defend => self.defend(effect),
             FightAction::Escape => self.escape(effect),
-            FightAction::Fight => self.fight(effect),
+            FightAction::Fight(target) => self.fight(target, effect),
+            FightAction::Retreat(room) => self.retreat(room, effect),
+            FightAction::Throw(potion, target) => self.throw_potion(potion, target, effect),
+            FightAction::UseTalisman(target) => self.use_talisman(target, effect),
         }
     }
 
@@ -84,16 +130,208 @@ impl<'a> ActionReplay<'a> {
         self.session.last_room = Some(self.session.maze.player);
         self.session.maze.player = room;
         effect.sound(Sound::Steps);
+        if self.session.player.has_artifact(Artifact::OwlEye) {
+            self.sense_enemies(effect);
+        }
+    }
+
+    /// Sense the enemies in the rooms adjacent to the player's one
+    fn sense_enemies(&mut self, effect: &mut Effect) {
+        let adjacent_rooms = self.session.maze.adjacent_rooms(self.session.maze.player);
+        for (node, room) in adjacent_rooms.into_iter() {
+            for enemy in room.enemies.iter() {
+                debug!("sensed enemy {:?} in room {}", enemy, node);
+                effect.message(Message::EnemySensed(node, *enemy));
+            }
+        }
     }
 
     /// Collect item at room and put it into the inventory
     fn collect_item(&mut self, effect: &mut Effect) {
         // get item in room
+        let item = *self.session.maze.item_in_room().unwrap();
+        if item != Item::Gold {
+            if let Err(err) = self.session.player.inventory.can_add(item) {
+                debug!("can't collect {:?}: {}", item, err);
+                Self::report_inventory_error(err, item, effect);
+                return;
+            }
+        }
+        match self.session.maze.chest().copied() {
+            Some(Chest::Locked { .. }) => panic!("chest is locked"),
+            Some(Chest::Mimic) => return self.reveal_mimic(effect),
+            Some(Chest::Trapped { damage }) => {
+                self.spring_trap(damage, effect);
+                if self.session.player.is_dead() {
+                    return;
+                }
+            }
+            Some(Chest::Plain) | None => {}
+        }
         let item = self.session.maze.take_item().unwrap();
         debug!("found item {:?} in room {}", item, self.session.maze.player);
-        self.session.player.inventory.add(item);
         effect.sound(Sound::ItemCollected);
-        effect.message(Message::ItemCollected(item));
+        if item == Item::Gold {
+            let coins = rand::rng().random_range(GOLD_IN_POUCH);
+            self.session.player.earn_gold(coins);
+            effect.message(Message::GoldCollected(coins));
+        } else {
+            self.store_item(item, effect);
+            effect.message(Message::ItemCollected(item));
+        }
+    }
+
+    /// Put item into the inventory. If there's no room for it, it's dropped in the player's room
+    fn store_item(&mut self, item: Item, effect: &mut Effect) {
+        if let Err(err) = self.session.player.inventory.add(item) {
+            debug!("{:?} dropped in the room: {}", item, err);
+            self.session.maze.drop_item(item);
+            Self::report_inventory_error(err, item, effect);
+        }
+    }
+
+    /// Tell the player why `item` can't be put into the inventory
+    fn report_inventory_error(err: InventoryError, item: Item, effect: &mut Effect) {
+        effect.message(match err {
+            InventoryError::Full => Message::InventoryFull,
+            InventoryError::StackFull => Message::ItemStackFull(item),
+        });
+    }
+
+    /// Inspect the chest in the room: traps are disarmed and mimics wake up
+    fn inspect_chest(&mut self, effect: &mut Effect) {
+        match self.session.maze.chest().copied().unwrap() {
+            Chest::Locked { .. } => panic!("chest is locked"),
+            Chest::Mimic => return self.reveal_mimic(effect),
+            Chest::Plain => {
+                debug!("the chest is safe");
+                effect.message(Message::ChestInspected);
+            }
+            Chest::Trapped { damage } => {
+                debug!("found a trap dealing {} HP; disarmed", damage);
+                effect.message(Message::TrapDisarmed);
+            }
+        }
+        self.session.maze.open_chest();
+    }
+
+    /// Open the locked chest in the room with a lockpick
+    fn pick_lock(&mut self, effect: &mut Effect) {
+        assert!(self.session.maze.chest().is_some_and(|x| x.is_locked()));
+        assert!(self.session.player.inventory.has(Item::Lockpick));
+        self.session.player.inventory.consume(Item::Lockpick);
+        self.session.maze.open_chest();
+        debug!("lock picked");
+        effect.message(Message::LockPicked);
+        effect.sound(Sound::ItemCollected);
+    }
+
+    /// Force the lock of the chest in the room; the chest opens once the lock gives way
+    fn force_chest(&mut self, effect: &mut Effect) {
+        let chest = self.session.maze.chest_mut().unwrap();
+        assert!(chest.is_locked());
+        let remaining = chest.force();
+        debug!(
+            "forced chest; {} turns before the lock gives way",
+            remaining
+        );
+        if remaining == 0 {
+            self.session.maze.open_chest();
+        }
+        effect.message(Message::ChestForced(remaining));
+        effect.sound(Sound::PlayerAttack);
+    }
+
+    /// The chest in the room is a mimic: it wakes up and attacks the player
+    fn reveal_mimic(&mut self, effect: &mut Effect) {
+        let mimic = Enemy::Mimic(Mimic::new(rand::rng().random_range(MIMIC_HEALTH)));
+        debug!("the chest is a mimic: {:?}", mimic);
+        self.session.maze.open_chest();
+        self.session
+            .maze
+            .room_mut(self.session.maze.player)
+            .unwrap()
+            .enemies
+            .push(mimic);
+        self.session.start_fighting();
+        effect.message(Message::MimicRevealed);
+        effect.sound(Sound::EnemyApproaching);
+    }
+
+    /// A trap in the chest springs, dealing `damage` to the player
+    fn spring_trap(&mut self, damage: Hp, effect: &mut Effect) {
+        debug!("trap sprung; dealing {} HP to player", damage);
+        self.session.player.damage(damage);
+        self.session.stats.damage_suffered += damage as u64;
+        effect.message(Message::TrapSprung(damage));
+        effect.sound(Sound::EnemyAttack);
+        if self.session.player.is_dead() {
+            debug!("player is dead due to a trap. Game over...");
+            effect.message(Message::PlayerDead);
+            effect.sound(Sound::PlayerDead);
+        }
+    }
+
+    /// Buy item from the merchant
+    fn buy(&mut self, item: Item, effect: &mut Effect) {
+        let price = self.session.buy_price(item).unwrap();
+        assert!(self.session.merchant().unwrap().has(item));
+        if let Err(err) = self.session.player.inventory.can_add(item) {
+            debug!("can't buy {:?}: {}", item, err);
+            Self::report_inventory_error(err, item, effect);
+            return;
+        }
+        assert!(self.session.player.spend_gold(price as u32));
+        self.session.merchant_mut().unwrap().sell(item);
+        self.session.player.inventory.add(item).unwrap();
+        debug!("bought {:?} for {} gold", item, price);
+        effect.message(Message::ItemBought(item, price));
+        effect.sound(Sound::ItemCollected);
+    }
+
+    /// Sell item to the merchant
+    fn sell(&mut self, item: Item, effect: &mut Effect) {
+        let price = self.session.sell_price(item).unwrap();
+        assert!(self.session.merchant().is_some());
+        self.session.player.inventory.consume(item);
+        self.session.merchant_mut().unwrap().buy(item);
+        self.session.player.earn_gold(price as u32);
+        debug!("sold {:?} for {} gold", item, price);
+        effect.message(Message::ItemSold(item, price));
+        effect.sound(Sound::GameSaved);
+    }
+
+    /// Answer the npc; trigger the choice effect, unless the npc has already triggered one
+    fn talk(&mut self, node: usize, choice: usize, effect: &mut Effect) {
+        let dialogue = self.session.dialogue().unwrap();
+        let Some(dialogue_effect) = dialogue.choice(node, choice).unwrap().effect else {
+            return;
+        };
+        let speaker = self
+            .session
+            .maze
+            .npc_mut()
+            .and_then(|x| x.speaker_mut())
+            .unwrap();
+        if speaker.effect_triggered() {
+            debug!("npc has already triggered its dialogue effect");
+            effect.message(Message::NothingToGive);
+            return;
+        }
+        speaker.trigger_effect();
+        debug!("triggering dialogue effect {:?}", dialogue_effect);
+        match dialogue_effect {
+            DialogueEffect::Heal(hp) => {
+                self.session.player.heal(hp);
+                effect.message(Message::Healed(hp));
+            }
+            DialogueEffect::GiveItem(item) => {
+                effect.message(Message::ItemReceived(item));
+                effect.sound(Sound::ItemCollected);
+                self.store_item(item, effect);
+            }
+            DialogueEffect::Curse(status, turns) => self.apply_status_effect(status, turns, effect),
+        }
     }
 
     /// Go to previous room
@@ -116,11 +354,42 @@ impl<'a> ActionReplay<'a> {
         self.session.stats.items_used += 1;
         debug!("using item {:?}", item);
         match item {
-            Item::Armor => self.wear_armor(effect),
+            Item::Amulet(_) | Item::Armor(_) | Item::Weapon(_) => {
+                return self.equip_item(item, effect);
+            }
             Item::Potion(potion) => self.drink_potion(potion, effect),
+            Item::Relic(_) => {
+                return self.combine_relics(effect);
+            }
+            Item::Sonar if self.session.player.torch.light_level() == LightLevel::Dark => {
+                debug!("can't use sonar in the dark");
+                effect.message(Message::SonarInDarkness);
+                return;
+            }
             Item::Sonar => self.use_sonar(effect),
-            Item::Talisman => self.use_talisman(effect),
-            Item::AlchemyBook | Item::PaintCan | Item::MazeKey => {}
+            Item::Compass => self.use_compass(effect),
+            Item::MapScroll => self.use_map_scroll(effect),
+            Item::Rope if self.session.maze.player == 0 => {
+                debug!("player is already at the entrance");
+                effect.message(Message::ItemUseless(item));
+                return;
+            }
+            Item::Rope => self.use_rope(effect),
+            Item::TeleportStone => {
+                if !self.use_teleport_stone(effect) {
+                    effect.message(Message::ItemUseless(item));
+                    return;
+                }
+            }
+            Item::TorchOil => self.session.player.torch.refuel(TORCH_OIL_FUEL),
+            Item::AlchemyBook
+            | Item::Artifact(_)
+            | Item::Gold
+            | Item::Herb(_)
+            | Item::Lockpick
+            | Item::PaintCan
+            | Item::MazeKey
+            | Item::Talisman => {}
         }
         if item.consumable() {
             debug!("item {:?} is consumable; decrease quantity", item);
@@ -129,22 +398,101 @@ impl<'a> ActionReplay<'a> {
         effect.message(Message::ItemUsed(item));
     }
 
-    /// Wear armor
-    fn wear_armor(&mut self, effect: &mut Effect) {
-        debug!("increasing max HP by 1");
-        self.session.player.incr_max_health(1);
-        debug!(
-            "armor worned; new max HP {}",
-            self.session.player.max_health()
-        );
+    /// Move item from inventory to its equipment slot.
+    /// The item previously in the slot is put back into the inventory
+    fn equip_item(&mut self, item: Item, effect: &mut Effect) {
+        self.session.player.inventory.consume(item);
+        if let Some(previous) = self.session.player.equipment.equip(item) {
+            debug!("{:?} put back into the inventory", previous);
+            self.store_item(previous, effect);
+        }
         effect.sound(Sound::ArmorEquipped);
-        effect.message(Message::ArmorEquipped);
+        effect.message(Message::ItemEquipped(item));
+    }
+
+    /// Remove item from equipment slot and put it back into the inventory
+    fn unequip_item(&mut self, slot: EquipmentSlot, effect: &mut Effect) {
+        let item = self.session.player.equipment.unequip(slot).unwrap();
+        if let Err(err) = self.session.player.inventory.add(item) {
+            debug!("can't unequip {:?}: {}", slot, err);
+            self.session.player.equipment.equip(item);
+            Self::report_inventory_error(err, item, effect);
+            return;
+        }
+        debug!("unequipped {:?}", item);
+        effect.message(Message::ItemUnequipped(item));
+    }
+
+    /// Drop item from the inventory into the player's room
+    fn drop_item(&mut self, item: Item, effect: &mut Effect) {
+        assert!(self.session.can_drop_items());
+        self.session.player.inventory.consume(item);
+        self.session.maze.drop_item(item);
+        debug!("dropped {:?} in room {}", item, self.session.maze.player);
+        effect.message(Message::ItemDropped(item));
+    }
+
+    /// Combine the three relics into the Mazebane, if the player has gathered all of them
+    fn combine_relics(&mut self, effect: &mut Effect) {
+        let missing = Relic::ALL
+            .iter()
+            .filter(|x| !self.session.player.inventory.has(Item::Relic(**x)))
+            .count();
+        if missing > 0 {
+            debug!("can't combine relics; {} missing", missing);
+            effect.message(Message::RelicsMissing(missing as u8));
+            return;
+        }
+        for relic in Relic::ALL {
+            self.session.player.inventory.consume(Item::Relic(relic));
+        }
+        let mazebane = Item::Weapon(Weapon::new(WeaponKind::Mazebane));
+        debug!("relics combined into {:?}", mazebane);
+        self.store_item(mazebane, effect);
+        effect.message(Message::RelicsCombined(mazebane));
+        effect.sound(Sound::ItemCollected);
+    }
+
+    /// Brew `first` and `second` into a potion; ingredients are wasted if there's no recipe for them
+    fn brew(&mut self, first: Item, second: Item, effect: &mut Effect) {
+        assert!(self.session.can_brew());
+        assert!(first.is_ingredient() && second.is_ingredient());
+        let inventory = &mut self.session.player.inventory;
+        assert!(inventory.quantity(first) >= if first == second { 2 } else { 1 });
+        assert!(inventory.has(second));
+        inventory.consume(first);
+        inventory.consume(second);
+        self.session.make_noise(POTION_NOISE);
+        effect.sound(Sound::DrinkPotion);
+        match Recipe::find(first, second) {
+            Some(recipe) => {
+                debug!(
+                    "brewed {:?} out of {:?} and {:?}",
+                    recipe.potion, first, second
+                );
+                self.session.discover_recipe(recipe);
+                effect.message(Message::PotionBrewed(recipe.potion));
+                self.store_item(Item::Potion(recipe.potion), effect);
+            }
+            None => {
+                debug!("there's no recipe for {:?} and {:?}", first, second);
+                effect.message(Message::BrewFailed);
+            }
+        }
     }
 
     /// Drink potion and apply effects
     fn drink_potion(&mut self, potion: Potion, effect: &mut Effect) {
         debug!("drinking potion: {:?}", potion);
+        self.session.identify_potion(potion);
+        self.session.make_noise(POTION_NOISE);
         match potion {
+            Potion::Antidote => {
+                self.session
+                    .player
+                    .cure_status_effect(StatusEffect::Poisoned);
+                self.session.player.heal(1);
+            }
             Potion::Chamomille if self.session.player.state() == PlayerState::Explore => {
                 self.session.player.start_sleeping(3);
                 self.session.player.heal(1);
@@ -161,19 +509,22 @@ impl<'a> ActionReplay<'a> {
             }
             Potion::FairyInABottle => {
                 self.session.player.heal_max();
+                self.apply_status_effect(StatusEffect::Regenerating, 5, effect);
             }
             Potion::Mead => {
                 self.session.player.heal(2);
             }
             Potion::Red => {
                 self.session.player.heal(5);
+                self.apply_status_effect(StatusEffect::Hasted, 3, effect);
             }
             Potion::SnakePoison => {
-                self.session.player.damage(2);
+                self.apply_status_effect(StatusEffect::Poisoned, 3, effect);
             }
             Potion::UnicornElixir => {
                 self.session.player.incr_max_health(5);
                 self.session.player.heal_max();
+                self.apply_status_effect(StatusEffect::Blessed, 10, effect);
             }
             Potion::Vinegar => {
                 self.session.player.damage(1);
@@ -183,14 +534,20 @@ impl<'a> ActionReplay<'a> {
         effect.message(Message::PotionDrunk(potion));
     }
 
+    /// Apply status effect to player for `turns` and report it
+    fn apply_status_effect(&mut self, status: StatusEffect, turns: u8, effect: &mut Effect) {
+        self.session.player.apply_status_effect(status, turns);
+        effect.message(Message::StatusEffectApplied(status));
+    }
+
     /// Use sonar to detect enemies and items in adjacent rooms
     fn use_sonar(&mut self, effect: &mut Effect) {
         let adjacent_rooms = self.session.maze.adjacent_rooms(self.session.maze.player);
         let mut revealed = false;
         for (node, room) in adjacent_rooms.into_iter() {
-            if let Some(enemy) = room.enemy {
+            for enemy in room.enemies.iter() {
                 debug!("revealed enemy {:?} in room {}", enemy, node);
-                effect.message(Message::Reveal(node, Reveal::Enemy(enemy)));
+                effect.message(Message::Reveal(node, Reveal::Enemy(*enemy)));
                 revealed = true;
             }
             if let Some(item) = room.item {
@@ -206,22 +563,131 @@ impl<'a> ActionReplay<'a> {
         effect.sound(Sound::Sonar);
     }
 
-    /// Use talisman to kill enemy or vanish donmaze
-    fn use_talisman(&mut self, effect: &mut Effect) {
-        // get current room enemy and remove from it
-        let enemy = self.session.maze.take_enemy().unwrap();
+    /// Use compass to find the first step on the shortest path to the exit
+    fn use_compass(&mut self, effect: &mut Effect) {
+        let maze = &self.session.maze;
+        let exit = maze
+            .rooms()
+            .into_iter()
+            .find(|(_, room)| room.is_exit())
+            .map(|(node, _)| node)
+            .unwrap();
+        let step = if exit == maze.player {
+            None
+        } else {
+            let distances = maze.distances_from(exit, u8::MAX);
+            maze.adjacent_rooms(maze.player)
+                .into_iter()
+                .map(|(node, _)| node)
+                .min_by_key(|node| (distances.get(node).copied().unwrap_or(u8::MAX), *node))
+        };
+        debug!("compass points to {:?}; exit is {}", step, exit);
+        effect.message(Message::CompassPoint(step));
+    }
+
+    /// Use map scroll to reveal how many rooms lie beyond each adjacent room and whether the exit is among them
+    fn use_map_scroll(&mut self, effect: &mut Effect) {
+        let maze = &self.session.maze;
+        let mut adjacent_rooms: Vec<u32> = maze
+            .adjacent_rooms(maze.player)
+            .into_iter()
+            .map(|(node, _)| node)
+            .collect();
+        adjacent_rooms.sort();
+        let mut revealed = Vec::new();
+        for adjacent in adjacent_rooms {
+            let rooms = maze.distances_through(maze.player, adjacent, MAP_SCROLL_RANGE);
+            let has_exit = rooms.keys().any(|node| maze.room(*node).unwrap().is_exit());
+            revealed.extend(rooms.keys());
+            debug!(
+                "map reveals {} rooms through room {}; exit: {}",
+                rooms.len(),
+                adjacent,
+                has_exit
+            );
+            effect.message(Message::MapRevealed(adjacent, rooms.len() as u8, has_exit));
+        }
+        // the revealed rooms are marked as explored
+        for room in revealed {
+            self.session.visit_room(room);
+        }
+    }
+
+    /// Use rope to walk back along the explored rooms to the entrance of the maze.
+    /// The player stops in the first room with enemies on the way
+    fn use_rope(&mut self, effect: &mut Effect) {
+        let maze = &self.session.maze;
+        let path = maze
+            .path_to(0, |room| self.session.room_visited(room))
+            .or_else(|| maze.path_to(0, |_| true))
+            .unwrap();
+        debug!("player follows the rope back to room 0 through {:?}", path);
+        for room in path {
+            self.session.visit_room(self.session.maze.player);
+            self.session.last_room = Some(self.session.maze.player);
+            self.session.maze.player = room;
+            if self.session.maze.room(room).unwrap().has_enemies() {
+                debug!("the way back is blocked by enemies in room {}", room);
+                break;
+            }
+        }
+        effect.sound(Sound::Steps);
+    }
+
+    /// Use teleport stone to go to a random visited room.
+    /// Returns whether the player has been teleported
+    fn use_teleport_stone(&mut self, effect: &mut Effect) -> bool {
+        let mut rooms: Vec<u32> = self
+            .session
+            .visited_rooms
+            .iter()
+            .copied()
+            .filter(|x| *x != self.session.maze.player)
+            .collect();
+        if rooms.is_empty() {
+            debug!("there are no visited rooms to teleport to");
+            return false;
+        }
+        rooms.sort();
+        let room = *random::choice(&mut rand::rng(), &rooms);
+        debug!("player teleported to room {}", room);
+        self.teleport(room);
+        effect.sound(Sound::Rush);
+        true
+    }
+
+    /// Move player to `room`, which is not adjacent.
+    /// The player is considered to come from an adjacent room of `room`, preferring an explored one,
+    /// so that the directions of the other rooms can be resolved
+    fn teleport(&mut self, room: u32) {
+        self.session.visit_room(self.session.maze.player);
+        self.session.maze.player = room;
+        let mut adjacent_rooms = self.session.adjacent_rooms();
+        adjacent_rooms.sort_by_key(|x| (!self.session.room_visited(*x), *x));
+        self.session.last_room = adjacent_rooms.first().copied();
+    }
+
+    /// Use talisman to kill the enemy at `target` or vanish donmaze
+    fn use_talisman(&mut self, target: usize, effect: &mut Effect) {
+        assert!(self.session.player.inventory.has(Item::Talisman));
+        assert!(target < self.session.maze.fighting_enemies().len());
+        self.session.player.inventory.consume(Item::Talisman);
+        self.session.stats.items_used += 1;
+        effect.message(Message::ItemUsed(Item::Talisman));
+        // get the enemy at target and remove it from the room
+        let enemy = self.session.maze.take_enemy(target).unwrap();
         debug!(
             "enemy {:?} removed from room {}",
             enemy, self.session.maze.player
         );
         // move don maze to a random room
-        if matches!(enemy, Enemy::DonMaze) {
+        if matches!(enemy, Enemy::DonMaze(_)) {
             let rooms_without_enemies: Vec<u32> = self
                 .session
                 .maze
                 .rooms()
                 .into_iter()
-                .filter(|(_, room)| room.enemy.is_none())
+                .filter(|(_, room)| !room.has_enemies())
                 .map(|(node, _)| node)
                 .collect();
             let mut rng = rand::rng();
@@ -231,21 +697,24 @@ impl<'a> ActionReplay<'a> {
                 enemy, self.session.maze.player, new_enemy_room
             );
             let room = self.session.maze.room_mut(new_enemy_room).unwrap();
-            room.enemy = Some(enemy);
+            room.enemies.push(enemy);
         } else {
             self.session.stats.enemies_killed += 1;
+            self.session.gain_experience(enemy.experience(), effect);
+        }
+        // leave fight if there are no other enemies
+        if self.session.maze.fighting_enemies().is_empty() {
+            self.session.player.start_exploring();
         }
-        // leave fight
-        self.session.player.start_exploring();
         effect.sound(Sound::EnemyScream);
         effect.message(Message::EnemyVanished);
     }
 
-    /// Try to escape (50% chance) to the first adjacent room, but not previous room.
+    /// Try to escape to the first adjacent room, but not previous room.
     /// If there's no adjacent room, but previous room; escape to previous room
     fn escape(&mut self, effect: &mut Effect) {
         let mut rng = rand::rng();
-        if random::happens(&mut rng, ESCAPE_PROBABILITY) {
+        if random::happens(&mut rng, self.session.escape_probability()) {
             // find room to escape to
             let adjacent_rooms_but_not_last: Vec<u32> = self
                 .session
@@ -261,74 +730,157 @@ impl<'a> ActionReplay<'a> {
             } else {
                 *random::choice(&mut rng, &adjacent_rooms_but_not_last)
             };
-            debug!("escape succeeded; new room {}", new_room);
-            self.session.last_room = Some(self.session.maze.player);
-            self.session.maze.player = new_room;
-            self.session.stats.fights_escaped += 1;
-            self.session.player.start_exploring();
-            effect.message(Message::EscapeSucceeded(new_room));
-            effect.sound(Sound::Rush);
+            self.escape_to(new_room, effect);
         } else {
             debug!("escape failed");
             effect.message(Message::EscapeFailed);
         }
     }
 
-    /// Fight enemy
-    fn fight(&mut self, effect: &mut Effect) {
-        // get current room enemy and remove from it
-        let enemy = self.session.maze.fighting_enemy_mut().unwrap();
-        let damage_dealt = if matches!(enemy, Enemy::DonMaze) {
-            debug!("you can't deal with donmaze");
-            0
+    /// Try to retreat to the provided adjacent room
+    fn retreat(&mut self, room: u32, effect: &mut Effect) {
+        assert!(self.session.maze.room_adjacent(room));
+        let mut rng = rand::rng();
+        if random::happens(&mut rng, self.session.retreat_probability(room)) {
+            self.escape_to(room, effect);
         } else {
-            let mut rng = rand::rng();
-            match rng.random_range(0..100) {
+            debug!("retreat to {} failed", room);
+            effect.message(Message::EscapeFailed);
+        }
+    }
+
+    /// Leave the fight and move player to `room`
+    fn escape_to(&mut self, room: u32, effect: &mut Effect) {
+        debug!("escape succeeded; new room {}", room);
+        self.session.last_room = Some(self.session.maze.player);
+        self.session.maze.player = room;
+        self.session.stats.fights_escaped += 1;
+        self.session.player.start_exploring();
+        effect.message(Message::EscapeSucceeded(room));
+        effect.sound(Sound::Rush);
+    }
+
+    /// Raise guard for this turn
+    fn defend(&mut self, effect: &mut Effect) {
+        debug!("player is defending");
+        self.session.defending = true;
+        effect.message(Message::Defending);
+    }
+
+    /// Throw potion at the enemy at `target`, applying its effect to the enemy
+    fn throw_potion(&mut self, potion: Potion, target: usize, effect: &mut Effect) {
+        assert!(target < self.session.maze.fighting_enemies().len());
+        let item = Item::Potion(potion);
+        assert!(self.session.player.inventory.has(item));
+        self.session.player.inventory.consume(item);
+        self.session.stats.items_used += 1;
+        effect.message(Message::PotionThrown(potion));
+        effect.sound(Sound::PlayerAttack);
+        let enemy = self.session.maze.fighting_enemy_mut(target).unwrap();
+        debug!("throwing {:?} at {:?}", potion, enemy);
+        match potion.thrown_effect() {
+            ThrownEffect::Damage(_) if !enemy.is_vulnerable_to(None) => {
+                debug!("the potion can't hurt {:?}", enemy);
+                effect.message(Message::DamageDealt(0));
+            }
+            ThrownEffect::Damage(hp) => {
+                let damage_dealt = hp.min(enemy.health());
+                effect.message(Message::DamageDealt(damage_dealt));
+                self.session
+                    .damage_fighting_enemy(target, damage_dealt, effect);
+            }
+            ThrownEffect::Heal(hp) => {
+                let health = enemy.health();
+                enemy.heal(hp);
+                effect.message(Message::EnemyHealed(enemy.health() - health));
+            }
+            ThrownEffect::Sleep => {
+                self.session.enemy_asleep = Some(target);
+                effect.message(Message::EnemyFellAsleep);
+            }
+        }
+    }
+
+    /// Calculate damage dealt by player bare-handed or with the weapon
+    fn deal_damage(rng: &mut ThreadRng, weapon: Option<&Weapon>) -> Hp {
+        let roll = rng.random_range(0..100);
+        match weapon {
+            Some(weapon) => weapon.kind.damage(roll),
+            None => match roll {
                 x if x < 30 => 1,
                 x if x < 60 => 2,
                 x if x < 80 => 3,
                 x if x < 95 => 4,
                 _ => 5,
+            },
+        }
+    }
+
+    /// Fight the enemy at `target`
+    fn fight(&mut self, target: usize, effect: &mut Effect) {
+        assert!(target < self.session.maze.fighting_enemies().len());
+        let mut rng = rand::rng();
+        if !random::happens(&mut rng, self.session.hit_probability(target)) {
+            debug!("player missed the hit");
+            effect.message(Message::PlayerMissed);
+            return;
+        }
+        let weapon = self.session.player.equipment.weapon();
+        let damage_dealt = if self.session.maze.fighting_enemies()[target].is_vulnerable_to(weapon)
+        {
+            let damage = Self::deal_damage(&mut rng, weapon) + self.session.player.damage_bonus();
+            if random::happens(&mut rng, self.session.critical_probability()) {
+                debug!("player landed a critical hit");
+                effect.message(Message::CriticalHit);
+                damage.saturating_mul(2)
+            } else {
+                damage
             }
-        };
-        debug!("player dealt {} HP to {:?}", damage_dealt, enemy);
-        enemy.damage(damage_dealt);
-        self.session.stats.damage_inflicted += damage_dealt as u64;
-        effect.message(Message::DamageDealt(damage_dealt));
-        // check if enemy has died
-        if enemy.health() == 0 {
-            debug!("enemy defeated");
-            // remove enemy from room
-            let room = self
-                .session
-                .maze
-                .room_mut(self.session.maze.player)
-                .unwrap();
-            room.enemy = None;
-            self.session.stats.enemies_killed += 1;
-            self.session.player.start_exploring();
-            effect.message(Message::EnemyDefeated);
-            effect.sound(Sound::PlayerAttack);
-            effect.sound(Sound::EnemyScream);
         } else {
-            debug!("new enemy HP: {}", enemy.health());
-            effect.sound(Sound::PlayerAttack);
+            debug!("you can't deal with donmaze without the mazebane");
+            0
+        };
+        // wear weapon
+        if let Some(broken) = self.session.player.equipment.wear_weapon() {
+            debug!("{:?} has broken", broken);
+            effect.message(Message::ItemBroken(broken));
         }
+        debug!("player dealt {} HP to enemy at {}", damage_dealt, target);
+        effect.message(Message::DamageDealt(damage_dealt));
+        effect.sound(Sound::PlayerAttack);
+        self.session
+            .damage_fighting_enemy(target, damage_dealt, effect);
     }
 
-    /// Play sleep turn. Decrease sleep counter. Wake up if 0
+    /// Fall asleep for `turns` to regain health
+    fn rest(&mut self, turns: u8, effect: &mut Effect) {
+        debug!("player rests for {} turns", turns);
+        self.session.player.start_sleeping(turns);
+        self.session.rest = Some(0);
+        self.session.stats.times_rested += 1;
+        effect.message(Message::RestStarted(turns));
+        effect.sound(Sound::Sleep);
+    }
+
+    /// Play sleep turn. Status effects have already been ticked by the session,
+    /// so the player may have just woken up
     fn sleep(&mut self, effect: &mut Effect) {
-        assert_eq!(self.session.player.state(), PlayerState::Asleep);
-        self.session.player.decr_sleep_counter();
         self.session.stats.slept_for_turns += 1;
         if self.session.player.state() == PlayerState::Asleep {
             debug!("zzzzzz you're sleeping");
             effect.message(Message::Sleeping);
             effect.sound(Sound::Sleep);
-        } else {
-            debug!("wake up!");
-            effect.message(Message::WakeUp);
-            effect.sound(Sound::WakeUp);
+            if let Some(rest) = self.session.rest.as_mut() {
+                *rest = rest.saturating_add(1);
+                let player = &mut self.session.player;
+                if rest.is_multiple_of(REST_HEALING_TURNS) && player.health() < player.max_health()
+                {
+                    debug!("player rested; restoring 1 HP");
+                    player.heal(1);
+                    self.session.stats.health_restored_resting += 1;
+                    effect.message(Message::Regenerated(1));
+                }
+            }
         }
     }
 }
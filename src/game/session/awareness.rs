@@ -0,0 +1,118 @@
+//! # Awareness
+//!
+//! This module exposes the awareness pass, which tells the player about the enemies nearby
+
+use super::{Effect, Message, PlayerState, Session};
+use crate::audio::Sound;
+
+/// Enemies further than this amount of rooms can't be heard
+const HEARING_DISTANCE: u8 = 2;
+
+/// Awareness reports the enemies the player can hear from the nearby rooms
+pub struct Awareness<'a> {
+    session: &'a Session,
+}
+
+impl<'a> Awareness<'a> {
+    /// Instantiate a new Awareness
+    pub fn new(session: &'a Session) -> Self {
+        Self { session }
+    }
+
+    /// Report the enemies the player can hear.
+    /// Enemies in the adjacent rooms can be heard clearly, so a sound is played too
+    pub fn play(&self, effect: &mut Effect) {
+        if self.session.player.state() != PlayerState::Explore {
+            return;
+        }
+        let player = self.session.maze.player;
+        let mut rooms: Vec<(u32, u8)> = self
+            .session
+            .maze
+            .distances_from(player, HEARING_DISTANCE)
+            .into_iter()
+            .filter(|(room, distance)| {
+                *distance > 0 && self.session.maze.room(*room).unwrap().has_enemies()
+            })
+            .collect();
+        rooms.sort_by_key(|(room, distance)| (*distance, *room));
+        let mut enemy_nearby = false;
+        for (room, distance) in rooms {
+            let Some(through) = self.adjacent_room_towards(room, distance) else {
+                continue;
+            };
+            for enemy in self.session.maze.room(room).unwrap().enemies.iter() {
+                debug!(
+                    "player heard {:?} at {} rooms through room {}",
+                    enemy, distance, through
+                );
+                effect.message(Message::EnemyHeard(*enemy, through, distance));
+            }
+            enemy_nearby |= distance == 1;
+        }
+        if enemy_nearby {
+            effect.sound(Sound::EnemyNearby);
+        }
+    }
+
+    /// Get the room adjacent to the player the noise coming from `room`, `distance` rooms away, passes through
+    fn adjacent_room_towards(&self, room: u32, distance: u8) -> Option<u32> {
+        if distance == 1 {
+            return Some(room);
+        }
+        let mut adjacent_rooms: Vec<u32> = self
+            .session
+            .maze
+            .adjacent_rooms(self.session.maze.player)
+            .into_iter()
+            .map(|(id, _)| id)
+            .filter(|id| {
+                self.session
+                    .maze
+                    .distances_from(*id, distance - 1)
+                    .contains_key(&room)
+            })
+            .collect();
+        adjacent_rooms.sort();
+        adjacent_rooms.first().copied()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::game::entity::{Daemon, Enemy, Shadow};
+
+    #[test]
+    fn should_hear_nearby_enemies() {
+        let mut session = Session::mock();
+        session.maze.room_mut(2).unwrap().enemies.clear();
+        session.maze.room_mut(1).unwrap().enemies = vec![Enemy::Shadow(Shadow::new(3))];
+        session.maze.room_mut(4).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(4))];
+        session.maze.room_mut(5).unwrap().enemies = vec![Enemy::Daemon(Daemon::new(4))];
+        let mut effect = Effect::default();
+        Awareness::new(&session).play(&mut effect);
+        assert_eq!(
+            effect.messages,
+            vec![
+                Message::EnemyHeard(Enemy::Shadow(Shadow::new(3)), 1, 1),
+                Message::EnemyHeard(Enemy::Daemon(Daemon::new(4)), 2, 2),
+            ]
+        );
+        assert_eq!(effect.sounds, vec![Sound::EnemyNearby]);
+        // far enemies are heard without sound
+        session.maze.room_mut(1).unwrap().enemies.clear();
+        let mut effect = Effect::default();
+        Awareness::new(&session).play(&mut effect);
+        assert_eq!(effect.messages.len(), 1);
+        assert!(effect.sounds.is_empty());
+        // nothing can be heard while fighting
+        session.player.start_fighting();
+        let mut effect = Effect::default();
+        Awareness::new(&session).play(&mut effect);
+        assert!(effect.messages.is_empty());
+    }
+}
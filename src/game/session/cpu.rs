@@ -2,15 +2,24 @@
 //!
 //! This module expose the CPU player
 
+use std::collections::HashMap;
+
 use rand::rngs::ThreadRng;
 
-use super::{Effect, PlayerState, Session};
+use super::{Effect, PlayerState, Session, COUNTERATTACK_PROBABILITY};
 use crate::audio::Sound;
-use crate::game::entity::Enemy;
+use crate::game::entity::{Armor, Artifact, Enemy, StatusEffect};
 use crate::game::session::Message;
 use crate::game::Hp;
 use crate::utils::random;
 
+/// Damage absorbed when the player is defending
+const DEFEND_DAMAGE_REDUCTION: Hp = 2;
+/// Probability for an npc to move to an adjacent room each turn
+const NPC_MOVE_PROBABILITY: u8 = 50;
+/// The troll heart regenerates 1 HP every this amount of turns
+const TROLL_HEART_REGENERATION_TURNS: u64 = 20;
+
 struct EnemyHit {
     damage: Hp,
     missed: bool,
@@ -20,35 +29,124 @@ struct EnemyHit {
 /// Cpu plays the CPU actions
 pub struct Cpu<'a> {
     session: &'a mut Session,
+    /// Whether the player was resting when the turn started; the enemies joining the player get free hits then
+    caught_resting: bool,
 }
 
 impl<'a> Cpu<'a> {
     /// Instantiate a new Cpu
     pub fn new(session: &'a mut Session) -> Self {
-        Self { session }
+        Self {
+            session,
+            caught_resting: false,
+        }
     }
 
     /// Play turn for cpu
     pub fn play(&mut self, effect: &mut Effect) {
+        // NOTE: the first enemy joining the player interrupts the rest
+        self.caught_resting = self.session.rest.is_some();
+        self.regenerate_player(effect);
         self.fight_player(effect);
         self.move_exploring_enemies(effect);
+        self.move_npcs(effect);
+    }
+
+    /// Regenerate the player carrying the troll heart
+    fn regenerate_player(&mut self, effect: &mut Effect) {
+        let player = &mut self.session.player;
+        if player.has_artifact(Artifact::TrollHeart)
+            && self
+                .session
+                .stats
+                .turn
+                .is_multiple_of(TROLL_HEART_REGENERATION_TURNS)
+            && player.health() < player.max_health()
+        {
+            debug!("troll heart regenerates player; restoring 1 HP");
+            player.heal(1);
+            effect.message(Message::Regenerated(1));
+        }
     }
 
-    /// Take enemy in the same room of the player
-    /// and deal damage to player
+    /// Take enemies in the same room of the player
+    /// and make each of them deal damage to player
     fn fight_player(&mut self, effect: &mut Effect) {
-        let enemy = match self.session.maze.fighting_enemy() {
-            None => return,
-            Some(e) => e,
-        };
-        // if player state is NOT FIGHTING; it means the player has just joined; so no damage has to be dealt
+        // NOTE: these flags last for a single turn
+        let defending = std::mem::take(&mut self.session.defending);
+        let mut enemy_asleep = self.session.enemy_asleep.take();
+        if self.session.maze.fighting_enemies().is_empty() {
+            return;
+        }
+        // if player state is NOT FIGHTING; it means the player has just joined; so no damage has to be dealt,
+        // unless the player has been caught resting
         if self.session.player.state() != PlayerState::Fight {
-            let fighting_enemy = *enemy;
-            self.start_player_fight(fighting_enemy, effect);
+            let mut target = 0;
+            while let Some(enemy) = self.session.maze.fighting_enemies().get(target).copied() {
+                let enemies = self.session.maze.fighting_enemies().len();
+                self.start_player_fight(target, enemy, effect);
+                if self.session.player.is_dead() || self.session.won {
+                    return;
+                }
+                if self.session.maze.fighting_enemies().len() == enemies {
+                    target += 1;
+                }
+            }
             return;
         }
+        let mut target = 0;
+        while let Some(enemy) = self.session.maze.fighting_enemies().get(target).copied() {
+            if enemy_asleep == Some(target) {
+                debug!("enemy is asleep; skip attack");
+                effect.message(Message::EnemySleeping);
+                target += 1;
+                continue;
+            }
+            let enemies = self.session.maze.fighting_enemies().len();
+            self.enemy_attack(target, &enemy, defending, effect);
+            if self.session.player.is_dead() || self.session.won {
+                return;
+            }
+            // if the enemy has been killed by a counterattack, the next one takes its place
+            if self.session.maze.fighting_enemies().len() == enemies {
+                target += 1;
+            } else if let Some(asleep) = enemy_asleep.filter(|x| *x > target) {
+                enemy_asleep = Some(asleep - 1);
+            }
+        }
+    }
+
+    /// Make the fighting enemy at `target` attack the player
+    fn enemy_attack(&mut self, target: usize, enemy: &Enemy, defending: bool, effect: &mut Effect) {
         // calculate damage to deal, based on enemy type
-        let hit = Self::deal_damage(enemy);
+        let accuracy_malus = self
+            .session
+            .player
+            .equipment
+            .amulet()
+            .map(|x| x.enemy_accuracy_malus())
+            .unwrap_or_default();
+        let mut hit = Self::deal_damage(enemy, accuracy_malus);
+        let critical_hit_status_effect = enemy.critical_hit_status_effect();
+        if self.session.player.has_status_effect(StatusEffect::Blessed) {
+            debug!("player is blessed; damage reduced by 1");
+            hit.damage = hit.damage.saturating_sub(1);
+        }
+        if defending {
+            debug!(
+                "player is defending; damage reduced by {}",
+                DEFEND_DAMAGE_REDUCTION
+            );
+            hit.damage = hit.damage.saturating_sub(DEFEND_DAMAGE_REDUCTION);
+        }
+        if !hit.missed && self.session.player.equipment.armor().is_some() {
+            debug!("armor absorbed {} HP", Armor::DAMAGE_REDUCTION);
+            hit.damage = hit.damage.saturating_sub(Armor::DAMAGE_REDUCTION);
+            if let Some(broken) = self.session.player.equipment.wear_armor() {
+                debug!("{:?} has broken", broken);
+                effect.message(Message::ItemBroken(broken));
+            }
+        }
         if hit.missed {
             debug!("enemy missed the hit");
             effect.message(Message::EnemyMissed);
@@ -62,21 +160,46 @@ impl<'a> Cpu<'a> {
             // report damage
             effect.message(Message::DamageSuffered(hit.damage, hit.critical_hit));
             effect.sound(Sound::EnemyAttack);
+            if let Some((status, turns)) = critical_hit_status_effect.filter(|_| hit.critical_hit) {
+                debug!("critical hit inflicted {:?} to player", status);
+                self.session.player.apply_status_effect(status, turns);
+                effect.message(Message::StatusEffectApplied(status));
+            }
             // check if player is dead
             if self.session.player.is_dead() {
                 debug!("player is dead. Game over...");
                 effect.sound(Sound::PlayerDead);
                 effect.message(Message::PlayerDead);
+                return;
             }
         }
+        if defending && random::happens(&mut rand::rng(), COUNTERATTACK_PROBABILITY) {
+            self.counterattack(target, effect);
+        }
+    }
+
+    /// Player counterattacks the enemy at `target` while defending
+    fn counterattack(&mut self, target: usize, effect: &mut Effect) {
+        let enemy = &self.session.maze.fighting_enemies()[target];
+        let damage_dealt = if enemy.is_vulnerable_to(self.session.player.equipment.weapon()) {
+            1 + self.session.player.damage_bonus()
+        } else {
+            0
+        };
+        debug!("player counterattacked dealing {} HP", damage_dealt);
+        effect.message(Message::Counterattack(damage_dealt));
+        effect.sound(Sound::PlayerAttack);
+        self.session
+            .damage_fighting_enemy(target, damage_dealt, effect);
     }
 
     /// Calculate damage to deal based on random and enemy type
-    /// The bool defines whether is critical hit
-    fn deal_damage(enemy: &Enemy) -> EnemyHit {
+    /// The bool defines whether is critical hit.
+    /// Enemy accuracy is decreased by `accuracy_malus`
+    fn deal_damage(enemy: &Enemy, accuracy_malus: u8) -> EnemyHit {
         let mut rng = rand::rng();
         let critical_hit = random::happens(&mut rng, 10);
-        let will_hit = random::happens(&mut rng, enemy.accuracy());
+        let will_hit = random::happens(&mut rng, enemy.accuracy().saturating_sub(accuracy_malus));
         let base_attack: Hp = enemy.base_attack();
         let damage_dealt = if critical_hit {
             base_attack.saturating_add(1)
@@ -92,74 +215,163 @@ impl<'a> Cpu<'a> {
 
     /// Move enemies which ARE not in fight
     fn move_exploring_enemies(&mut self, effect: &mut Effect) {
-        let rooms_with_exploring_enemies: Vec<u32> = self
+        let rooms_with_exploring_enemies: Vec<(u32, usize)> = self
             .session
             .maze
             .rooms()
             .into_iter()
-            .filter(|(_, room)| room.enemy.is_some())
+            .filter(|(_, room)| room.has_enemies())
             .filter(|(id, _)| *id != self.session.maze.player)
-            .map(|(id, _)| id)
+            .map(|(id, room)| (id, room.enemies.len()))
             .collect();
         debug!(
-            "there are still {} exploring enemies",
+            "there are still {} rooms with exploring enemies",
             rooms_with_exploring_enemies.len()
         );
+        // rooms reached by the noise made by the player, with their distance from the player
+        let noise = self
+            .session
+            .maze
+            .distances_from(self.session.maze.player, self.session.noise);
         // iter rooms
         let mut rng = rand::rng();
-        for room in rooms_with_exploring_enemies.into_iter() {
-            self.move_enemy_at(&mut rng, room, effect);
+        for (room, enemies) in rooms_with_exploring_enemies.into_iter() {
+            // NOTE: enemies which joined the room during this turn are pushed at the end, so they won't move again;
+            // enemies which couldn't be moved stay in front
+            let mut enemy = 0;
+            for _ in 0..enemies {
+                if !self.move_enemy_at(&mut rng, room, enemy, &noise, effect) {
+                    enemy += 1;
+                }
+            }
         }
     }
 
-    /// Move enemy located at room u32
-    fn move_enemy_at(&mut self, rng: &mut ThreadRng, room: u32, effect: &mut Effect) {
+    /// Move the `enemy`-th enemy located at room u32.
+    /// If the enemy hears the `noise`, it moves toward the player.
+    /// Returns whether the enemy has been moved
+    fn move_enemy_at(
+        &mut self,
+        rng: &mut ThreadRng,
+        room: u32,
+        enemy: usize,
+        noise: &HashMap<u32, u8>,
+        effect: &mut Effect,
+    ) -> bool {
+        if !self.session.maze.room(room).unwrap().enemies[enemy].wanders() {
+            return false;
+        }
         // get adjacent rooms to enemy room
-        let adjacent_rooms_without_enemies: Vec<u32> = self
+        let mut adjacent_rooms_with_space: Vec<u32> = self
             .session
             .maze
             .adjacent_rooms(room)
             .into_iter()
-            .filter(|(_, room)| room.enemy.is_none())
+            .filter(|(_, room)| !room.is_crowded())
             .map(|(id, _)| id)
             .collect();
         debug!(
-            "enemy at {} has {} adjacent rooms without enemies",
+            "enemy at {} has {} adjacent rooms which can host it",
             room,
-            adjacent_rooms_without_enemies.len()
+            adjacent_rooms_with_space.len()
         );
-        if adjacent_rooms_without_enemies.is_empty() {
+        if adjacent_rooms_with_space.is_empty() {
             debug!(
-                "there's no adjacent room without enemies; so the enemy at {} won't be moved",
+                "there's no adjacent room with space; so the enemy at {} won't be moved",
                 room
             );
-        } else {
-            // choose room to move the enemy to
-            let new_enemy_room = *random::choice(rng, &adjacent_rooms_without_enemies);
-            debug!("moved enemy at {} to {}", room, new_enemy_room);
-            let enemy = self
+            return false;
+        }
+        if let Some(distance) = noise.get(&room) {
+            let closer_rooms: Vec<u32> = adjacent_rooms_with_space
+                .iter()
+                .copied()
+                .filter(|x| noise.get(x).is_some_and(|x| x < distance))
+                .collect();
+            if !closer_rooms.is_empty() {
+                debug!("enemy at {} heard a noise; moving toward the player", room);
+                adjacent_rooms_with_space = closer_rooms;
+            }
+        }
+        // choose room to move the enemy to
+        let new_enemy_room = *random::choice(rng, &adjacent_rooms_with_space);
+        debug!("moved enemy at {} to {}", room, new_enemy_room);
+        let enemy = self
+            .session
+            .maze
+            .room_mut(room)
+            .unwrap()
+            .enemies
+            .remove(enemy);
+        let new_room = self.session.maze.room_mut(new_enemy_room).unwrap();
+        new_room.enemies.push(enemy);
+        let target = new_room.enemies.len() - 1;
+        // check if enemy has joined player
+        if new_enemy_room == self.session.maze.player {
+            debug!("enemy {:?} has joined the room of the player", enemy);
+            self.start_player_fight(target, enemy, effect);
+        }
+        true
+    }
+
+    /// Move the npcs which are not in the player's room
+    fn move_npcs(&mut self, effect: &mut Effect) {
+        let mut rooms_with_npcs: Vec<u32> = self
+            .session
+            .maze
+            .rooms()
+            .into_iter()
+            .filter(|(id, room)| {
+                room.npc.as_ref().is_some_and(|x| x.wanders()) && *id != self.session.maze.player
+            })
+            .map(|(id, _)| id)
+            .collect();
+        rooms_with_npcs.sort();
+        let mut rng = rand::rng();
+        for room in rooms_with_npcs {
+            if !random::happens(&mut rng, NPC_MOVE_PROBABILITY) {
+                continue;
+            }
+            let free_rooms: Vec<u32> = self
+                .session
+                .maze
+                .adjacent_rooms(room)
+                .into_iter()
+                .filter(|(_, room)| room.npc.is_none())
+                .map(|(id, _)| id)
+                .collect();
+            if free_rooms.is_empty() {
+                continue;
+            }
+            let new_room = *random::choice(&mut rng, &free_rooms);
+            debug!("moved npc at {} to {}", room, new_room);
+            let npc = self
                 .session
                 .maze
                 .room_mut(room)
                 .unwrap()
-                .enemy
+                .npc
                 .take()
                 .unwrap();
-            let new_room = self.session.maze.room_mut(new_enemy_room).unwrap();
-            new_room.enemy = Some(enemy);
-            // check if enemy has joined player
-            if new_enemy_room == self.session.maze.player {
-                debug!("enemy {:?} has joined the room of the player", enemy);
-                self.start_player_fight(enemy, effect);
+            if new_room == self.session.maze.player {
+                debug!("the npc has joined the room of the player");
+                effect.message(Message::NpcMet(npc.clone()));
             }
+            self.session.maze.room_mut(new_room).unwrap().npc = Some(npc);
         }
     }
 
-    /// Start player fight
-    fn start_player_fight(&mut self, enemy: Enemy, effect: &mut Effect) {
+    /// Start player fight with the fighting enemy at `target`.
+    /// Each enemy catching the player resting gets a free hit before the player wakes up
+    fn start_player_fight(&mut self, target: usize, enemy: Enemy, effect: &mut Effect) {
         effect.message(Message::EnemyApproaching(enemy));
         effect.sound(Sound::EnemyApproaching);
         // put player into fight
-        self.session.player.start_fighting();
+        self.session.start_fighting();
+        if self.caught_resting && !self.session.player.is_dead() {
+            debug!("player has been caught resting by {:?}", enemy);
+            effect.message(Message::SurprisedWhileResting);
+            self.enemy_attack(target, &enemy, false, effect);
+        }
     }
 }
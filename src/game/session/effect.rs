@@ -2,8 +2,9 @@
 //!
 //! The effect defines the outcome for a turn played
 
+use super::Version;
 use crate::audio::Sound;
-use crate::game::entity::{Enemy, Item, Potion};
+use crate::game::entity::{Enemy, Item, LightLevel, Npc, Potion, StatusEffect};
 use crate::game::Hp;
 use crate::utils::room_resolver::Direction;
 
@@ -19,23 +20,66 @@ pub struct Effect {
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Defines a message to report to the UI
 pub enum Message {
-    ArmorEquipped,
     /// Item collected by the player
     ItemCollected(Item),
+    /// Item dropped by the player in the room
+    ItemDropped(Item),
+    /// The inventory is full; the player can't carry any other item
+    InventoryFull,
+    /// The player can't carry any more of the item
+    ItemStackFull(Item),
+    /// Item has been equipped by the player
+    ItemEquipped(Item),
+    /// Item has been removed from the equipment
+    ItemUnequipped(Item),
+    /// Equipped item has broken
+    ItemBroken(Item),
     /// Item used by the player
     ItemUsed(Item),
+    /// The player counterattacked while defending, dealing HP to the enemy
+    Counterattack(Hp),
+    /// The player landed a critical hit
+    CriticalHit,
     /// A damage in HP inflicted by the player to the enemy
     DamageDealt(Hp),
     /// A damage in HP inflicted by the enemy to the player; bool (is critical?)
     DamageSuffered(Hp, bool),
     /// An enemy has approached the player in his room
     EnemyApproaching(Enemy),
+    /// An enemy has been heard by the player; (enemy, adjacent room the noise comes from, distance in rooms)
+    EnemyHeard(Enemy, u32, u8),
+    /// The player raised the guard
+    Defending,
+    /// The boss entered a new phase of the fight
+    BossPhaseChanged(u8),
+    /// Don Maze has been defeated
+    DonMazeDefeated,
     /// The enemy has been defeated
     EnemyDefeated,
+    /// The enemy has been put asleep by a thrown potion
+    EnemyFellAsleep,
+    /// The enemy has been healed by HP by a thrown potion
+    EnemyHealed(Hp),
+    /// The defeated enemy dropped an item in the room
+    EnemyDroppedLoot(Item),
     /// Enemy missed attack
     EnemyMissed,
+    /// The enemy is asleep and skips its attack
+    EnemySleeping,
     /// Enemy died or moved due to talisman
     EnemyVanished,
+    /// Experience gained by the player
+    ExperienceGained(u32),
+    /// The ingredients brewed together don't make any potion
+    BrewFailed,
+    /// The compass points to the adjacent room on the shortest path to the exit; `None` if the player is at the exit
+    CompassPoint(Option<u32>),
+    /// The owl's eye sensed an enemy in an adjacent room
+    EnemySensed(u32, Enemy),
+    /// The chest has been inspected and it's safe
+    ChestInspected,
+    /// The lock of the chest has been forced; the amount of turns before the lock gives way
+    ChestForced(u8),
     /// Escape try failed
     EscapeFailed,
     /// Escape try succeeded
@@ -44,19 +88,75 @@ pub enum Message {
     FallAsleep,
     /// Game saved
     GameSaved,
+    /// Gold coins collected by the player
+    GoldCollected(u32),
+    /// The player has been healed by an npc
+    Healed(Hp),
+    /// Item bought from the merchant for gold
+    ItemBought(Item, u16),
+    /// Item given to the player by an npc
+    ItemReceived(Item),
+    /// The item can't be used right now
+    ItemUseless(Item),
+    /// Item sold to the merchant for gold
+    ItemSold(Item, u16),
     /// Leave maze and win
     LeaveMaze,
+    /// The player reached a new level
+    LevelUp(u8),
+    /// The torch light level changed
+    LightChanged(LightLevel),
+    /// The lock of the chest has been picked
+    LockPicked,
+    /// The chest was a mimic, which attacks the player
+    MimicRevealed,
+    /// The map scroll revealed the amount of rooms beyond the adjacent room and whether the exit is among them
+    MapRevealed(u32, u8, bool),
+    /// A trap in the chest has been found and disarmed
+    TrapDisarmed,
+    /// A trap in the chest sprung, dealing HP to the player
+    TrapSprung(Hp),
+    /// The player and an npc are in the same room
+    NpcMet(Npc),
+    /// The npc has already triggered its dialogue effect
+    NothingToGive,
     /// Room changed
     RoomChanged(Direction),
     /// pleayer is dead
     PlayerDead,
+    /// The player missed the attack
+    PlayerMissed,
+    /// The player lost HP due to poison
+    PoisonDamage(Hp),
+    /// A potion has been brewed
+    PotionBrewed(Potion),
     /// A potion has been drunk
     PotionDrunk(Potion),
+    /// A potion has been thrown at the enemy
+    PotionThrown(Potion),
+    /// The player restored HP due to regeneration
+    Regenerated(Hp),
+    /// The player started resting for the provided amount of turns
+    RestStarted(u8),
+    /// An enemy caught the player resting
+    SurprisedWhileResting,
+    /// The relics have been combined into the item
+    RelicsCombined(Item),
+    /// The relics can't be combined; some are missing
+    RelicsMissing(u8),
+    /// The opened save has been migrated from an older version
+    SaveMigrated(Version),
+    /// Sonar can't be used in the dark
+    SonarInDarkness,
     /// Sonar reveal
     Reveal(u32, Reveal),
     /// Sonar revealed nothing
     RevealNothing,
     Sleeping,
+    /// A status effect has been applied to the player
+    StatusEffectApplied(StatusEffect),
+    /// A status effect applied to the player has expired
+    StatusEffectExpired(StatusEffect),
     /// Wake up from sleeping
     WakeUp,
 }
@@ -90,9 +190,9 @@ mod test {
     #[test]
     fn should_make_effect() {
         let mut effect = Effect::default();
-        effect.message(Message::ArmorEquipped);
+        effect.message(Message::GameSaved);
         effect.sound(Sound::DrinkPotion);
-        assert_eq!(effect.messages, vec![Message::ArmorEquipped]);
+        assert_eq!(effect.messages, vec![Message::GameSaved]);
         assert_eq!(effect.sounds, vec![Sound::DrinkPotion]);
     }
 }
@@ -0,0 +1,347 @@
+//! # Migration
+//!
+//! Step-by-step migrations of saved sessions from older versions to the current one
+
+use serde_json::{json, Error as JsonError, Map, Value};
+use thiserror::Error;
+
+use super::Version;
+use crate::game::entity::{DonMaze, Enemy, Item};
+
+pub type MigrationResult<T> = Result<T, MigrationError>;
+
+/// Session migration error
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("serialization error: {0}")]
+    Json(JsonError),
+    #[error("malformed save: {0}")]
+    Malformed(&'static str),
+    #[error("missing game version")]
+    MissingVersion,
+    #[error("unsupported game version: {0}")]
+    UnsupportedVersion(String),
+}
+
+impl From<JsonError> for MigrationError {
+    fn from(e: JsonError) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Migrate the saved session to the current version, applying a step for each version in between.
+/// Returns the version the session has been migrated from, if it wasn't current
+pub fn migrate(save: &mut Value) -> MigrationResult<Option<Version>> {
+    let from = read_version(save)?;
+    let mut version = from;
+    while let Some(next) = version.next() {
+        debug!("migrating session from {:?} to {:?}", version, next);
+        match version {
+            Version::V010 => migrate_v010(save)?,
+            Version::V020 => unreachable!("V020 is the current version"),
+        }
+        version = next;
+    }
+    save["version"] = json!(version);
+    Ok(Some(from).filter(|x| *x != Version::CURRENT))
+}
+
+/// Read the version of the saved session
+fn read_version(save: &Value) -> MigrationResult<Version> {
+    let version = save.get("version").ok_or(MigrationError::MissingVersion)?;
+    serde_json::from_value(version.clone())
+        .map_err(|_| MigrationError::UnsupportedVersion(version.to_string()))
+}
+
+/// Get the object at `key` in `value`
+fn object_mut<'a>(
+    value: &'a mut Value,
+    key: &'static str,
+) -> MigrationResult<&'a mut Map<String, Value>> {
+    value
+        .get_mut(key)
+        .and_then(Value::as_object_mut)
+        .ok_or(MigrationError::Malformed(key))
+}
+
+/// V010 -> V020:
+///
+/// - the oldest saves kept the visited rooms and their amount in the stats
+/// - the player sleep counter has been replaced by the `Asleep` status effect
+/// - rooms held a single optional `enemy` instead of a list of enemies
+/// - enemies had no max health and Don Maze had no health at all
+/// - the inventory was a map of item keys to their quantity
+fn migrate_v010(save: &mut Value) -> MigrationResult<()> {
+    let stats = object_mut(save, "stats")?;
+    stats.remove("rooms_explored");
+    let visited_rooms = stats.remove("visited_rooms");
+    let session = save
+        .as_object_mut()
+        .ok_or(MigrationError::Malformed("session"))?;
+    if !session.contains_key("visited_rooms") {
+        session.insert(
+            "visited_rooms".to_string(),
+            visited_rooms.unwrap_or_else(|| json!([])),
+        );
+    }
+    let player = object_mut(save, "player")?;
+    let sleep_counter = player
+        .remove("sleep_counter")
+        .and_then(|x| x.as_u64())
+        .unwrap_or_default();
+    if sleep_counter > 0 && player.get("state") == Some(&json!("Asleep")) {
+        player.insert(
+            "status_effects".to_string(),
+            json!([{ "effect": "Asleep", "turns": sleep_counter.min(u8::MAX as u64) }]),
+        );
+    }
+    migrate_v010_inventory(save)?;
+    migrate_v010_rooms(save)
+}
+
+/// Turn the legacy single optional enemy of each room into a list of enemies
+fn migrate_v010_rooms(save: &mut Value) -> MigrationResult<()> {
+    let rooms = save
+        .pointer_mut("/maze/rooms")
+        .and_then(Value::as_object_mut)
+        .ok_or(MigrationError::Malformed("rooms"))?;
+    for room in rooms.values_mut() {
+        let room = room
+            .as_object_mut()
+            .ok_or(MigrationError::Malformed("room"))?;
+        let enemy = room.remove("enemy").filter(|x| !x.is_null());
+        if !room.contains_key("enemies") {
+            room.insert(
+                "enemies".to_string(),
+                json!(enemy.into_iter().collect::<Vec<_>>()),
+            );
+        }
+        let enemies = room
+            .get_mut("enemies")
+            .and_then(Value::as_array_mut)
+            .ok_or(MigrationError::Malformed("enemies"))?;
+        for enemy in enemies.iter_mut() {
+            migrate_v010_enemy(enemy)?;
+        }
+    }
+    Ok(())
+}
+
+/// Give enemies their max health and Don Maze its health
+fn migrate_v010_enemy(enemy: &mut Value) -> MigrationResult<()> {
+    if enemy.get("type") == Some(&json!("DonMaze")) {
+        if enemy.get("health").is_none() {
+            *enemy = serde_json::to_value(Enemy::DonMaze(DonMaze::default()))?;
+        }
+        return Ok(());
+    }
+    let enemy = enemy
+        .as_object_mut()
+        .ok_or(MigrationError::Malformed("enemy"))?;
+    if !enemy.contains_key("max_health") {
+        let health = enemy
+            .get("health")
+            .cloned()
+            .ok_or(MigrationError::Malformed("health"))?;
+        enemy.insert("max_health".to_string(), health);
+    }
+    Ok(())
+}
+
+/// Turn the legacy inventory map of item keys to quantity into a list of items.
+/// Unknown items are discarded
+fn migrate_v010_inventory(save: &mut Value) -> MigrationResult<()> {
+    let items = save
+        .pointer_mut("/player/inventory/items")
+        .ok_or(MigrationError::Malformed("inventory"))?;
+    let legacy = match items.as_object() {
+        Some(legacy) => legacy,
+        None => return Ok(()),
+    };
+    debug!("migrating legacy inventory with {} items", legacy.len());
+    let mut keys: Vec<(u32, &Value)> = legacy
+        .iter()
+        .filter_map(|(key, qty)| match key.parse() {
+            Ok(key) => Some((key, qty)),
+            Err(_) => {
+                warn!("invalid item key in inventory: {}", key);
+                None
+            }
+        })
+        .collect();
+    keys.sort_by_key(|(key, _)| *key);
+    let mut migrated = Vec::with_capacity(keys.len());
+    for (key, qty) in keys {
+        match Item::from_key(key) {
+            Some(item) => migrated.push(json!([serde_json::to_value(item)?, qty])),
+            None => warn!("unknown item key in inventory: {}; item discarded", key),
+        }
+    }
+    *items = Value::Array(migrated);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::game::entity::{Daemon, Potion};
+    use crate::game::inventory::Inventory;
+
+    /// A V010 save with the given player and rooms
+    fn v010_save(player: Value, rooms: Value) -> Value {
+        json!({
+            "version": "V010",
+            "maze": { "rooms": rooms },
+            "player": player,
+            "stats": {},
+        })
+    }
+
+    #[test]
+    fn should_not_migrate_current_version() {
+        let mut save = json!({ "version": "V020", "stats": {} });
+        let expected = save.clone();
+        assert_eq!(migrate(&mut save).unwrap(), None);
+        assert_eq!(save, expected);
+    }
+
+    #[test]
+    fn should_migrate_from_v010() {
+        let mut save = json!({
+            "version": "V010",
+            "maze": { "rooms": {} },
+            "player": { "state": "Asleep", "sleep_counter": 3, "inventory": { "items": [] } },
+            "stats": { "rooms_explored": 2, "turn": 4, "visited_rooms": [0, 1] },
+        });
+        assert_eq!(migrate(&mut save).unwrap(), Some(Version::V010));
+        assert_eq!(
+            save,
+            json!({
+                "version": "V020",
+                "maze": { "rooms": {} },
+                "player": {
+                    "state": "Asleep",
+                    "inventory": { "items": [] },
+                    "status_effects": [{ "effect": "Asleep", "turns": 3 }]
+                },
+                "stats": { "turn": 4 },
+                "visited_rooms": [0, 1],
+            })
+        );
+    }
+
+    #[test]
+    fn should_keep_visited_rooms_when_migrating_from_v010() {
+        let mut save = v010_save(
+            json!({ "state": "Explore", "sleep_counter": 0, "inventory": { "items": [] } }),
+            json!({}),
+        );
+        save["visited_rooms"] = json!([0, 4]);
+        assert_eq!(migrate(&mut save).unwrap(), Some(Version::V010));
+        assert_eq!(save["visited_rooms"], json!([0, 4]));
+        assert_eq!(
+            save["player"],
+            json!({ "state": "Explore", "inventory": { "items": [] } })
+        );
+    }
+
+    #[test]
+    fn should_migrate_legacy_single_enemy_rooms_from_v010() {
+        let mut save = v010_save(
+            json!({ "state": "Explore", "inventory": { "items": [] } }),
+            json!({
+                "0": { "enemy": { "type": "Daemon", "health": 3 }, "is_exit": false, "item": null },
+                "1": { "enemy": null, "is_exit": false, "item": null },
+                "2": { "is_exit": true, "item": null },
+            }),
+        );
+        migrate(&mut save).unwrap();
+        let rooms = &save["maze"]["rooms"];
+        let enemies: Vec<Enemy> = serde_json::from_value(rooms["0"]["enemies"].clone()).unwrap();
+        assert_eq!(enemies, vec![Enemy::Daemon(Daemon::new(3))]);
+        assert_eq!(rooms["0"].get("enemy"), None);
+        assert_eq!(
+            rooms["1"],
+            json!({ "enemies": [], "is_exit": false, "item": null })
+        );
+        assert_eq!(
+            rooms["2"],
+            json!({ "enemies": [], "is_exit": true, "item": null })
+        );
+    }
+
+    #[test]
+    fn should_migrate_legacy_enemies_from_v010() {
+        let mut save = v010_save(
+            json!({ "state": "Explore", "inventory": { "items": [] } }),
+            json!({
+                "0": {
+                    "enemies": [
+                        { "type": "DonMaze" },
+                        { "type": "Shadow", "health": 2 },
+                        { "type": "Mimic", "health": 5, "max_health": 6 },
+                    ],
+                    "is_exit": false,
+                    "item": null
+                },
+            }),
+        );
+        migrate(&mut save).unwrap();
+        let enemies: Vec<Enemy> =
+            serde_json::from_value(save["maze"]["rooms"]["0"]["enemies"].clone()).unwrap();
+        assert_eq!(enemies[0], Enemy::DonMaze(DonMaze::default()));
+        assert_eq!(enemies[1].health(), 2);
+        assert_eq!(enemies[1].max_health(), 2);
+        assert_eq!(enemies[2].health(), 5);
+        assert_eq!(enemies[2].max_health(), 6);
+    }
+
+    #[test]
+    fn should_migrate_legacy_inventory_from_v010() {
+        let mut save = v010_save(
+            json!({
+                "state": "Explore",
+                "inventory": { "items": { "258": 1, "3": 2, "1": 1, "200": 3, "foo": 1 } }
+            }),
+            json!({}),
+        );
+        migrate(&mut save).unwrap();
+        let inventory: Inventory =
+            serde_json::from_value(save["player"]["inventory"].clone()).unwrap();
+        assert_eq!(
+            inventory.items(),
+            vec![
+                (Item::Armor(Default::default()), 1),
+                (Item::Sonar, 2),
+                (Item::Potion(Potion::UnicornElixir), 1)
+            ]
+        );
+        let mut save = v010_save(
+            json!({ "state": "Explore", "inventory": { "items": {} } }),
+            json!({}),
+        );
+        migrate(&mut save).unwrap();
+        let inventory: Inventory =
+            serde_json::from_value(save["player"]["inventory"].clone()).unwrap();
+        assert_eq!(inventory, Inventory::default());
+    }
+
+    #[test]
+    fn should_fail_migrating_unsupported_version() {
+        assert!(matches!(
+            migrate(&mut json!({ "version": "V990" })),
+            Err(MigrationError::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            migrate(&mut json!({})),
+            Err(MigrationError::MissingVersion)
+        ));
+        assert!(matches!(
+            migrate(&mut json!({ "version": "V010" })),
+            Err(MigrationError::Malformed("stats"))
+        ));
+    }
+}
@@ -15,6 +15,10 @@ pub struct Stats {
     pub last_turn: DateTime<Local>,
     pub slept_for_turns: u64,
     pub turn: u64,
+    #[serde(default)]
+    pub times_rested: u64,
+    #[serde(default)]
+    pub health_restored_resting: u64,
 }
 
 impl Default for Stats {
@@ -28,6 +32,8 @@ impl Default for Stats {
             last_turn: Local::now(),
             slept_for_turns: 0,
             turn: 0,
+            times_rested: 0,
+            health_restored_resting: 0,
         }
     }
 }
@@ -2,12 +2,27 @@
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Version {
     V010,
+    V020,
 }
 
 impl Version {
-    /// Returns whether game version is compatible
-    pub fn is_compatible(&self) -> bool {
-        [Self::V010].contains(self)
+    /// Version of the sessions written by the current game
+    pub const CURRENT: Version = Version::V020;
+
+    /// Returns the version name
+    pub fn name(&self) -> &str {
+        match self {
+            Self::V010 => "0.1.0",
+            Self::V020 => "0.2.0",
+        }
+    }
+
+    /// Returns the version which follows this one, if this is not the current version
+    pub fn next(&self) -> Option<Self> {
+        match self {
+            Self::V010 => Some(Self::V020),
+            Self::V020 => None,
+        }
     }
 }
 
@@ -19,8 +34,9 @@ mod test {
     use super::*;
 
     #[test]
-    fn should_tell_whether_version_is_compatible() {
-        assert_eq!(Version::V010.is_compatible(), true);
+    fn should_get_next_version() {
+        assert_eq!(Version::V010.next(), Some(Version::V020));
+        assert_eq!(Version::CURRENT.next(), None);
     }
 
     #[test]
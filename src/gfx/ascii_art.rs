@@ -5,7 +5,9 @@
 mod enemies;
 mod graffiti;
 mod misc;
+mod npcs;
 
 pub use enemies::{DAEMON, DON_MAZE, SHADOW};
 pub use graffiti::graffiti;
-pub use misc::{CHEST, WALL_MARK};
+pub use misc::{CHEST, LOCKED_CHEST, MIMIC, OPEN_CHEST, WALL_MARK};
+pub use npcs::{GHOST, MERCHANT, PRISONER};
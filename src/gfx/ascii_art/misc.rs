@@ -11,6 +11,39 @@ pub const CHEST: &str = r#" ***********
 *************
 *************"#;
 
+pub const LOCKED_CHEST: &str = r#" ***********
+*************
+*************
+*****/ \*****
+****|   |****
+****| o |****
+****|___|****
+*************
+*************
+*************"#;
+
+pub const OPEN_CHEST: &str = r#" ***********
+*           *
+*           *
+ ***********
+*************
+****** ******
+*****   *****
+*************
+*************
+*************"#;
+
+pub const MIMIC: &str = r#" ***********
+** O     O **
+*************
+V V V V V V V
+
+ ~~~~~~~~~~~
+^ ^ ^ ^ ^ ^ ^
+*************
+*************
+*************"#;
+
 pub const WALL_MARK: &str = r#"* *
  *
 * * "#;
@@ -0,0 +1,46 @@
+//! # Npcs
+
+pub const MERCHANT: &str = r#"      *****
+     *******
+   ***********
+     ** * **
+     *******
+      *****
+   ***********
+  *************       *****
+ ***************     *******
+ ** ********* **     *******
+ ** ********* **  ***********
+ ** ********* ** ************
+    *********     ***********
+    ***   ***     ***********
+    ***   ***
+    ***   ***"#;
+
+pub const GHOST: &str = r#"      *******
+    ***********
+   *************
+   ***  ***  ***
+   ***  ***  ***
+   *************
+   ****** ******
+   *************
+   *************
+   *************
+   *************
+   *** *** *** *
+   **   *   *"#;
+
+pub const PRISONER: &str = r#"   O    *****    O
+   O   *******   O
+   O   ** * **   O
+   O   *******   O
+   O    *****    O
+   O*************O
+    *************
+    *************
+    *************
+    ***********
+    ****   ****
+    ****   ****
+  OOOOOOOOOOOOOOOO"#;
@@ -150,6 +150,18 @@ impl Render {
         }
     }
 
+    /// Repaint lines and rectangles of `shapes` with `color`
+    pub fn fade(&self, shapes: Vec<Shape>, color: Color) -> Vec<Shape> {
+        shapes
+            .into_iter()
+            .map(|shape| match shape {
+                Shape::Line(line) => Shape::Line(Line { color, ..line }),
+                Shape::Rectangle(rect) => Shape::Rectangle(Rectangle { color, ..rect }),
+                shape => shape,
+            })
+            .collect()
+    }
+
     /// Stack shapes into a stack where each vector is divided by a new layer
     pub fn stack(&self, layers: Vec<Vec<Shape>>) -> Vec<Shape> {
         let mut stack = Vec::new();
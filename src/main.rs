@@ -45,6 +45,9 @@ fn main() -> anyhow::Result<()> {
     // run Game
     Runtime::setup(
         Options::default()
+            .autosave_turns(args.autosave.unwrap_or(20))
+            .config_dir(config_dir)
+            .difficulty(args.difficulty.unwrap_or_default())
             .music(!args.no_music && !args.muted)
             .sound(!args.muted)
             .saved_games_dir(game_saves_dir)
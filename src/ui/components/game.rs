@@ -3,29 +3,37 @@
 //! Game components
 
 mod actions;
+mod brew;
 mod canvas;
+mod dialogue;
 mod hp;
 mod inventory;
 mod label;
 mod messages;
 mod popup;
+mod trade;
 
 pub use actions::AvailableActions;
+pub use brew::Brew;
 pub use canvas::Canvas;
+pub use dialogue::Dialogue;
 pub use hp::{EnemyHp, PlayerHp};
 pub use inventory::Inventory;
 pub use label::EnemyName;
 pub use messages::Messages;
-pub use popup::{ErrorPopup, GameOverPopup, QuitPopup, SaveFileNamePopup};
+pub use popup::{ErrorPopup, GameOverPopup, OverwritePopup, QuitPopup, SaveFileNamePopup};
+pub use trade::Trade;
 
 use super::Msg;
-use crate::game::entity::Item;
+use crate::game::entity::{EquipmentSlot, Item};
 use crate::game::session::Action;
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash)]
 pub enum GameId {
     AvailableActions,
+    Brew,
     Canvas,
+    Dialogue,
     EnemyHp,
     EnemyName,
     ErrorPopup,
@@ -34,23 +42,40 @@ pub enum GameId {
     Messages,
     PlayerHp,
     SaveFileNamePopup,
+    OverwritePopup,
     QuitPopup,
+    Trade,
 }
 
 /// Messages related to game
 #[derive(PartialEq, Eq)]
 pub enum GameMsg {
     ActionSelected(Action),
+    Brew(Action),
+    CloseBrew,
+    CloseDialogue,
     CloseErrorPopup,
     CloseInventory,
+    CloseOverwritePopup,
     CloseQuitPopup,
     CloseSaveFileName,
+    CloseTrade,
+    /// Answer the npc with the choice at the provided position of the provided dialogue node
+    DialogueChoice(usize, usize),
+    DropItem(Item),
     GameOver,
+    /// Save game replacing the existing save with the same name
+    OverwriteGame(String),
     /// If true, save game
     Quit(bool),
     SaveGame(String),
+    ShowBrew,
     ShowInventory,
+    /// Trade or talk with the npc in the room
+    ShowNpc,
     ShowSaveFileName,
     ShowQuitPopup,
+    Trade(Action),
+    UnequipItem(EquipmentSlot),
     UseItem(Item),
 }
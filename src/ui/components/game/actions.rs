@@ -7,7 +7,10 @@ use tuirealm::props::{Alignment, BorderType, Borders, Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
 
 use super::{GameMsg, Msg};
-use crate::game::session::{Action, ExploreAction, FightAction, Session};
+use crate::game::entity::Item;
+use crate::game::session::{
+    Action, ExploreAction, FightAction, Session, COUNTERATTACK_PROBABILITY,
+};
 use crate::utils::room_resolver::{self, Direction as MazeDirection};
 
 #[derive(MockComponent)]
@@ -40,20 +43,84 @@ impl AvailableActions {
         }
     }
 
-    fn action_name(action: &Action, session: &Session) -> &'static str {
+    fn action_name(action: &Action, session: &Session) -> String {
         match action {
-            Action::Die => "Game over",
-            Action::UseItem(_) => panic!("ACCESS VIOLATION"),
+            Action::Die => "Game over".to_string(),
+            Action::Brew(_, _)
+            | Action::UseItem(_)
+            | Action::UnequipItem(_)
+            | Action::DropItem(_)
+            | Action::Explore(
+                ExploreAction::Buy(_) | ExploreAction::Sell(_) | ExploreAction::Talk(_, _),
+            ) => {
+                panic!("ACCESS VIOLATION")
+            }
             Action::Explore(ExploreAction::ChangeRoom(room)) => {
-                Self::room_direction(*room, session)
+                Self::room_direction(*room, session).to_string()
+            }
+            Action::Explore(ExploreAction::CollectItem) => "Gather item".to_string(),
+            Action::Explore(ExploreAction::ForceChest) => "Force the lock (noisy)".to_string(),
+            Action::Explore(ExploreAction::GoToPreviousRoom) => "Go back".to_string(),
+            Action::Explore(ExploreAction::InspectChest) => "Inspect chest".to_string(),
+            Action::Explore(ExploreAction::LeaveMaze) => "Leave the labyrinth".to_string(),
+            Action::Explore(ExploreAction::PickLock) => "Pick the lock".to_string(),
+            Action::Explore(ExploreAction::Rest(turns)) => format!("Rest for {} turns", turns),
+            Action::Explore(ExploreAction::Sneak(room)) => {
+                format!("Sneak {}", Self::relative_direction(*room, session))
+            }
+            Action::Fight(FightAction::Defend) => {
+                format!("Defend ({}% counter)", COUNTERATTACK_PROBABILITY)
+            }
+            Action::Fight(FightAction::Escape) => {
+                format!("Escape fight ({}%)", session.escape_probability())
+            }
+            Action::Fight(FightAction::Fight(target))
+                if session.get_fighting_enemies().len() == 1 =>
+            {
+                format!(
+                    "Fight ({}%, {}% crit)",
+                    session.hit_probability(*target),
+                    session.critical_probability()
+                )
+            }
+            Action::Fight(FightAction::Fight(target)) => format!(
+                "Fight {} #{} ({}%, {}% crit)",
+                session.get_fighting_enemies()[*target].name(),
+                target + 1,
+                session.hit_probability(*target),
+                session.critical_probability()
+            ),
+            Action::Fight(FightAction::Retreat(room)) => format!(
+                "Retreat {} ({}%)",
+                Self::relative_direction(*room, session),
+                session.retreat_probability(*room)
+            ),
+            Action::Fight(FightAction::UseTalisman(_))
+                if session.get_fighting_enemies().len() == 1 =>
+            {
+                "Use talisman".to_string()
+            }
+            Action::Fight(FightAction::UseTalisman(target)) => format!(
+                "Use talisman on {} #{}",
+                session.get_fighting_enemies()[*target].name(),
+                target + 1
+            ),
+            Action::Fight(FightAction::Throw(potion, _))
+                if session.get_fighting_enemies().len() == 1 =>
+            {
+                format!(
+                    "Throw {}",
+                    Item::Potion(*potion).name(&session.potion_knowledge())
+                )
             }
-            Action::Explore(ExploreAction::CollectItem) => "Gather item",
-            Action::Explore(ExploreAction::GoToPreviousRoom) => "Go back",
-            Action::Explore(ExploreAction::LeaveMaze) => "Leave the labyrinth",
-            Action::Fight(FightAction::Escape) => "Escape fight",
-            Action::Fight(FightAction::Fight) => "Fight",
-            Action::SaveGame => "Save game",
-            Action::Sleep => "Sleep",
+            Action::Fight(FightAction::Throw(potion, target)) => format!(
+                "Throw {} at {} #{}",
+                Item::Potion(*potion).name(&session.potion_knowledge()),
+                session.get_fighting_enemies()[*target].name(),
+                target + 1
+            ),
+            Action::SaveGame => "Save game".to_string(),
+            Action::Sleep => "Sleep".to_string(),
         }
     }
 
@@ -65,6 +132,18 @@ impl AvailableActions {
         }
     }
 
+    /// Direction of `room` relative to the player, including the room the player comes from
+    fn relative_direction(room: u32, session: &Session) -> &'static str {
+        if Some(room) == session.get_last_room() {
+            return "back";
+        }
+        match room_resolver::resolve_room_direction(room, session) {
+            MazeDirection::Ahead => "ahead",
+            MazeDirection::Left => "left",
+            MazeDirection::Right => "right",
+        }
+    }
+
     fn selected_action(&self) -> Option<Action> {
         match self.state() {
             State::One(StateValue::Usize(idx)) => Some(self.actions[idx]),
@@ -122,6 +201,10 @@ impl Component<Msg, NoUserEvent> for AvailableActions {
                 code: Key::Char('s'),
                 modifiers: KeyModifiers::NONE,
             }) => Some(Msg::Game(GameMsg::ShowSaveFileName)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('t'),
+                modifiers: KeyModifiers::NONE,
+            }) => Some(Msg::Game(GameMsg::ShowNpc)),
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Game(GameMsg::ShowQuitPopup))
             }
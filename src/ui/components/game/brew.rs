@@ -0,0 +1,115 @@
+//! # Brew
+//!
+//! Brew popup, used to brew potions out of the ingredients in the inventory
+
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, TextSpan};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
+
+use super::{GameMsg, Msg};
+use crate::game::session::{Action, Session};
+
+#[derive(MockComponent)]
+pub struct Brew {
+    component: List,
+    brews: Vec<Action>,
+}
+
+impl Brew {
+    pub fn new(session: &Session) -> Self {
+        let brews = session.available_brews();
+        let rows = brews
+            .iter()
+            .map(|x| vec![TextSpan::from(Self::brew_name(x, session))])
+            .collect();
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightGreen)
+                        .modifiers(BorderType::Double),
+                )
+                .foreground(Color::LightGreen)
+                .highlighted_str("➤	 ")
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .title(
+                    format!("Alchemy ({} recipes discovered)", session.recipes().len()),
+                    Alignment::Center,
+                )
+                .rows(rows),
+            brews,
+        }
+    }
+
+    /// Ingredients followed by the potion they make, if the recipe has been discovered
+    fn brew_name(action: &Action, session: &Session) -> String {
+        match action {
+            Action::Brew(first, second) => format!(
+                "{} + {} ➜ {}",
+                first.name(&session.potion_knowledge()),
+                second.name(&session.potion_knowledge()),
+                session
+                    .known_recipe(*first, *second)
+                    .map(|x| x.name().to_string())
+                    .unwrap_or_else(|| String::from("???"))
+            ),
+            _ => panic!("ACCESS VIOLATION"),
+        }
+    }
+
+    fn selected_brew(&self) -> Option<Action> {
+        match self.state() {
+            State::One(StateValue::Usize(idx)) => self.brews.get(idx).copied(),
+            _ => None,
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Brew {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.selected_brew().map(|x| Msg::Game(GameMsg::Brew(x))),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => Some(Msg::Game(GameMsg::CloseBrew)),
+            _ => None,
+        }
+    }
+}
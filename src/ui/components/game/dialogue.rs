@@ -0,0 +1,173 @@
+//! # Dialogue
+//!
+//! Dialogue popup, used to talk to the npcs
+
+use tuirealm::command::{Cmd, CmdResult, Direction};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, AttrValue, Attribute, BorderType, Borders, Color, Props, Style};
+use tuirealm::ratatui::layout::{Constraint, Direction as LayoutDirection, Layout};
+use tuirealm::ratatui::widgets::{
+    List as TuiList, ListDirection, ListItem, ListState, Paragraph as TuiParagraph, Wrap,
+};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
+
+use super::{GameMsg, Msg};
+use crate::game::entity::{Item, EXIT_HINT, KEY_HINT};
+use crate::game::Session;
+
+pub struct Dialogue {
+    props: Props,
+    npc: String,
+    node: usize,
+    line: String,
+    choices: Vec<String>,
+    choice: usize,
+}
+
+impl Dialogue {
+    /// Instantiate a new dialogue popup showing `node` of the dialogue spoken by the npc in the room
+    pub fn new(session: &Session, node: usize) -> Self {
+        let npc = session.npc().map(|x| x.name().to_string());
+        let node_data = session.dialogue().and_then(|x| x.node(node));
+        Self {
+            props: Props::default(),
+            npc: npc.unwrap_or_default(),
+            node,
+            line: node_data
+                .map(|x| Self::fill_hints(&x.line, session))
+                .unwrap_or_default(),
+            choices: node_data
+                .map(|x| x.choices.iter().map(|x| x.text.clone()).collect())
+                .unwrap_or_default(),
+            choice: 0,
+        }
+    }
+
+    /// Replace the hint placeholders in `line` with the position of the maze key and of the exit
+    fn fill_hints(line: &str, session: &Session) -> String {
+        let key_hint = if session.player_inventory().has(Item::MazeKey) {
+            "right in your pocket".to_string()
+        } else {
+            Self::distance_hint(session.maze_key_distance())
+        };
+        line.replace(KEY_HINT, &key_hint)
+            .replace(EXIT_HINT, &Self::distance_hint(session.exit_distance()))
+    }
+
+    fn distance_hint(distance: Option<u8>) -> String {
+        match distance {
+            None => "somewhere beyond my sight".to_string(),
+            Some(0) => "right here, in this very room".to_string(),
+            Some(1) => "in a room next to this one".to_string(),
+            Some(distance) => format!("{} rooms away from here", distance),
+        }
+    }
+}
+
+impl MockComponent for Dialogue {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::ratatui::layout::Rect) {
+        let focus = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(area);
+        // render line
+        let line_block = tui_realm_stdlib::utils::get_block(
+            Borders::default()
+                .color(Color::LightCyan)
+                .modifiers(BorderType::Double),
+            Some((self.npc.clone(), Alignment::Center)),
+            focus,
+            None,
+        );
+        frame.render_widget(
+            TuiParagraph::new(self.line.as_str())
+                .block(line_block)
+                .style(Style::default().fg(Color::LightCyan))
+                .wrap(Wrap { trim: true }),
+            chunks[0],
+        );
+        // render choices
+        let choices_block = tui_realm_stdlib::utils::get_block(
+            Borders::default()
+                .color(Color::LightCyan)
+                .modifiers(BorderType::Double),
+            Some((String::from("Your answer"), Alignment::Left)),
+            focus,
+            None,
+        );
+        let list_items: Vec<ListItem> = self
+            .choices
+            .iter()
+            .map(|x| ListItem::new(x.as_str()))
+            .collect();
+        let list = TuiList::new(list_items)
+            .block(choices_block)
+            .style(Style::default())
+            .direction(ListDirection::TopToBottom)
+            .highlight_style(Style::default().fg(Color::LightCyan))
+            .highlight_symbol("➤	 ");
+        let mut state: ListState = ListState::default();
+        state.select(Some(self.choice));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        State::One(StateValue::Usize(self.choice))
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        let prev = self.choice;
+        match cmd {
+            Cmd::Move(Direction::Down) if self.choice + 1 < self.choices.len() => {
+                self.choice += 1;
+            }
+            Cmd::Move(Direction::Down) => self.choice = 0,
+            Cmd::Move(Direction::Up) if self.choice > 0 => self.choice -= 1,
+            Cmd::Move(Direction::Up) => self.choice = self.choices.len().saturating_sub(1),
+            _ => {}
+        }
+        if prev != self.choice {
+            CmdResult::Changed(self.state())
+        } else {
+            CmdResult::None
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Dialogue {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) if self.choice < self.choices.len() => {
+                Some(Msg::Game(GameMsg::DialogueChoice(self.node, self.choice)))
+            }
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Game(GameMsg::CloseDialogue))
+            }
+            _ => None,
+        }
+    }
+}
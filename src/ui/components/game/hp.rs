@@ -1,21 +1,37 @@
 //! # hp
 
-use tui_realm_stdlib::Label;
-use tuirealm::props::{Alignment, Color};
+use tui_realm_stdlib::Paragraph;
+use tuirealm::props::{Alignment, BorderSides, Borders, Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
-use crate::game::Hp;
+use crate::game::entity::{ActiveStatusEffect, Enemy, LightLevel, Torch, MAX_TORCH_FUEL};
+use crate::game::session::MAX_NOISE;
+use crate::game::{Hp, Session};
 
+/// Width of the experience bar in cells
+const XP_BAR_WIDTH: u32 = 10;
+/// Width of the torch fuel bar in cells
+const TORCH_BAR_WIDTH: u32 = 10;
+
+/// Health points of the enemies in the room; one row per enemy
 #[derive(MockComponent)]
 pub struct EnemyHp {
-    component: HealthPoints,
+    component: Paragraph,
 }
 
 impl EnemyHp {
-    pub fn new(hp: Hp) -> Self {
+    pub fn new(enemies: &[Enemy]) -> Self {
+        let rows: Vec<TextSpan> = enemies
+            .iter()
+            .map(|x| TextSpan::from(hp_text(x.health())))
+            .collect();
         Self {
-            component: HealthPoints::new(hp, Alignment::Right),
+            component: Paragraph::default()
+                .alignment(Alignment::Right)
+                .borders(Borders::default().sides(BorderSides::NONE))
+                .foreground(Color::Red)
+                .text(&rows),
         }
     }
 }
@@ -26,17 +42,87 @@ impl Component<Msg, NoUserEvent> for EnemyHp {
     }
 }
 
+/// Player health points followed by level, experience bar, torch fuel, noise, gold and the active status effects
 #[derive(MockComponent)]
 pub struct PlayerHp {
-    component: HealthPoints,
+    component: Paragraph,
 }
 
 impl PlayerHp {
-    pub fn new(hp: Hp) -> Self {
+    pub fn new(session: &Session) -> Self {
+        let player = session.player();
+        let mut rows = vec![
+            TextSpan::from(hp_text(player.health())).fg(Color::Red),
+            TextSpan::from(Self::level_text(session)).fg(Color::Yellow),
+            Self::torch_row(&player.torch),
+            Self::noise_row(session.noise()),
+            TextSpan::from(format!("Gold: {}", player.gold())).fg(Color::Yellow),
+        ];
+        rows.extend(player.status_effects().iter().map(Self::status_effect_row));
         Self {
-            component: HealthPoints::new(hp, Alignment::Left),
+            component: Paragraph::default()
+                .alignment(Alignment::Left)
+                .borders(Borders::default().sides(BorderSides::NONE))
+                .text(&rows),
+        }
+    }
+
+    fn level_text(session: &Session) -> String {
+        let player = session.player();
+        match session.difficulty().experience_to_level_up(player.level()) {
+            Some(required) => {
+                let filled = (player.experience() * XP_BAR_WIDTH / required).min(XP_BAR_WIDTH);
+                format!(
+                    "Lv. {} {}{} {}/{} XP",
+                    player.level(),
+                    "■".repeat(filled as usize),
+                    "□".repeat((XP_BAR_WIDTH - filled) as usize),
+                    player.experience(),
+                    required
+                )
+            }
+            None => format!("Lv. {} (max)", player.level()),
         }
     }
+
+    fn torch_row(torch: &Torch) -> TextSpan {
+        let filled = (torch.fuel() as u32 * TORCH_BAR_WIDTH).div_ceil(MAX_TORCH_FUEL as u32);
+        let color = match torch.light_level() {
+            LightLevel::Bright => Color::LightYellow,
+            LightLevel::Dim => Color::Yellow,
+            LightLevel::Dark => Color::DarkGray,
+        };
+        TextSpan::from(format!(
+            "Torch {}{} {}/{}",
+            "■".repeat(filled as usize),
+            "□".repeat((TORCH_BAR_WIDTH - filled) as usize),
+            torch.fuel(),
+            MAX_TORCH_FUEL
+        ))
+        .fg(color)
+    }
+
+    fn noise_row(noise: u8) -> TextSpan {
+        let text = match noise {
+            0 => "Noise: silent".to_string(),
+            noise => format!("Noise: {}", "♪".repeat(noise as usize)),
+        };
+        let color = if noise >= MAX_NOISE {
+            Color::LightRed
+        } else {
+            Color::Gray
+        };
+        TextSpan::from(text).fg(color)
+    }
+
+    fn status_effect_row(active: &ActiveStatusEffect) -> TextSpan {
+        let color = if active.effect.is_beneficial() {
+            Color::LightGreen
+        } else {
+            Color::LightMagenta
+        };
+        TextSpan::from(format!("{} ({})", active.effect.name(), active.turns)).fg(color)
+    }
 }
 
 impl Component<Msg, NoUserEvent> for PlayerHp {
@@ -45,26 +131,10 @@ impl Component<Msg, NoUserEvent> for PlayerHp {
     }
 }
 
-#[derive(MockComponent)]
-struct HealthPoints {
-    component: Label,
-}
-
-impl HealthPoints {
-    pub fn new(hp: Hp, alignment: Alignment) -> Self {
-        Self {
-            component: Label::default()
-                .foreground(Color::Red)
-                .alignment(alignment)
-                .text(Self::hp_text(hp)),
-        }
-    }
-
-    fn hp_text(hp: Hp) -> String {
-        if hp < 255 {
-            " ♥ ".repeat(hp as usize)
-        } else {
-            "∞".to_string()
-        }
+fn hp_text(hp: Hp) -> String {
+    if hp < 255 {
+        " ♥ ".repeat(hp as usize)
+    } else {
+        "∞".to_string()
     }
 }
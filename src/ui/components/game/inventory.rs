@@ -15,12 +15,15 @@ use tuirealm::ratatui::widgets::{
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, State};
 
 use super::{GameMsg, Msg};
-use crate::game::entity::Item;
+use crate::game::entity::{Item, PotionKnowledge};
+use crate::game::inventory::INVENTORY_CAPACITY;
 use crate::game::Session;
 
 struct ItemState {
     item: Item,
+    artifact: bool,
     consumable: bool,
+    equipped: bool,
     description: String,
     quantity: u8,
     name: String,
@@ -32,31 +35,79 @@ pub struct Inventory {
     props: Props,
     item: usize,
     this_item_desc: String,
+    title: String,
+    can_brew: bool,
+    can_drop: bool,
 }
 
 impl Inventory {
     pub fn new(session: &Session) -> Self {
-        let has_alchemy_book = session.player_inventory().has(Item::AlchemyBook);
+        let knowledge = session.potion_knowledge();
+        let make_state = |item: Item, quantity: u8, equipped: bool| ItemState {
+            item,
+            artifact: matches!(item, Item::Artifact(_)),
+            consumable: item.consumable(),
+            equipped,
+            description: item.description(&knowledge).to_string(),
+            name: Self::item_name(item, &knowledge),
+            quantity,
+            usable: item.usable(session.player().state()),
+        };
         let mut inventory: Vec<ItemState> = session
             .player_inventory()
             .items()
             .into_iter()
-            .map(|(i, q)| ItemState {
-                item: i,
-                consumable: i.consumable(),
-                description: i.description(has_alchemy_book).to_string(),
-                name: i.name(has_alchemy_book).to_string(),
-                quantity: q,
-                usable: i.usable(session.player().state()),
-            })
+            .map(|(i, q)| make_state(i, q, false))
+            .collect();
+        // equipped items come first
+        let mut equipped: Vec<ItemState> = session
+            .player()
+            .equipment
+            .items()
+            .into_iter()
+            .map(|i| make_state(i, 1, true))
             .collect();
-        // sort by name
-        inventory.sort_by_key(|x| x.name.clone());
+        equipped.append(&mut inventory);
+        let inventory = equipped;
         Self {
             inventory,
             item: 0,
             props: Props::default(),
             this_item_desc: String::default(),
+            title: Self::title(session),
+            can_brew: session.can_brew(),
+            can_drop: session.can_drop_items(),
+        }
+    }
+
+    /// Title reporting how full the inventory is and the available commands
+    fn title(session: &Session) -> String {
+        let mut commands = Vec::new();
+        if session.can_drop_items() {
+            commands.push("<D> to drop");
+        }
+        if session.can_brew() {
+            commands.push("<B> to brew");
+        }
+        let title = format!(
+            "Inventory {}/{}",
+            session.player_inventory().size(),
+            INVENTORY_CAPACITY
+        );
+        if commands.is_empty() {
+            title
+        } else {
+            format!("{} (press {})", title, commands.join(", "))
+        }
+    }
+
+    /// Item name followed by its durability, if any
+    fn item_name(item: Item, knowledge: &PotionKnowledge) -> String {
+        match item.durability() {
+            Some((durability, max)) => {
+                format!("{} [{}/{}]", item.name(knowledge), durability, max)
+            }
+            None => item.name(knowledge).to_string(),
         }
     }
 
@@ -123,7 +174,7 @@ impl MockComponent for Inventory {
             Borders::default()
                 .color(Color::LightRed)
                 .modifiers(BorderType::Double),
-            Some((String::from("Inventory"), Alignment::Center)),
+            Some((self.title.clone(), Alignment::Center)),
             focus,
             None,
         );
@@ -133,12 +184,16 @@ impl MockComponent for Inventory {
             .inventory
             .iter()
             .map(|item| {
-                let item_style = if item.usable {
+                let item_style = if item.artifact {
+                    Style::default().fg(Color::LightYellow)
+                } else if item.usable {
                     Style::default()
                 } else {
                     Style::default().add_modifier(TextModifiers::CROSSED_OUT)
                 };
-                let cols = if item.consumable {
+                let cols = if item.equipped {
+                    Span::styled(format!("{} (equipped)", item.name), item_style)
+                } else if item.consumable || item.quantity > 1 {
                     Span::styled(format!("{} ({}x)", item.name, item.quantity), item_style)
                 } else {
                     Span::styled(item.name.to_string(), item_style)
@@ -306,12 +361,28 @@ impl Component<Msg, NoUserEvent> for Inventory {
             Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
                 Some(Msg::Game(GameMsg::CloseInventory))
             }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('b'),
+                ..
+            }) if self.can_brew => Some(Msg::Game(GameMsg::ShowBrew)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('d'),
+                ..
+            }) if self.can_drop => self
+                .inventory
+                .get(self.item)
+                .filter(|x| !x.equipped)
+                .map(|x| Msg::Game(GameMsg::DropItem(x.item))),
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => {
                 // get item and return use item if it is usable
                 if let Some(item) = self.inventory.get(self.item) {
-                    if item.usable {
+                    if item.usable && item.equipped {
+                        item.item
+                            .slot()
+                            .map(|slot| Msg::Game(GameMsg::UnequipItem(slot)))
+                    } else if item.usable {
                         Some(Msg::Game(GameMsg::UseItem(item.item)))
                     } else {
                         None
@@ -1,23 +1,45 @@
 //! # labels
 
-use tui_realm_stdlib::Label;
-use tuirealm::props::{Alignment, Color};
+use tui_realm_stdlib::Paragraph;
+use tuirealm::props::{Alignment, BorderSides, Borders, Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
+use crate::game::entity::Enemy;
 
+/// Names and evasion of the enemies in the room; enemies are numbered in group fights
 #[derive(MockComponent)]
 pub struct EnemyName {
-    component: Label,
+    component: Paragraph,
 }
 
 impl EnemyName {
-    pub fn new(name: &str) -> Self {
+    pub fn new(enemies: &[Enemy]) -> Self {
+        let rows: Vec<TextSpan> = match enemies {
+            [enemy] => vec![TextSpan::from(format!(
+                "{} (evasion {}%)",
+                enemy.name(),
+                enemy.evasion()
+            ))],
+            enemies => enemies
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    TextSpan::from(format!(
+                        "{} #{} (evasion {}%)",
+                        x.name(),
+                        i + 1,
+                        x.evasion()
+                    ))
+                })
+                .collect(),
+        };
         Self {
-            component: Label::default()
+            component: Paragraph::default()
                 .alignment(Alignment::Right)
+                .borders(Borders::default().sides(BorderSides::NONE))
                 .foreground(Color::Red)
-                .text(name),
+                .text(&rows),
         }
     }
 }
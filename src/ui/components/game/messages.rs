@@ -7,6 +7,7 @@ use tuirealm::props::{BorderType, Borders, Color, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
+use crate::game::entity::{LightLevel, Npc};
 use crate::game::session::{Message, Reveal};
 use crate::game::Session;
 use crate::utils::room_resolver::{self, Direction as MazeDirection};
@@ -41,19 +42,61 @@ impl Messages {
     }
 
     fn message(message: &Message, session: &Session) -> String {
-        let has_alchemy_book = session
-            .player_inventory()
-            .has(crate::game::entity::Item::AlchemyBook);
+        let knowledge = session.potion_knowledge();
         match message {
-            Message::ArmorEquipped => "Armor equipped; HP increased by 1".to_string(),
+            Message::BossPhaseChanged(2) => {
+                "Don Maze is wounded; it's furious now and its blows are faster".to_string()
+            }
+            Message::BossPhaseChanged(_) => {
+                "Don Maze is desperate; its eyes are glowing in the dark".to_string()
+            }
+            Message::ChestForced(0) => "The lock gives way and the chest opens".to_string(),
+            Message::ChestForced(_) => "You hit the lock, but it still holds".to_string(),
+            Message::ChestInspected => "You inspect the chest: it looks safe".to_string(),
+            Message::CompassPoint(None) => {
+                "The compass needle spins wildly: the exit is right here".to_string()
+            }
+            Message::CompassPoint(Some(room)) => format!(
+                "The compass needle points {}",
+                Self::relative_direction(*room, session)
+            ),
+            Message::CriticalHit => "Critical hit!".to_string(),
+            Message::Counterattack(hp) => {
+                format!("You counterattacked, dealing {} HP to enemy", hp)
+            }
             Message::DamageDealt(hp) => format!("Dealt {} HP to enemy", hp),
             Message::DamageSuffered(hp, true) => {
                 format!("Critical hit! The enemy dealt {} HP to you.", hp)
             }
             Message::DamageSuffered(hp, false) => format!("The enemy dealt {} HP to you.", hp),
             Message::EnemyApproaching(enemy) => format!("{} entered the room", enemy.name()),
+            Message::EnemyHeard(enemy, room, 1) => format!(
+                "You hear {} {}",
+                enemy.noise(),
+                Self::relative_direction(*room, session)
+            ),
+            Message::EnemyHeard(enemy, room, _) => format!(
+                "You hear faint {} {}, in the distance",
+                enemy.noise(),
+                Self::relative_direction(*room, session)
+            ),
+            Message::EnemySensed(room, enemy) => format!(
+                "The owl's eye senses a {} in the room {}",
+                enemy.name(),
+                Self::room_direction(*room, session)
+            ),
+            Message::Defending => "You raise your guard".to_string(),
+            Message::DonMazeDefeated => {
+                "Don Maze has been defeated! The maze is finally free".to_string()
+            }
+            Message::EnemyDroppedLoot(item) => {
+                format!("The enemy dropped a {}", item.name(&knowledge))
+            }
             Message::EnemyDefeated => "Enemy defeated".to_string(),
+            Message::EnemyFellAsleep => "The enemy fell asleep".to_string(),
+            Message::EnemyHealed(hp) => format!("The enemy restored {} HP", hp),
             Message::EnemyMissed => "Enemy missed".to_string(),
+            Message::EnemySleeping => "The enemy is sleeping...".to_string(),
             Message::EnemyVanished => "The enemy vanished...".to_string(),
             Message::EscapeFailed => "You failed to escape the enemy".to_string(),
             Message::EscapeSucceeded(room) => {
@@ -62,19 +105,94 @@ impl Messages {
                     Self::room_direction(*room, session)
                 )
             }
+            Message::BrewFailed => {
+                "The mixture fizzles and turns into a stinking sludge. You throw it away"
+                    .to_string()
+            }
+            Message::ExperienceGained(xp) => format!("You gained {} XP", xp),
             Message::FallAsleep => "You suddenly feel sleepy and you fall asleep".to_string(),
             Message::GameSaved => "Game has been saved".to_string(),
-            Message::ItemCollected(item) => format!("You found a {}", item.name(has_alchemy_book)),
-            Message::ItemUsed(item) => format!(
-                "You used {}: {}",
-                item.name(has_alchemy_book),
-                item.effect()
-            ),
+            Message::GoldCollected(coins) => format!("You found {} gold coins", coins),
+            Message::Healed(hp) => format!("You feel better: you recovered {} HP", hp),
+            Message::ItemBought(item, price) => {
+                format!("You bought a {} for {} gold", item.name(&knowledge), price)
+            }
+            Message::ItemReceived(item) => {
+                format!("You received a {}", item.name(&knowledge))
+            }
+            Message::ItemSold(item, price) => {
+                format!("You sold a {} for {} gold", item.name(&knowledge), price)
+            }
+            Message::ItemBroken(item) => format!("Your {} broke", item.name(&knowledge)),
+            Message::ItemCollected(item) => format!("You found a {}", item.name(&knowledge)),
+            Message::ItemDropped(item) => format!("You dropped the {}", item.name(&knowledge)),
+            Message::InventoryFull => {
+                "Your inventory is full: drop something to make room".to_string()
+            }
+            Message::ItemStackFull(item) => {
+                format!("You can't carry any more {}", item.name(&knowledge))
+            }
+            Message::ItemEquipped(item) => format!("You equipped {}", item.name(&knowledge)),
+            Message::ItemUnequipped(item) => {
+                format!("You unequipped {}", item.name(&knowledge))
+            }
+            Message::ItemUsed(item) => {
+                format!("You used {}: {}", item.name(&knowledge), item.effect())
+            }
+            Message::ItemUseless(item) => {
+                format!("You can't use the {} here", item.name(&knowledge))
+            }
             Message::LeaveMaze => "You left the maze".to_string(),
+            Message::LockPicked => "You picked the lock and opened the chest".to_string(),
+            Message::LevelUp(level) => format!("Level up! You reached level {}", level),
+            Message::LightChanged(LightLevel::Bright) => "Your torch burns bright".to_string(),
+            Message::LightChanged(LightLevel::Dim) => {
+                "Your torch is flickering; it's running out of fuel".to_string()
+            }
+            Message::LightChanged(LightLevel::Dark) => {
+                "Your torch went out. It's pitch dark".to_string()
+            }
+            Message::MapRevealed(room, 1, false) => format!(
+                "The map shows a dead end {}",
+                Self::relative_direction(*room, session)
+            ),
+            Message::MapRevealed(room, rooms, exit) => format!(
+                "The map shows {} rooms {}{}",
+                rooms,
+                Self::relative_direction(*room, session),
+                if *exit { ", including the exit" } else { "" }
+            ),
+            Message::NpcMet(Npc::Ghost(_)) => {
+                "The ghost of a lost adventurer floats in the room. Press <T> to talk to it"
+                    .to_string()
+            }
+            Message::NpcMet(Npc::Merchant(_)) => {
+                "You met a wandering merchant. Press <T> to trade with him".to_string()
+            }
+            Message::NpcMet(Npc::Prisoner(_)) => {
+                "A prisoner is chained to the wall. Press <T> to talk to him".to_string()
+            }
+            Message::NothingToGive => "There's nothing more they can do for you".to_string(),
+            Message::MimicRevealed => "The chest opens its jaws: it's a mimic!".to_string(),
             Message::PlayerDead => "You died".to_string(),
+            Message::PlayerMissed => "You missed the enemy".to_string(),
+            Message::PoisonDamage(hp) => format!("The poison dealt {} HP to you", hp),
+            Message::PotionBrewed(potion) => format!("You brewed a {}", potion.name()),
             Message::PotionDrunk(potion) => {
                 format!("You drunk the {}: {}", potion.name(), potion.effect())
             }
+            Message::PotionThrown(potion) => format!(
+                "You threw the {} at the enemy",
+                crate::game::entity::Item::Potion(*potion).name(&knowledge)
+            ),
+            Message::Regenerated(hp) => format!("You regenerated {} HP", hp),
+            Message::RelicsCombined(item) => format!(
+                "The relics shine as they join together: you forged the {}",
+                item.name(&knowledge)
+            ),
+            Message::RelicsMissing(missing) => {
+                format!("{} relics are still missing to forge the weapon", missing)
+            }
             Message::Reveal(room, Reveal::Enemy(enemy)) => format!(
                 "The sonar revealed a {} in the room {}",
                 enemy.name(),
@@ -82,10 +200,20 @@ impl Messages {
             ),
             Message::Reveal(room, Reveal::Item(item)) => format!(
                 "The sonar revealed a {} in the room {}",
-                item.name(has_alchemy_book),
+                item.name(&knowledge),
                 Self::room_direction(*room, session)
             ),
             Message::RevealNothing => "The sonar didn't reveal anything.".to_string(),
+            Message::SaveMigrated(version) => {
+                format!(
+                    "Your game, saved with v{}, has been updated",
+                    version.name()
+                )
+            }
+            Message::SonarInDarkness => "The sonar doesn't work in the dark".to_string(),
+            Message::RestStarted(turns) => {
+                format!("You lie down to rest for {} turns", turns)
+            }
             Message::RoomChanged(MazeDirection::Ahead) => {
                 "You entered the room in front of you".to_string()
             }
@@ -96,10 +224,30 @@ impl Messages {
                 "You entered the room on your right".to_string()
             }
             Message::Sleeping => "You're still sleeping like a baby...".to_string(),
+            Message::StatusEffectApplied(status) => {
+                format!("You are now {}", status.name().to_lowercase())
+            }
+            Message::StatusEffectExpired(status) => {
+                format!("You are no longer {}", status.name().to_lowercase())
+            }
+            Message::SurprisedWhileResting => "You've been caught resting!".to_string(),
+            Message::TrapDisarmed => "You found a trap in the chest and disarmed it".to_string(),
+            Message::TrapSprung(hp) => format!("A trap in the chest dealt {} HP to you", hp),
             Message::WakeUp => "You finally woke up".to_string(),
         }
     }
 
+    fn relative_direction(room: u32, session: &Session) -> &'static str {
+        if Some(room) == session.get_last_room() {
+            return "behind you";
+        }
+        match room_resolver::resolve_room_direction(room, session) {
+            MazeDirection::Ahead => "ahead of you",
+            MazeDirection::Left => "to your left",
+            MazeDirection::Right => "to your right",
+        }
+    }
+
     fn room_direction(room: u32, session: &Session) -> &'static str {
         match room_resolver::resolve_room_direction(room, session) {
             MazeDirection::Ahead => "in front of you",
@@ -136,6 +136,69 @@ impl Component<Msg, NoUserEvent> for QuitPopup {
     }
 }
 
+// -- overwrite popup
+
+#[derive(MockComponent)]
+pub struct OverwritePopup {
+    component: Radio,
+    name: String,
+}
+
+impl OverwritePopup {
+    pub fn new(name: &str) -> Self {
+        Self {
+            component: Radio::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::LightRed)
+                        .modifiers(BorderType::Double),
+                )
+                .foreground(Color::LightRed)
+                .title(
+                    format!("'{}' already exists. Overwrite it?", name),
+                    Alignment::Center,
+                )
+                .rewind(true)
+                .choices(&["Overwrite", "Cancel"])
+                .value(1),
+            name: name.to_string(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for OverwritePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Game(GameMsg::CloseOverwritePopup))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.perform(Cmd::Submit) {
+                CmdResult::Submit(State::One(StateValue::Usize(0))) => {
+                    Some(Msg::Game(GameMsg::OverwriteGame(self.name.clone())))
+                }
+                _ => Some(Msg::Game(GameMsg::CloseOverwritePopup)),
+            },
+            _ => None,
+        }
+    }
+}
+
+// -- save file name popup
+
 #[derive(MockComponent)]
 pub struct SaveFileNamePopup {
     component: Input,
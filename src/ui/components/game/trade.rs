@@ -0,0 +1,121 @@
+//! # Trade
+//!
+//! Trade popup, used to buy and sell items to the wandering merchant
+
+use tui_realm_stdlib::List;
+use tuirealm::command::{Cmd, Direction, Position};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, TextSpan};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
+
+use super::{GameMsg, Msg};
+use crate::game::session::{Action, ExploreAction, Session};
+
+#[derive(MockComponent)]
+pub struct Trade {
+    component: List,
+    trades: Vec<Action>,
+}
+
+impl Trade {
+    pub fn new(session: &Session) -> Self {
+        let trades = session.available_trades();
+        let rows = trades
+            .iter()
+            .map(|x| vec![TextSpan::from(Self::trade_name(x, session))])
+            .collect();
+        Self {
+            component: List::default()
+                .borders(
+                    Borders::default()
+                        .color(Color::Yellow)
+                        .modifiers(BorderType::Double),
+                )
+                .foreground(Color::Yellow)
+                .highlighted_str("➤	 ")
+                .rewind(true)
+                .scroll(true)
+                .step(4)
+                .title(
+                    format!(
+                        "Wandering merchant (you have {} gold)",
+                        session.player().gold()
+                    ),
+                    Alignment::Center,
+                )
+                .rows(rows),
+            trades,
+        }
+    }
+
+    fn trade_name(action: &Action, session: &Session) -> String {
+        let knowledge = session.potion_knowledge();
+        match action {
+            Action::Explore(ExploreAction::Buy(item)) => format!(
+                "Buy {} ({} gold)",
+                item.name(&knowledge),
+                session.buy_price(*item).unwrap_or_default()
+            ),
+            Action::Explore(ExploreAction::Sell(item)) => format!(
+                "Sell {} ({} gold)",
+                item.name(&knowledge),
+                session.sell_price(*item).unwrap_or_default()
+            ),
+            _ => panic!("ACCESS VIOLATION"),
+        }
+    }
+
+    fn selected_trade(&self) -> Option<Action> {
+        match self.state() {
+            State::One(StateValue::Usize(idx)) => self.trades.get(idx).copied(),
+            _ => None,
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Trade {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Home, ..
+            }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => self.selected_trade().map(|x| Msg::Game(GameMsg::Trade(x))),
+            Event::Keyboard(KeyEvent { code: Key::Esc, .. }) => {
+                Some(Msg::Game(GameMsg::CloseTrade))
+            }
+            _ => None,
+        }
+    }
+}
@@ -84,11 +84,21 @@ impl Stats {
                         session.stats().fights_escaped
                     )),
                     TextSpan::from(format!("Items used: {}", session.stats().items_used)),
+                    TextSpan::from(format!(
+                        "Level reached: {} ({} XP)",
+                        session.player().level(),
+                        session.player().experience()
+                    )),
                     TextSpan::from(format!("Rooms explored: {}", session.visited_rooms())),
                     TextSpan::from(format!(
                         "You've been asleep for {} turns",
                         session.stats().slept_for_turns
                     )),
+                    TextSpan::from(format!(
+                        "You rested {} times, restoring {} HP",
+                        session.stats().times_rested,
+                        session.stats().health_restored_resting
+                    )),
                     TextSpan::from(format!("You survived for {} turns", session.stats().turn)),
                     TextSpan::from(""),
                     TextSpan::from("Try again?"),
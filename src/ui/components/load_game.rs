@@ -10,7 +10,7 @@ use std::path::PathBuf;
 
 pub use games::Games;
 pub use metadata::Metadata;
-pub use popup::ErrorPopup;
+pub use popup::{ErrorPopup, RestorePopup};
 
 use super::Msg;
 
@@ -29,4 +29,5 @@ pub enum LoadGameMsg {
     GameChanged(PathBuf),
     LoadGame(PathBuf),
     GoToMenu,
+    RestoreBackup(PathBuf),
 }
@@ -5,7 +5,8 @@ use tuirealm::props::{Alignment, BorderSides, Borders, TextSpan};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent};
 
 use super::Msg;
-use crate::game::Session;
+use crate::game::session::Version;
+use crate::utils::saved_games::SaveHeader;
 
 #[derive(MockComponent)]
 pub struct Metadata {
@@ -13,20 +14,25 @@ pub struct Metadata {
 }
 
 impl Metadata {
-    pub fn new(session: &Session) -> Self {
+    pub fn new(header: &SaveHeader) -> Self {
+        let mut text = vec![
+            TextSpan::from(format!("seed: {}", header.seed)),
+            TextSpan::from(format!("difficulty: {}", header.difficulty.name())),
+            TextSpan::from(format!("last turn: {}", header.timestamp.to_rfc2822())),
+            TextSpan::from(format!("turn: {}", header.turn)),
+            TextSpan::from(" ♥ ".repeat(header.health as usize)),
+        ];
+        if header.version != Version::CURRENT {
+            text.push(TextSpan::from(format!(
+                "saved with v{}: it will be migrated",
+                header.version.name()
+            )));
+        }
         Self {
             component: Paragraph::default()
                 .alignment(Alignment::Center)
                 .borders(Borders::default().sides(BorderSides::NONE))
-                .text(&[
-                    TextSpan::from(format!("seed: {}", session.maze_seed())),
-                    TextSpan::from(format!(
-                        "last turn: {}",
-                        session.stats().last_turn.to_rfc2822()
-                    )),
-                    TextSpan::from(format!("turn: {}", session.stats().turn)),
-                    TextSpan::from(" ♥ ".repeat(session.player().health() as usize)),
-                ])
+                .text(&text)
                 .wrap(true),
         }
     }
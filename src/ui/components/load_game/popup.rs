@@ -2,6 +2,8 @@
 //!
 //! Menu popup
 
+use std::path::{Path, PathBuf};
+
 use tui_realm_stdlib::Paragraph;
 use tuirealm::event::{Key, KeyEvent};
 use tuirealm::props::{Alignment, BorderType, Borders, Color, TextSpan};
@@ -42,3 +44,49 @@ impl Component<Msg, NoUserEvent> for ErrorPopup {
         }
     }
 }
+
+#[derive(MockComponent)]
+pub struct RestorePopup {
+    component: Paragraph,
+    game_file: PathBuf,
+}
+
+impl RestorePopup {
+    pub fn new<S: AsRef<str>>(text: S, game_file: &Path) -> Self {
+        Self {
+            component: Paragraph::default()
+                .alignment(Alignment::Center)
+                .borders(
+                    Borders::default()
+                        .color(Color::Red)
+                        .modifiers(BorderType::Double),
+                )
+                .foreground(Color::Red)
+                .text(&[
+                    TextSpan::from(text.as_ref()),
+                    TextSpan::from(""),
+                    TextSpan::from("Restore previous version? (Y/n)"),
+                ])
+                .wrap(true),
+            game_file: game_file.to_path_buf(),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for RestorePopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter | Key::Char('y'),
+                ..
+            }) => Some(Msg::LoadGame(LoadGameMsg::RestoreBackup(
+                self.game_file.clone(),
+            ))),
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc | Key::Char('n'),
+                ..
+            }) => Some(Msg::LoadGame(LoadGameMsg::CloseErrorPopup)),
+            _ => None,
+        }
+    }
+}
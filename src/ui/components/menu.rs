@@ -4,10 +4,12 @@
 
 mod buttons;
 mod input;
+mod popup;
 mod title;
 
-pub use buttons::{Exit, LoadGame, NewGame};
-pub use input::Seed;
+pub use buttons::{Exit, LoadGame, NewGame, Recover};
+pub use input::{DifficultySelector, Seed};
+pub use popup::RecoverErrorPopup;
 pub use title::Title;
 
 use super::Msg;
@@ -18,18 +20,26 @@ pub enum MenuId {
     Title,
     NewGame,
     LoadGame,
+    Recover,
+    RecoverErrorPopup,
     Seed,
+    Difficulty,
     Exit,
 }
 
 /// Messages related to main menu
 #[derive(PartialEq, Eq)]
 pub enum MenuMsg {
+    ActiveDifficulty,
     ActiveNewGame,
     ActiveLoadGame,
     ActiveExit,
+    ActiveRecover,
     ActiveSeed,
+    CloseRecoverError,
+    DiscardAutosave,
     NewGame,
     LoadGame,
     Quit,
+    Recover,
 }
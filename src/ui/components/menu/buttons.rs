@@ -50,10 +50,12 @@ impl Component<Msg, NoUserEvent> for NewGame {
 #[derive(MockComponent)]
 pub struct LoadGame {
     component: Button,
+    /// Whether the recover button follows
+    recover: bool,
 }
 
-impl Default for LoadGame {
-    fn default() -> Self {
+impl LoadGame {
+    pub fn new(recover: bool) -> Self {
         Self {
             component: Button::default()
                 .alignment(Alignment::Center)
@@ -65,6 +67,7 @@ impl Default for LoadGame {
                 )
                 .text(&[TextSpan::from("Load game")])
                 .wrap(true),
+            recover,
         }
     }
 }
@@ -78,6 +81,9 @@ impl Component<Msg, NoUserEvent> for LoadGame {
             Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
                 Some(Msg::Menu(MenuMsg::ActiveNewGame))
             }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) if self.recover => Some(Msg::Menu(MenuMsg::ActiveRecover)),
             Event::Keyboard(KeyEvent {
                 code: Key::Down, ..
             }) => Some(Msg::Menu(MenuMsg::ActiveExit)),
@@ -87,12 +93,53 @@ impl Component<Msg, NoUserEvent> for LoadGame {
 }
 
 #[derive(MockComponent)]
-pub struct Exit {
+pub struct Recover {
     component: Button,
 }
 
-impl Default for Exit {
+impl Default for Recover {
     fn default() -> Self {
+        Self {
+            component: Button::default()
+                .alignment(Alignment::Center)
+                .foreground(Color::Yellow)
+                .borders(
+                    Borders::default()
+                        .color(Color::Yellow)
+                        .modifiers(BorderType::Double),
+                )
+                .text(&[TextSpan::from("Recover last session")])
+                .wrap(true),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for Recover {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Menu(MenuMsg::Recover)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                Some(Msg::Menu(MenuMsg::ActiveLoadGame))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => Some(Msg::Menu(MenuMsg::ActiveExit)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(MockComponent)]
+pub struct Exit {
+    component: Button,
+    /// Whether the recover button precedes
+    recover: bool,
+}
+
+impl Exit {
+    pub fn new(recover: bool) -> Self {
         Self {
             component: Button::default()
                 .alignment(Alignment::Center)
@@ -104,6 +151,7 @@ impl Default for Exit {
                 )
                 .text(&[TextSpan::from("Quit")])
                 .wrap(true),
+            recover,
         }
     }
 }
@@ -114,6 +162,9 @@ impl Component<Msg, NoUserEvent> for Exit {
             Event::Keyboard(KeyEvent {
                 code: Key::Enter, ..
             }) => Some(Msg::Menu(MenuMsg::Quit)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) if self.recover => {
+                Some(Msg::Menu(MenuMsg::ActiveRecover))
+            }
             Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
                 Some(Msg::Menu(MenuMsg::ActiveLoadGame))
             }
@@ -2,13 +2,14 @@
 //!
 //! Input components for menu
 
-use tui_realm_stdlib::Input;
-use tuirealm::command::{Cmd, Position};
+use tui_realm_stdlib::{Input, Radio};
+use tuirealm::command::{Cmd, Direction, Position};
 use tuirealm::event::{Key, KeyEvent, KeyModifiers};
 use tuirealm::props::{Alignment, BorderType, Borders, Color, Style};
-use tuirealm::{Component, Event, MockComponent, NoUserEvent};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, State, StateValue};
 
 use super::{MenuMsg, Msg};
+use crate::game::Difficulty;
 
 #[derive(MockComponent)]
 pub struct Seed {
@@ -46,6 +47,9 @@ impl Component<Msg, NoUserEvent> for Seed {
             Event::Keyboard(KeyEvent {
                 code: Key::Left, ..
             }) => Some(Msg::Menu(MenuMsg::ActiveNewGame)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => Some(Msg::Menu(MenuMsg::ActiveDifficulty)),
             Event::Keyboard(KeyEvent {
                 code: Key::Backspace,
                 ..
@@ -77,3 +81,62 @@ impl Component<Msg, NoUserEvent> for Seed {
         }
     }
 }
+
+#[derive(MockComponent)]
+pub struct DifficultySelector {
+    component: Radio,
+}
+
+impl DifficultySelector {
+    pub fn new(difficulty: Difficulty) -> Self {
+        let choices: Vec<&str> = Difficulty::ALL.iter().map(|x| x.name()).collect();
+        Self {
+            component: Radio::default()
+                .foreground(Color::LightMagenta)
+                .title("Difficulty", Alignment::Center)
+                .borders(
+                    Borders::default()
+                        .color(Color::LightMagenta)
+                        .modifiers(BorderType::Double),
+                )
+                .choices(&choices)
+                .value(
+                    Difficulty::ALL
+                        .iter()
+                        .position(|x| *x == difficulty)
+                        .unwrap_or_default(),
+                ),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for DifficultySelector {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Left, ..
+            }) => {
+                // leave the selector when moving left from the first choice
+                if self.state() == State::One(StateValue::Usize(0)) {
+                    return Some(Msg::Menu(MenuMsg::ActiveSeed));
+                }
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Right));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => Some(Msg::Menu(MenuMsg::NewGame)),
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => Some(Msg::Menu(MenuMsg::ActiveExit)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => Some(Msg::Menu(MenuMsg::ActiveLoadGame)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! # Popup
+//!
+//! Menu popup
+
+use tui_realm_stdlib::Paragraph;
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, BorderType, Borders, Color, TextSpan};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent};
+
+use super::{MenuMsg, Msg};
+
+#[derive(MockComponent)]
+pub struct RecoverErrorPopup {
+    component: Paragraph,
+}
+
+impl RecoverErrorPopup {
+    pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            component: Paragraph::default()
+                .alignment(Alignment::Center)
+                .borders(
+                    Borders::default()
+                        .color(Color::Red)
+                        .modifiers(BorderType::Double),
+                )
+                .foreground(Color::Red)
+                .text(&[
+                    TextSpan::from(text.as_ref()),
+                    TextSpan::from(""),
+                    TextSpan::from("Discard the autosave? (Y/n)"),
+                ])
+                .wrap(true),
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for RecoverErrorPopup {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter | Key::Char('y'),
+                ..
+            }) => Some(Msg::Menu(MenuMsg::DiscardAutosave)),
+            Event::Keyboard(KeyEvent {
+                code: Key::Esc | Key::Char('n'),
+                ..
+            }) => Some(Msg::Menu(MenuMsg::CloseRecoverError)),
+            _ => None,
+        }
+    }
+}
@@ -59,13 +59,18 @@ pub struct Stats {
 
 impl Stats {
     pub fn new(session: &Session) -> Self {
+        let headline = if session.has_defeated_don_maze() {
+            "You slew Don Maze with the Mazebane and freed the maze!"
+        } else {
+            "You escaped the maze!"
+        };
         Self {
             component: Paragraph::default()
                 .borders(Borders::default().sides(BorderSides::NONE))
                 .foreground(Color::Reset)
                 .alignment(Alignment::Center)
                 .text(&[
-                    TextSpan::from("You escaped the maze!"),
+                    TextSpan::from(headline),
                     TextSpan::from(""),
                     TextSpan::from(format!(
                         "Damage inflicted: {} HP",
@@ -84,11 +89,21 @@ impl Stats {
                         session.stats().fights_escaped
                     )),
                     TextSpan::from(format!("Items used: {}", session.stats().items_used)),
+                    TextSpan::from(format!(
+                        "Level reached: {} ({} XP)",
+                        session.player().level(),
+                        session.player().experience()
+                    )),
                     TextSpan::from(format!("Rooms explored: {}", session.visited_rooms())),
                     TextSpan::from(format!(
                         "You've been asleep for {} turns",
                         session.stats().slept_for_turns
                     )),
+                    TextSpan::from(format!(
+                        "You rested {} times, restoring {} HP",
+                        session.stats().times_rested,
+                        session.stats().health_restored_resting
+                    )),
                     TextSpan::from(format!("You played for {} turns", session.stats().turn)),
                     TextSpan::from(""),
                     TextSpan::from("Thank you for playing!"),
@@ -2,7 +2,7 @@
 //!
 //! Ui related things
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use tuirealm::props::Shape;
@@ -11,8 +11,10 @@ use tuirealm::ratatui::widgets::Clear;
 use tuirealm::terminal::{CrosstermTerminalAdapter, TerminalBridge};
 use tuirealm::{Application, EventListenerCfg, NoUserEvent, State, StateValue};
 
+use crate::game::entity::Enemy;
 use crate::game::session::Message;
-use crate::game::{Hp, Session};
+use crate::game::{Difficulty, Session};
+use crate::utils::saved_games::SaveHeader;
 use crate::utils::ui::draw_area_in;
 
 mod components;
@@ -66,6 +68,8 @@ pub struct Ui {
     application: Application<Id, Msg, NoUserEvent>,
     terminal: TerminalBridge<CrosstermTerminalAdapter>,
     view: View,
+    /// Rows taken by the enemy data in the game view; one per enemy
+    enemy_rows: u16,
 }
 
 impl Ui {
@@ -80,6 +84,7 @@ impl Ui {
             application,
             terminal,
             view: View::None,
+            enemy_rows: 1,
         };
         Ok(ui)
     }
@@ -119,14 +124,15 @@ impl Ui {
     }
 
     fn view_game(&mut self) -> UiResult<()> {
+        let enemy_rows = self.enemy_rows;
         self.terminal.raw_mut().draw(|f| {
             let body = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints(
                     [
-                        Constraint::Length(1), // Enemy data
-                        Constraint::Min(20),   // Canvas
-                        Constraint::Length(6), // player's stats
+                        Constraint::Length(enemy_rows), // Enemy data
+                        Constraint::Min(20),            // Canvas
+                        Constraint::Length(6),          // player's stats
                     ]
                     .as_ref(),
                 )
@@ -173,6 +179,21 @@ impl Ui {
                 // make popup
                 self.application
                     .view(&Id::Game(GameId::Inventory), f, popup);
+            } else if self.application.mounted(&Id::Game(GameId::Brew)) {
+                let popup = draw_area_in(f.area(), 70, 80);
+                f.render_widget(Clear, popup);
+                // make popup
+                self.application.view(&Id::Game(GameId::Brew), f, popup);
+            } else if self.application.mounted(&Id::Game(GameId::Trade)) {
+                let popup = draw_area_in(f.area(), 60, 60);
+                f.render_widget(Clear, popup);
+                // make popup
+                self.application.view(&Id::Game(GameId::Trade), f, popup);
+            } else if self.application.mounted(&Id::Game(GameId::Dialogue)) {
+                let popup = draw_area_in(f.area(), 60, 50);
+                f.render_widget(Clear, popup);
+                // make popup
+                self.application.view(&Id::Game(GameId::Dialogue), f, popup);
             } else if self.application.mounted(&Id::Game(GameId::QuitPopup)) {
                 let popup = draw_area_in(f.area(), 50, 10);
                 f.render_widget(Clear, popup);
@@ -188,6 +209,12 @@ impl Ui {
                 // make popup
                 self.application
                     .view(&Id::Game(GameId::SaveFileNamePopup), f, popup);
+            } else if self.application.mounted(&Id::Game(GameId::OverwritePopup)) {
+                let popup = draw_area_in(f.area(), 50, 10);
+                f.render_widget(Clear, popup);
+                // make popup
+                self.application
+                    .view(&Id::Game(GameId::OverwritePopup), f, popup);
             } else if self.application.mounted(&Id::Game(GameId::GameOverPopup)) {
                 let popup = draw_area_in(f.area(), 50, 10);
                 f.render_widget(Clear, popup);
@@ -209,7 +236,7 @@ impl Ui {
                 .constraints(
                     [
                         Constraint::Length(7),  // Title
-                        Constraint::Length(10), // stats
+                        Constraint::Length(13), // stats
                         Constraint::Length(3),  // menu
                         Constraint::Length(1),  // footer
                     ]
@@ -268,32 +295,57 @@ impl Ui {
     fn view_menu(&mut self) -> UiResult<()> {
         self.terminal.raw_mut().draw(|f| {
             // Prepare chunks
+            let recover = self.application.mounted(&Id::Menu(MenuId::Recover));
             let body = Layout::default()
                 .direction(Direction::Vertical)
                 .horizontal_margin(30)
                 .constraints(
                     [
-                        Constraint::Length(7), // Title
-                        Constraint::Length(3), // new game + seed
-                        Constraint::Length(3), // load game
-                        Constraint::Length(3), // quit
-                        Constraint::Length(1), // footer
+                        Constraint::Length(7),                           // Title
+                        Constraint::Length(3),                           // new game + seed
+                        Constraint::Length(3),                           // load game
+                        Constraint::Length(if recover { 3 } else { 0 }), // recover
+                        Constraint::Length(3),                           // quit
+                        Constraint::Length(1),                           // footer
                     ]
                     .as_ref(),
                 )
                 .split(f.area());
             let new_game_chunks = Layout::default()
                 .direction(Direction::Horizontal)
-                .constraints([Constraint::Percentage(30), Constraint::Percentage(70)].as_ref())
+                .constraints(
+                    [
+                        Constraint::Percentage(25),
+                        Constraint::Percentage(40),
+                        Constraint::Percentage(35),
+                    ]
+                    .as_ref(),
+                )
                 .split(body[1]);
             self.application.view(&Id::Menu(MenuId::Title), f, body[0]);
             self.application
                 .view(&Id::Menu(MenuId::NewGame), f, new_game_chunks[0]);
             self.application
                 .view(&Id::Menu(MenuId::Seed), f, new_game_chunks[1]);
+            self.application
+                .view(&Id::Menu(MenuId::Difficulty), f, new_game_chunks[2]);
             self.application
                 .view(&Id::Menu(MenuId::LoadGame), f, body[2]);
-            self.application.view(&Id::Menu(MenuId::Exit), f, body[3]);
+            if recover {
+                self.application
+                    .view(&Id::Menu(MenuId::Recover), f, body[3]);
+            }
+            self.application.view(&Id::Menu(MenuId::Exit), f, body[4]);
+            // popups
+            if self
+                .application
+                .mounted(&Id::Menu(MenuId::RecoverErrorPopup))
+            {
+                let popup = draw_area_in(f.area(), 50, 20);
+                f.render_widget(Clear, popup);
+                self.application
+                    .view(&Id::Menu(MenuId::RecoverErrorPopup), f, popup);
+            }
         })?;
         Ok(())
     }
@@ -345,7 +397,7 @@ impl Ui {
         )?;
         self.application.mount(
             Id::Game(GameId::PlayerHp),
-            Box::new(game::PlayerHp::new(session.player().health())),
+            Box::new(game::PlayerHp::new(session)),
             vec![],
         )?;
         self.application
@@ -356,17 +408,21 @@ impl Ui {
     }
 
     /// load game loader
-    pub fn load_game_loader(&mut self, games: &[PathBuf], game0: Option<&Session>) -> UiResult<()> {
+    pub fn load_game_loader(
+        &mut self,
+        games: &[PathBuf],
+        game0: Option<&SaveHeader>,
+    ) -> UiResult<()> {
         self.application.umount_all();
         self.application.mount(
             Id::LoadGame(LoadGameId::Games),
             Box::new(load_game::Games::new(games)),
             vec![],
         )?;
-        if let Some(session) = game0 {
+        if let Some(header) = game0 {
             self.application.mount(
                 Id::LoadGame(LoadGameId::Metadata),
-                Box::new(load_game::Metadata::new(session)),
+                Box::new(load_game::Metadata::new(header)),
                 vec![],
             )?;
         }
@@ -404,7 +460,8 @@ impl Ui {
     }
 
     /// Load menu view
-    pub fn load_menu(&mut self) -> UiResult<()> {
+    /// Load menu; if `recover` is set, the player is offered to recover the last session
+    pub fn load_menu(&mut self, recover: bool, difficulty: Difficulty) -> UiResult<()> {
         self.application.umount_all();
         let (width, _) = self.sizes()?;
         let width = width as u16 - 60;
@@ -423,14 +480,26 @@ impl Ui {
             Box::new(menu::Seed::default()),
             vec![],
         )?;
+        self.application.mount(
+            Id::Menu(MenuId::Difficulty),
+            Box::new(menu::DifficultySelector::new(difficulty)),
+            vec![],
+        )?;
         self.application.mount(
             Id::Menu(MenuId::LoadGame),
-            Box::new(menu::LoadGame::default()),
+            Box::new(menu::LoadGame::new(recover)),
             vec![],
         )?;
+        if recover {
+            self.application.mount(
+                Id::Menu(MenuId::Recover),
+                Box::new(menu::Recover::default()),
+                vec![],
+            )?;
+        }
         self.application.mount(
             Id::Menu(MenuId::Exit),
-            Box::new(menu::Exit::default()),
+            Box::new(menu::Exit::new(recover)),
             vec![],
         )?;
         self.application.active(&Id::Menu(MenuId::NewGame))?;
@@ -479,6 +548,29 @@ impl Ui {
         })
     }
 
+    pub fn get_menu_difficulty(&self) -> UiResult<Difficulty> {
+        Ok(
+            match self.application.state(&Id::Menu(MenuId::Difficulty))? {
+                State::One(StateValue::Usize(index)) => {
+                    Difficulty::ALL.get(index).copied().unwrap_or_default()
+                }
+                _ => Difficulty::default(),
+            },
+        )
+    }
+
+    /// Show the error occurred recovering the last session, offering to discard the autosave
+    pub fn show_menu_recover_error<S: AsRef<str>>(&mut self, text: S) -> UiResult<()> {
+        self.application.remount(
+            Id::Menu(MenuId::RecoverErrorPopup),
+            Box::new(menu::RecoverErrorPopup::new(text)),
+            vec![],
+        )?;
+        self.application
+            .active(&Id::Menu(MenuId::RecoverErrorPopup))?;
+        Ok(())
+    }
+
     /// Close menu error
     pub fn close_load_game_error(&mut self) -> UiResult<()> {
         self.application
@@ -498,11 +590,27 @@ impl Ui {
         Ok(())
     }
 
+    /// Show load game error, offering to restore the previous version of `game_file`
+    pub fn show_load_game_restore<S: AsRef<str>>(
+        &mut self,
+        text: S,
+        game_file: &Path,
+    ) -> UiResult<()> {
+        self.application.remount(
+            Id::LoadGame(LoadGameId::ErrorPopup),
+            Box::new(load_game::RestorePopup::new(text, game_file)),
+            vec![],
+        )?;
+        self.application
+            .active(&Id::LoadGame(LoadGameId::ErrorPopup))?;
+        Ok(())
+    }
+
     /// Set save file metadata
-    pub fn set_load_game_save_metadata(&mut self, session: &Session) -> UiResult<()> {
+    pub fn set_load_game_save_metadata(&mut self, header: &SaveHeader) -> UiResult<()> {
         self.application.remount(
             Id::LoadGame(LoadGameId::Metadata),
-            Box::new(load_game::Metadata::new(session)),
+            Box::new(load_game::Metadata::new(header)),
             vec![],
         )?;
         Ok(())
@@ -552,6 +660,57 @@ impl Ui {
         Ok(())
     }
 
+    /// Show brew popup
+    pub fn show_game_brew(&mut self, session: &Session) -> UiResult<()> {
+        self.application.remount(
+            Id::Game(GameId::Brew),
+            Box::new(game::Brew::new(session)),
+            vec![],
+        )?;
+        self.application.active(&Id::Game(GameId::Brew))?;
+        Ok(())
+    }
+
+    /// Close brew popup
+    pub fn close_game_brew(&mut self) -> UiResult<()> {
+        self.application.umount(&Id::Game(GameId::Brew))?;
+        Ok(())
+    }
+
+    /// Show trade popup with the merchant
+    pub fn show_game_trade(&mut self, session: &Session) -> UiResult<()> {
+        self.application.remount(
+            Id::Game(GameId::Trade),
+            Box::new(game::Trade::new(session)),
+            vec![],
+        )?;
+        self.application.active(&Id::Game(GameId::Trade))?;
+        Ok(())
+    }
+
+    /// Close trade popup
+    pub fn close_game_trade(&mut self) -> UiResult<()> {
+        self.application.umount(&Id::Game(GameId::Trade))?;
+        Ok(())
+    }
+
+    /// Show dialogue popup with the npc at `node`
+    pub fn show_game_dialogue(&mut self, session: &Session, node: usize) -> UiResult<()> {
+        self.application.remount(
+            Id::Game(GameId::Dialogue),
+            Box::new(game::Dialogue::new(session, node)),
+            vec![],
+        )?;
+        self.application.active(&Id::Game(GameId::Dialogue))?;
+        Ok(())
+    }
+
+    /// Close dialogue popup
+    pub fn close_game_dialogue(&mut self) -> UiResult<()> {
+        self.application.umount(&Id::Game(GameId::Dialogue))?;
+        Ok(())
+    }
+
     pub fn show_game_quit_popup(&mut self) -> UiResult<()> {
         self.application.remount(
             Id::Game(GameId::QuitPopup),
@@ -586,6 +745,22 @@ impl Ui {
         Ok(())
     }
 
+    pub fn show_game_overwrite_popup(&mut self, name: &str) -> UiResult<()> {
+        self.application.remount(
+            Id::Game(GameId::OverwritePopup),
+            Box::new(game::OverwritePopup::new(name)),
+            vec![],
+        )?;
+        self.application.active(&Id::Game(GameId::OverwritePopup))?;
+        Ok(())
+    }
+
+    /// Close game overwrite save
+    pub fn close_game_overwrite_popup(&mut self) -> UiResult<()> {
+        self.application.umount(&Id::Game(GameId::OverwritePopup))?;
+        Ok(())
+    }
+
     /// Update messages in view
     pub fn update_game_messages(
         &mut self,
@@ -624,31 +799,33 @@ impl Ui {
         Ok(())
     }
 
-    /// Update player health component
-    pub fn update_game_player_health(&mut self, hp: Hp) -> UiResult<()> {
+    /// Update player health, level and status effects component
+    pub fn update_game_player_health(&mut self, session: &Session) -> UiResult<()> {
         self.application.remount(
             Id::Game(GameId::PlayerHp),
-            Box::new(game::PlayerHp::new(hp)),
+            Box::new(game::PlayerHp::new(session)),
             vec![],
         )?;
         Ok(())
     }
 
-    pub fn update_game_enemy_data(&mut self, hp: Hp, name: &str) -> UiResult<()> {
+    pub fn update_game_enemy_data(&mut self, enemies: &[Enemy]) -> UiResult<()> {
+        self.enemy_rows = enemies.len().max(1) as u16;
         self.application.remount(
             Id::Game(GameId::EnemyHp),
-            Box::new(game::EnemyHp::new(hp)),
+            Box::new(game::EnemyHp::new(enemies)),
             vec![],
         )?;
         self.application.remount(
             Id::Game(GameId::EnemyName),
-            Box::new(game::EnemyName::new(name)),
+            Box::new(game::EnemyName::new(enemies)),
             vec![],
         )?;
         Ok(())
     }
 
     pub fn hide_game_enemy_data(&mut self) -> UiResult<()> {
+        self.enemy_rows = 1;
         if self.application.mounted(&Id::Game(GameId::EnemyHp)) {
             self.application.umount(&Id::Game(GameId::EnemyName))?;
             self.application.umount(&Id::Game(GameId::EnemyHp))?;
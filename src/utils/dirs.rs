@@ -41,6 +41,13 @@ pub fn get_log_path(config_dir: &Path) -> PathBuf {
     log_file
 }
 
+/// Returns the path for the marker file which exists while the game is running
+pub fn get_run_lock_path(config_dir: &Path) -> PathBuf {
+    let mut lock_file: PathBuf = PathBuf::from(config_dir);
+    lock_file.push("donmaze.lock");
+    lock_file
+}
+
 /// Get paths for theme provider
 /// Returns: path of saves dir
 /// If dir doesn't exist, it is created
@@ -87,6 +94,7 @@ mod tests {
         // Create file
         let mut f: File = OpenOptions::new()
             .create(true)
+            .truncate(true)
             .write(true)
             .open(conf_dir.as_path())
             .ok()
@@ -110,6 +118,15 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn should_get_run_lock_path() {
+        assert_eq!(
+            get_run_lock_path(Path::new("/home/omar/.config/donmaze/")),
+            PathBuf::from("/home/omar/.config/donmaze/donmaze.lock"),
+        );
+    }
+
     #[test]
     #[serial]
     fn should_get_save_paths() {
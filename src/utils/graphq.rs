@@ -38,10 +38,10 @@ mod test {
 
     #[test]
     fn should_tell_whether_two_graphs_are_equal() {
-        let a = UnGraph::<u32, u32>::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 4)]);
-        let b = UnGraph::<u32, u32>::from_edges(&[(1, 2), (2, 3), (3, 4), (1, 4)]);
+        let a = UnGraph::<u32, u32>::from_edges([(1, 2), (2, 3), (3, 4), (1, 4)]);
+        let b = UnGraph::<u32, u32>::from_edges([(1, 2), (2, 3), (3, 4), (1, 4)]);
         assert_eq!(graph_eq(&a, &b), true);
-        let c = UnGraph::<u32, u32>::from_edges(&[(1, 2), (2, 3), (1, 4)]);
+        let c = UnGraph::<u32, u32>::from_edges([(1, 2), (2, 3), (1, 4)]);
         assert_eq!(graph_eq(&a, &c), false);
     }
 }
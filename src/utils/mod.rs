@@ -4,6 +4,7 @@ pub mod dirs;
 pub mod graphq;
 pub mod random;
 pub mod room_resolver;
+pub mod run_lock;
 pub mod saved_games;
 pub mod ui;
 
@@ -0,0 +1,67 @@
+//! # Run lock
+//!
+//! Marker file which tells whether the previous run exited cleanly
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker file which exists while the game is running.
+/// It's removed only on a clean exit, so if it's still there on start, the previous run crashed
+pub struct RunLock {
+    path: PathBuf,
+}
+
+impl RunLock {
+    /// Create the marker file at `path`.
+    /// Returns the lock and whether the previous run didn't exit cleanly
+    pub fn acquire(path: &Path) -> std::io::Result<(Self, bool)> {
+        let crashed = path.exists();
+        if crashed {
+            warn!(
+                "run lock {} already exists; the previous run didn't exit cleanly",
+                path.display()
+            );
+        }
+        fs::write(path, std::process::id().to_string())?;
+        debug!("acquired run lock {}", path.display());
+        Ok((
+            Self {
+                path: path.to_path_buf(),
+            },
+            crashed,
+        ))
+    }
+
+    /// Remove the marker file on a clean exit.
+    /// NOTE: the lock is not released on drop, otherwise a panic would remove the marker while unwinding
+    pub fn release(self) -> std::io::Result<()> {
+        debug!("releasing run lock {}", self.path.display());
+        fs::remove_file(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_detect_unclean_exit() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("donmaze.lock");
+        let (lock, crashed) = RunLock::acquire(&path).unwrap();
+        assert_eq!(crashed, false);
+        assert!(path.exists());
+        lock.release().unwrap();
+        assert!(!path.exists());
+        // the previous run didn't release the lock
+        let (lock, crashed) = RunLock::acquire(&path).unwrap();
+        assert_eq!(crashed, false);
+        drop(lock);
+        let (_, crashed) = RunLock::acquire(&path).unwrap();
+        assert_eq!(crashed, true);
+    }
+}
@@ -2,16 +2,58 @@
 //!
 //! Utilities to save and load game files
 
-use std::fs::OpenOptions;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 
+use chrono::{DateTime, Local};
 use serde_json::Error as JsonError;
 use thiserror::Error;
 
-use crate::game::Session;
+use crate::game::session::{MigrationError, Version};
+use crate::game::{Difficulty, Hp, Session};
 
 pub type SavedGameResult<T> = Result<T, SavedGameError>;
 
+/// Amount of previous versions kept for each saved game
+pub const SAVE_BACKUPS: usize = 3;
+/// Directory in the saved games dir where backups and files being written are stored
+const BACKUPS_DIR: &str = ".backups";
+/// Each save starts with a line made of this prefix followed by the save header
+const HEADER_MAGIC: &str = "DONMAZE ";
+/// Max length of the header line
+const MAX_HEADER_LEN: u64 = 4096;
+/// Name of the save written when quitting the game
+pub const AUTOSAVE_NAME: &str = "autosave";
+/// Max length of a save name
+pub const MAX_SAVE_NAME_LEN: usize = 32;
+/// Names which can't be chosen by the player: the autosave and the device names reserved on Windows
+const RESERVED_NAMES: [&str; 23] = [
+    AUTOSAVE_NAME,
+    "con",
+    "prn",
+    "aux",
+    "nul",
+    "com1",
+    "com2",
+    "com3",
+    "com4",
+    "com5",
+    "com6",
+    "com7",
+    "com8",
+    "com9",
+    "lpt1",
+    "lpt2",
+    "lpt3",
+    "lpt4",
+    "lpt5",
+    "lpt6",
+    "lpt7",
+    "lpt8",
+    "lpt9",
+];
+
 /// Game file error
 #[derive(Debug, Error)]
 pub enum SavedGameError {
@@ -19,6 +61,22 @@ pub enum SavedGameError {
     Io(std::io::Error),
     #[error("serialization error: {0}")]
     Json(JsonError),
+    #[error("{0}")]
+    Migration(MigrationError),
+    #[error("a save named '{0}' already exists")]
+    AlreadyExists(String),
+    #[error("save name can't be empty")]
+    EmptyName,
+    #[error("invalid character in save name: '{0}'")]
+    InvalidNameChar(char),
+    #[error("save name is too long (max {} characters)", MAX_SAVE_NAME_LEN)]
+    NameTooLong,
+    #[error("no backup could be restored")]
+    NoBackup,
+    #[error("not a saved game")]
+    NotASave,
+    #[error("'{0}' is a reserved name")]
+    ReservedName(String),
 }
 
 impl From<std::io::Error> for SavedGameError {
@@ -33,36 +91,296 @@ impl From<JsonError> for SavedGameError {
     }
 }
 
+impl From<MigrationError> for SavedGameError {
+    fn from(e: MigrationError) -> Self {
+        Self::Migration(e)
+    }
+}
+
+/// Summary of a saved game, written at the beginning of the save,
+/// so that it can be read without loading the whole session
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SaveHeader {
+    pub name: String,
+    pub seed: String,
+    pub difficulty: Difficulty,
+    pub turn: u64,
+    pub health: Hp,
+    pub max_health: Hp,
+    /// When the last turn has been played
+    pub timestamp: DateTime<Local>,
+    /// Engine version the game has been saved with
+    pub version: Version,
+}
+
+impl SaveHeader {
+    fn new(name: &str, game: &Session, version: Version) -> Self {
+        Self {
+            name: name.to_string(),
+            seed: game.maze_seed().to_string(),
+            difficulty: game.difficulty(),
+            turn: game.stats().turn,
+            health: game.player().health(),
+            max_health: game.player().max_health(),
+            timestamp: game.stats().last_turn,
+            version,
+        }
+    }
+}
+
 pub struct SavedGameFiles;
 
 impl SavedGameFiles {
-    /// Save `game` at `games_dir/name`
+    /// Save `game` at `games_dir/name`.
+    /// Fails if the name is not valid or if a save with the same name already exists
     pub fn save_game(name: &str, games_dir: &Path, game: &Session) -> SavedGameResult<()> {
+        Self::validate_name(name)?;
+        if games_dir.join(name).exists() {
+            return Err(SavedGameError::AlreadyExists(name.to_string()));
+        }
+        Self::write_game(name, games_dir, game)
+    }
+
+    /// Save `game` at `games_dir/name`, replacing the existing save with the same name.
+    /// Fails if the name is not valid
+    pub fn overwrite_game(name: &str, games_dir: &Path, game: &Session) -> SavedGameResult<()> {
+        Self::validate_name(name)?;
+        Self::write_game(name, games_dir, game)
+    }
+
+    /// Save `game` as the autosave in `games_dir`
+    pub fn autosave(games_dir: &Path, game: &Session) -> SavedGameResult<()> {
+        Self::write_game(AUTOSAVE_NAME, games_dir, game)
+    }
+
+    /// Delete the autosave in `games_dir`, along with its backups
+    pub fn discard_autosave(games_dir: &Path) -> SavedGameResult<()> {
+        let path = games_dir.join(AUTOSAVE_NAME);
+        debug!("discarding autosave {}", path.display());
+        for backup in Self::backups(&path) {
+            fs::remove_file(backup)?;
+        }
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        info!("autosave discarded");
+        Ok(())
+    }
+
+    /// Check whether `name` can be used as a save name chosen by the player
+    pub fn validate_name(name: &str) -> SavedGameResult<()> {
+        if name.is_empty() {
+            return Err(SavedGameError::EmptyName);
+        }
+        if name.chars().count() > MAX_SAVE_NAME_LEN {
+            return Err(SavedGameError::NameTooLong);
+        }
+        if let Some(ch) = name
+            .chars()
+            .find(|x| !x.is_ascii_alphanumeric() && !['-', '_', ' '].contains(x))
+        {
+            return Err(SavedGameError::InvalidNameChar(ch));
+        }
+        if RESERVED_NAMES.contains(&name.to_lowercase().as_str()) {
+            return Err(SavedGameError::ReservedName(name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Write `game` at `games_dir/name`; the previous version of the save is kept as a backup
+    fn write_game(name: &str, games_dir: &Path, game: &Session) -> SavedGameResult<()> {
         debug!("saving game {}", name);
-        let mut path = games_dir.to_path_buf();
-        path.push(name);
-        debug!("opening save file {}", path.display());
+        let path = games_dir.join(name);
+        Self::replace_save(&path, true, |writer| {
+            debug!("serializing JSON to file");
+            write!(writer, "{}", HEADER_MAGIC)?;
+            serde_json::to_writer(&mut *writer, &SaveHeader::new(name, game, Version::CURRENT))?;
+            writeln!(writer)?;
+            serde_json::to_writer(writer, game)?;
+            Ok(())
+        })?;
+        info!("game saved");
+        Ok(())
+    }
+
+    /// Replace the save at `path` with the content written by `write`.
+    /// The content is written to a temporary file, which then replaces the save with a single rename;
+    /// if `keep_backup` is set, the previous version of the save is kept as a backup.
+    /// The save is never missing in between
+    fn replace_save<F>(path: &Path, keep_backup: bool, write: F) -> SavedGameResult<()>
+    where
+        F: FnOnce(&mut BufWriter<File>) -> SavedGameResult<()>,
+    {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let tmp_path = Self::backups_dir(path).join(format!("{}.tmp", name));
+        fs::create_dir_all(Self::backups_dir(path))?;
+        debug!("opening temporary save file {}", tmp_path.display());
         let file = OpenOptions::new()
             .create(true)
             .truncate(true)
             .write(true)
-            .open(&path)?;
-        debug!("serializing JSON to file");
-        serde_json::to_writer(&file, game)?;
-        info!("game saved");
-        Ok(())
+            .open(&tmp_path)?;
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        if keep_backup && path.exists() {
+            Self::rotate_backups(path)?;
+        }
+        debug!("moving {} to {}", tmp_path.display(), path.display());
+        fs::rename(&tmp_path, path)?;
+        let dir = path
+            .parent()
+            .filter(|x| !x.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        Self::sync_dir(dir)
     }
 
-    /// Load game at path
+    /// Load game at path, migrating it from older versions
     pub fn load_game(path: &Path) -> SavedGameResult<Session> {
         debug!("loading game at {}", path.display());
-        let file = OpenOptions::new().read(true).open(path)?;
+        let mut reader = BufReader::new(File::open(path)?);
         debug!("game file opened");
-        let session = serde_json::from_reader(file)?;
+        if Self::read_header_line(&mut reader)?.is_none() {
+            debug!("save has no header");
+        }
+        let save = serde_json::from_reader(reader)?;
+        let session = Session::from_save(save)?;
+        if let Some(version) = session.migrated_from() {
+            info!("save migrated from {:?}", version);
+        }
         info!("save loaded");
         Ok(session)
     }
 
+    /// Read the header of the save at `path`, without loading the session.
+    /// Saves written before headers were introduced must be loaded to build their header
+    pub fn read_header(path: &Path) -> SavedGameResult<SaveHeader> {
+        debug!("reading header of {}", path.display());
+        let mut reader = BufReader::new(File::open(path)?);
+        if let Some(header) = Self::read_header_line(&mut reader)? {
+            return Ok(header);
+        }
+        if !Self::is_legacy_save(&mut reader)? {
+            return Err(SavedGameError::NotASave);
+        }
+        debug!("save has no header; loading the whole game");
+        let session = Self::load_game(path)?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        Ok(SaveHeader::new(
+            &name,
+            &session,
+            session.migrated_from().unwrap_or(Version::CURRENT),
+        ))
+    }
+
+    /// Read the header line at the beginning of the save, if any
+    fn read_header_line<R: BufRead>(reader: &mut R) -> SavedGameResult<Option<SaveHeader>> {
+        if !reader.fill_buf()?.starts_with(HEADER_MAGIC.as_bytes()) {
+            return Ok(None);
+        }
+        let mut line = String::new();
+        reader.take(MAX_HEADER_LEN).read_line(&mut line)?;
+        let header = line
+            .strip_prefix(HEADER_MAGIC)
+            .ok_or(SavedGameError::NotASave)?;
+        Ok(Some(serde_json::from_str(header)?))
+    }
+
+    /// Returns whether the file looks like a save written before headers were introduced
+    fn is_legacy_save<R: BufRead>(reader: &mut R) -> SavedGameResult<bool> {
+        Ok(reader.fill_buf()?.iter().find(|x| !x.is_ascii_whitespace()) == Some(&b'{'))
+    }
+
+    /// Returns whether the file at `path` is a saved game
+    fn is_save(path: &Path) -> bool {
+        let Ok(file) = File::open(path) else {
+            return false;
+        };
+        let mut reader = BufReader::new(file);
+        match reader.fill_buf() {
+            Ok(buf) if buf.starts_with(HEADER_MAGIC.as_bytes()) => true,
+            Ok(_) => Self::is_legacy_save(&mut reader).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the backups of the save at `path`, from the most recent one
+    pub fn backups(path: &Path) -> Vec<PathBuf> {
+        (1..=SAVE_BACKUPS)
+            .map(|version| Self::backup_path(path, version))
+            .filter(|x| x.is_file())
+            .collect()
+    }
+
+    /// Restore the most recent backup of the save at `path` which can be loaded.
+    /// The backup replaces the save, which is returned loaded
+    pub fn restore_backup(path: &Path) -> SavedGameResult<Session> {
+        for backup in Self::backups(path) {
+            match Self::load_game(&backup) {
+                Ok(session) => {
+                    debug!("restoring backup {}", backup.display());
+                    Self::replace_save(path, false, |writer| {
+                        std::io::copy(&mut File::open(&backup)?, writer)?;
+                        Ok(())
+                    })?;
+                    info!("backup {} restored", backup.display());
+                    return Ok(session);
+                }
+                Err(e) => {
+                    warn!("backup {} is not valid: {}", backup.display(), e);
+                }
+            }
+        }
+        Err(SavedGameError::NoBackup)
+    }
+
+    /// Shift the backups of the save at `path` by one version, discarding the oldest one,
+    /// then link (or copy) the save to the most recent backup, leaving the save in place
+    fn rotate_backups(path: &Path) -> SavedGameResult<()> {
+        let oldest = Self::backup_path(path, SAVE_BACKUPS);
+        if oldest.exists() {
+            fs::remove_file(&oldest)?;
+        }
+        for version in (1..SAVE_BACKUPS).rev() {
+            let backup = Self::backup_path(path, version);
+            if backup.exists() {
+                fs::rename(&backup, Self::backup_path(path, version + 1))?;
+            }
+        }
+        let latest = Self::backup_path(path, 1);
+        debug!("linking {} to {}", path.display(), latest.display());
+        if let Err(e) = fs::hard_link(path, &latest) {
+            debug!("could not link save ({}); copying it", e);
+            fs::copy(path, &latest)?;
+        }
+        Ok(())
+    }
+
+    /// Flush the entries of `dir` to the disk, so that renames in it survive a crash
+    #[cfg(unix)]
+    fn sync_dir(dir: &Path) -> SavedGameResult<()> {
+        File::open(dir)?.sync_all()?;
+        Ok(())
+    }
+
+    /// Directories can't be opened on non-unix systems, where renames are flushed with the file
+    #[cfg(not(unix))]
+    fn sync_dir(_dir: &Path) -> SavedGameResult<()> {
+        Ok(())
+    }
+
+    /// Path of the `version`-th most recent backup of the save at `path`
+    fn backup_path(path: &Path, version: usize) -> PathBuf {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        Self::backups_dir(path).join(format!("{}.{}", name, version))
+    }
+
+    /// Backups dir for the save at `path`
+    fn backups_dir(path: &Path) -> PathBuf {
+        path.parent().unwrap_or(Path::new("")).join(BACKUPS_DIR)
+    }
+
     /// Returns the list of available saved games
     pub fn saved_games(games_dir: &Path) -> SavedGameResult<Vec<PathBuf>> {
         debug!("scanning content of {}", games_dir.display());
@@ -70,6 +388,13 @@ impl SavedGameFiles {
             .flatten()
             .filter(|x| x.path().is_file())
             .map(|x| x.path())
+            .filter(|x| {
+                let is_save = Self::is_save(x);
+                if !is_save {
+                    debug!("skipping {}: not a saved game", x.display());
+                }
+                is_save
+            })
             .collect();
         Ok(files)
     }
@@ -82,6 +407,7 @@ mod test {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::game::session::{Action, ExploreAction};
 
     #[test]
     fn should_save_game() {
@@ -90,6 +416,61 @@ mod test {
         assert!(SavedGameFiles::save_game("mygame", games_dir.path(), &session).is_ok());
     }
 
+    #[test]
+    fn should_not_overwrite_existing_game() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        assert!(matches!(
+            SavedGameFiles::save_game("mygame", games_dir.path(), &session),
+            Err(SavedGameError::AlreadyExists(name)) if name == "mygame"
+        ));
+        assert!(SavedGameFiles::overwrite_game("mygame", games_dir.path(), &session).is_ok());
+        // the autosave is always overwritten
+        assert!(SavedGameFiles::autosave(games_dir.path(), &session).is_ok());
+        assert!(SavedGameFiles::autosave(games_dir.path(), &session).is_ok());
+        assert!(games_dir.path().join(AUTOSAVE_NAME).is_file());
+    }
+
+    #[test]
+    fn should_validate_save_name() {
+        assert!(SavedGameFiles::validate_name("my game_2-final").is_ok());
+        assert!(matches!(
+            SavedGameFiles::validate_name(""),
+            Err(SavedGameError::EmptyName)
+        ));
+        assert!(matches!(
+            SavedGameFiles::validate_name(&"a".repeat(MAX_SAVE_NAME_LEN + 1)),
+            Err(SavedGameError::NameTooLong)
+        ));
+        assert!(matches!(
+            SavedGameFiles::validate_name("../../.bashrc"),
+            Err(SavedGameError::InvalidNameChar('.'))
+        ));
+        assert!(matches!(
+            SavedGameFiles::validate_name("/etc/passwd"),
+            Err(SavedGameError::InvalidNameChar('/'))
+        ));
+        assert!(matches!(
+            SavedGameFiles::validate_name("AutoSave"),
+            Err(SavedGameError::ReservedName(_))
+        ));
+        for name in ["nul", "CON", "Prn", "aUx", "COM1", "com9", "Lpt1", "LPT9"] {
+            assert!(matches!(
+                SavedGameFiles::validate_name(name),
+                Err(SavedGameError::ReservedName(_))
+            ));
+        }
+        assert!(SavedGameFiles::validate_name("com10").is_ok());
+        assert!(SavedGameFiles::validate_name("lpt").is_ok());
+        let games_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            SavedGameFiles::save_game("../mygame", games_dir.path(), &Session::mock()),
+            Err(SavedGameError::InvalidNameChar('.'))
+        ));
+    }
+
     #[test]
     fn should_load_game() {
         let session = Session::mock();
@@ -104,6 +485,193 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_read_save_header() {
+        let mut session = Session::mock();
+        session.play_turn(Action::Explore(ExploreAction::ChangeRoom(1)));
+        let games_dir = TempDir::new().unwrap();
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        let path = games_dir.path().join("mygame");
+        let header = SavedGameFiles::read_header(&path).unwrap();
+        assert_eq!(header.name, "mygame");
+        assert_eq!(header.seed, "test");
+        assert_eq!(header.difficulty, session.difficulty());
+        assert_eq!(header.turn, 1);
+        assert_eq!(header.health, session.player().health());
+        assert_eq!(header.max_health, session.player().max_health());
+        assert_eq!(header.timestamp, session.stats().last_turn);
+        assert_eq!(header.version, Version::CURRENT);
+        // the body is still loaded after the header
+        let loaded = SavedGameFiles::load_game(&path).unwrap();
+        assert_eq!(loaded.stats(), session.stats());
+        assert_eq!(loaded.player(), session.player());
+    }
+
+    #[test]
+    fn should_read_header_of_save_without_header() {
+        let header = SavedGameFiles::read_header(Path::new("tests/test-savegame")).unwrap();
+        assert_eq!(header.name, "test-savegame");
+        assert_eq!(header.seed, "test");
+        assert_eq!(header.turn, 0);
+        assert_eq!(header.health, 5);
+        assert_eq!(header.version, Version::V010);
+    }
+
+    #[test]
+    fn should_skip_files_which_are_not_saves() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        let not_a_save = games_dir.path().join("notes.txt");
+        std::fs::write(&not_a_save, "remember to buy milk").unwrap();
+        std::fs::write(games_dir.path().join("empty"), "").unwrap();
+        assert_eq!(
+            SavedGameFiles::saved_games(games_dir.path()).unwrap(),
+            vec![games_dir.path().join("mygame")]
+        );
+        assert!(matches!(
+            SavedGameFiles::read_header(&not_a_save),
+            Err(SavedGameError::NotASave)
+        ));
+    }
+
+    #[test]
+    fn should_keep_backups_of_saved_game() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        let path = games_dir.path().join("mygame");
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        assert!(SavedGameFiles::backups(&path).is_empty());
+        for _ in 0..SAVE_BACKUPS + 2 {
+            SavedGameFiles::overwrite_game("mygame", games_dir.path(), &session)
+                .expect("failed to save game");
+        }
+        assert_eq!(SavedGameFiles::backups(&path).len(), SAVE_BACKUPS);
+        // no temporary file is left behind
+        assert!(!games_dir
+            .path()
+            .join(BACKUPS_DIR)
+            .join("mygame.tmp")
+            .exists());
+        assert_eq!(
+            SavedGameFiles::saved_games(games_dir.path()).unwrap(),
+            vec![path]
+        );
+    }
+
+    #[test]
+    fn should_keep_saved_game_while_rotating_backups() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        let path = games_dir.path().join("mygame");
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        for _ in 0..SAVE_BACKUPS + 1 {
+            let save = fs::read(&path).unwrap();
+            SavedGameFiles::rotate_backups(&path).unwrap();
+            // the save is untouched and the most recent backup is identical to it
+            assert_eq!(fs::read(&path).unwrap(), save);
+            assert_eq!(
+                fs::read(SavedGameFiles::backup_path(&path, 1)).unwrap(),
+                save
+            );
+            SavedGameFiles::overwrite_game("mygame", games_dir.path(), &session)
+                .expect("failed to save game");
+            assert!(SavedGameFiles::load_game(&path).is_ok());
+        }
+        assert_eq!(SavedGameFiles::backups(&path).len(), SAVE_BACKUPS);
+    }
+
+    #[test]
+    fn should_discard_autosave() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        let path = games_dir.path().join(AUTOSAVE_NAME);
+        SavedGameFiles::autosave(games_dir.path(), &session).expect("failed to autosave");
+        SavedGameFiles::autosave(games_dir.path(), &session).expect("failed to autosave");
+        assert_eq!(SavedGameFiles::backups(&path).len(), 1);
+        SavedGameFiles::discard_autosave(games_dir.path()).unwrap();
+        assert!(!path.exists());
+        assert!(SavedGameFiles::backups(&path).is_empty());
+        // discarding a missing autosave is fine
+        assert!(SavedGameFiles::discard_autosave(games_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn should_not_recover_finished_game() {
+        let games_dir = TempDir::new().unwrap();
+        let path = games_dir.path().join(AUTOSAVE_NAME);
+        // the autosave of a finished run is discarded
+        let finished = Session::new(Some("finished".to_string()), Difficulty::Hard);
+        SavedGameFiles::autosave(games_dir.path(), &finished).expect("failed to autosave");
+        SavedGameFiles::discard_autosave(games_dir.path()).unwrap();
+        assert!(SavedGameFiles::load_game(&path).is_err());
+        assert!(SavedGameFiles::restore_backup(&path).is_err());
+        // the new run is autosaved as soon as it starts
+        let session = Session::new(Some("new".to_string()), Difficulty::Easy);
+        SavedGameFiles::autosave(games_dir.path(), &session).expect("failed to autosave");
+        let header = SavedGameFiles::read_header(&path).unwrap();
+        assert_eq!(header.seed, session.maze_seed());
+        assert!(SavedGameFiles::backups(&path).is_empty());
+    }
+
+    #[test]
+    fn should_restore_backup() {
+        let session = Session::mock();
+        let games_dir = TempDir::new().unwrap();
+        let path = games_dir.path().join("mygame");
+        SavedGameFiles::save_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        assert!(matches!(
+            SavedGameFiles::restore_backup(&path),
+            Err(SavedGameError::NoBackup)
+        ));
+        SavedGameFiles::overwrite_game("mygame", games_dir.path(), &session)
+            .expect("failed to save game");
+        // corrupt the save
+        std::fs::write(&path, "{\"maze\":").unwrap();
+        assert!(SavedGameFiles::load_game(&path).is_err());
+        assert_eq!(
+            SavedGameFiles::restore_backup(&path).unwrap().maze_seed(),
+            session.maze_seed()
+        );
+        assert!(SavedGameFiles::load_game(&path).is_ok());
+        // the save is replaced with a copy of the backup, which is kept
+        let backup = SavedGameFiles::backup_path(&path, 1);
+        assert_eq!(fs::read(&path).unwrap(), fs::read(&backup).unwrap());
+        assert!(!games_dir
+            .path()
+            .join(BACKUPS_DIR)
+            .join("mygame.tmp")
+            .exists());
+    }
+
+    #[test]
+    fn should_load_and_migrate_fixture_game() {
+        let session = SavedGameFiles::load_game(Path::new("tests/test-savegame")).unwrap();
+        assert_eq!(session.migrated_from(), Some(Version::V010));
+        assert_eq!(session.maze_seed(), "test");
+        assert_eq!(session.visited_rooms(), 1);
+        assert_eq!(session.player().health(), 5);
+    }
+
+    #[test]
+    fn should_not_load_unsupported_game_version() {
+        let games_dir = TempDir::new().unwrap();
+        let mut path = games_dir.path().to_path_buf();
+        path.push("mygame");
+        std::fs::write(&path, r#"{"version":"V990"}"#).unwrap();
+        assert!(matches!(
+            SavedGameFiles::load_game(&path),
+            Err(SavedGameError::Migration(
+                MigrationError::UnsupportedVersion(_)
+            ))
+        ));
+    }
+
     #[test]
     fn should_get_games_list() {
         let session = Session::mock();